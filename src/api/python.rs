@@ -125,6 +125,8 @@ impl<'py> IntoPyDict<'py> for PrintOptions {
         dict.set_item("hide_all_namespaces", self.hide_all_namespaces)?;
         dict.set_item("color_namespace", self.color_namespace)?;
         dict.set_item("max_terms", self.max_terms)?;
+        dict.set_item("pretty_derivatives", self.pretty_derivatives)?;
+        dict.set_item("latex_cdot", self.latex_cdot)?;
         Ok(dict.into())
     }
 }
@@ -1759,7 +1761,8 @@ impl PythonTransformer {
             latex = false,
             precision = None,
             show_namespaces = false,
-            max_terms = None)
+            max_terms = None,
+            pretty_derivatives = false)
         )]
     pub fn print(
         &self,
@@ -1778,6 +1781,7 @@ impl PythonTransformer {
         precision: Option<usize>,
         show_namespaces: bool,
         max_terms: Option<usize>,
+        pretty_derivatives: bool,
     ) -> PyResult<PythonTransformer> {
         return append_transformer!(
             self,
@@ -1800,6 +1804,8 @@ impl PythonTransformer {
                 color_namespace: true,
                 hide_namespace: Some("python"),
                 max_terms,
+                pretty_derivatives,
+                latex_cdot: false,
             },)
         );
     }
@@ -2962,6 +2968,83 @@ impl PythonExpression {
         Atom::new_var(Atom::LOG).into()
     }
 
+    /// The built-in two-argument arctangent function `atan2(y, x)`.
+    #[classattr]
+    #[pyo3(name = "ATAN2")]
+    pub fn atan2() -> PythonExpression {
+        Atom::new_var(Atom::ATAN2).into()
+    }
+
+    /// The built-in function `max` that yields the largest of its arguments.
+    #[classattr]
+    #[pyo3(name = "MAX")]
+    pub fn max() -> PythonExpression {
+        Atom::new_var(Atom::MAX).into()
+    }
+
+    /// The built-in function `min` that yields the smallest of its arguments.
+    #[classattr]
+    #[pyo3(name = "MIN")]
+    pub fn min() -> PythonExpression {
+        Atom::new_var(Atom::MIN).into()
+    }
+
+    /// The built-in function `integrate(f, x)` that represents an unevaluated integral.
+    #[classattr]
+    #[pyo3(name = "INTEGRATE")]
+    pub fn integrate_symbol() -> PythonExpression {
+        Atom::new_var(Atom::INTEGRATE).into()
+    }
+
+    /// The built-in tangent function.
+    #[classattr]
+    #[pyo3(name = "TAN")]
+    pub fn tan_symbol() -> PythonExpression {
+        Atom::new_var(Atom::TAN).into()
+    }
+
+    /// The built-in hyperbolic sine function.
+    #[classattr]
+    #[pyo3(name = "SINH")]
+    pub fn sinh_symbol() -> PythonExpression {
+        Atom::new_var(Atom::SINH).into()
+    }
+
+    /// The built-in hyperbolic cosine function.
+    #[classattr]
+    #[pyo3(name = "COSH")]
+    pub fn cosh_symbol() -> PythonExpression {
+        Atom::new_var(Atom::COSH).into()
+    }
+
+    /// The built-in hyperbolic tangent function.
+    #[classattr]
+    #[pyo3(name = "TANH")]
+    pub fn tanh_symbol() -> PythonExpression {
+        Atom::new_var(Atom::TANH).into()
+    }
+
+    /// The built-in inverse sine function.
+    #[classattr]
+    #[pyo3(name = "ASIN")]
+    pub fn asin_symbol() -> PythonExpression {
+        Atom::new_var(Atom::ASIN).into()
+    }
+
+    /// The built-in inverse cosine function.
+    #[classattr]
+    #[pyo3(name = "ACOS")]
+    pub fn acos_symbol() -> PythonExpression {
+        Atom::new_var(Atom::ACOS).into()
+    }
+
+    /// The built-in inverse tangent function.
+    #[classattr]
+    #[pyo3(name = "ATAN")]
+    pub fn atan_symbol() -> PythonExpression {
+        Atom::new_var(Atom::ATAN).into()
+    }
+
     /// Return all defined symbol names (function names and variables).
     #[classmethod]
     pub fn get_all_symbol_names(_cls: &Bound<'_, PyType>) -> PyResult<Vec<String>> {
@@ -3082,7 +3165,8 @@ impl PythonExpression {
             latex = false,
             precision = None,
             show_namespaces = false,
-            max_terms = Some(100))
+            max_terms = Some(100),
+            pretty_derivatives = false)
         )]
     pub fn format(
         &self,
@@ -3101,6 +3185,7 @@ impl PythonExpression {
         precision: Option<usize>,
         show_namespaces: bool,
         max_terms: Option<usize>,
+        pretty_derivatives: bool,
     ) -> PyResult<String> {
         Ok(format!(
             "{}",
@@ -3125,6 +3210,8 @@ impl PythonExpression {
                     color_namespace: true,
                     hide_namespace: Some("python"),
                     max_terms,
+                    pretty_derivatives,
+                    latex_cdot: false,
                 },
             )
         ))
@@ -5894,7 +5981,8 @@ impl PythonSeries {
             latex = false,
             precision = None,
             show_namespaces = false,
-            max_terms = None)
+            max_terms = None,
+            pretty_derivatives = false)
         )]
     pub fn format(
         &self,
@@ -5913,6 +6001,7 @@ impl PythonSeries {
         precision: Option<usize>,
         show_namespaces: bool,
         max_terms: Option<usize>,
+        pretty_derivatives: bool,
     ) -> PyResult<String> {
         Ok(format!(
             "{}",
@@ -5936,6 +6025,8 @@ impl PythonSeries {
                     color_namespace: true,
                     hide_namespace: Some("python"),
                     max_terms,
+                    pretty_derivatives,
+                    latex_cdot: false,
                 },
                 PrintState::new()
             )
@@ -6413,7 +6504,8 @@ impl PythonPolynomial {
             latex = false,
             precision = None,
             show_namespaces = false,
-            max_terms = None)
+            max_terms = None,
+            pretty_derivatives = false)
         )]
     pub fn format(
         &self,
@@ -6432,6 +6524,7 @@ impl PythonPolynomial {
         precision: Option<usize>,
         show_namespaces: bool,
         max_terms: Option<usize>,
+        pretty_derivatives: bool,
     ) -> PyResult<String> {
         Ok(self.poly.format_string(
             &PrintOptions {
@@ -6453,6 +6546,8 @@ impl PythonPolynomial {
                 color_namespace: true,
                 hide_namespace: Some("python"),
                 max_terms,
+                pretty_derivatives,
+                latex_cdot: false,
             },
             PrintState::new(),
         ))
@@ -7319,7 +7414,8 @@ impl PythonFiniteFieldPolynomial {
             latex = false,
             precision = None,
             show_namespaces = false,
-            max_terms = None)
+            max_terms = None,
+            pretty_derivatives = false)
         )]
     pub fn format(
         &self,
@@ -7338,6 +7434,7 @@ impl PythonFiniteFieldPolynomial {
         precision: Option<usize>,
         show_namespaces: bool,
         max_terms: Option<usize>,
+        pretty_derivatives: bool,
     ) -> PyResult<String> {
         Ok(self.poly.format_string(
             &PrintOptions {
@@ -7359,6 +7456,8 @@ impl PythonFiniteFieldPolynomial {
                 color_namespace: true,
                 hide_namespace: Some("python"),
                 max_terms,
+                pretty_derivatives,
+                latex_cdot: false,
             },
             PrintState::new(),
         ))
@@ -8084,7 +8183,8 @@ impl PythonPrimeTwoPolynomial {
             latex = false,
             precision = None,
             show_namespaces = false,
-            max_terms = None)
+            max_terms = None,
+            pretty_derivatives = false)
         )]
     pub fn format(
         &self,
@@ -8103,6 +8203,7 @@ impl PythonPrimeTwoPolynomial {
         precision: Option<usize>,
         show_namespaces: bool,
         max_terms: Option<usize>,
+        pretty_derivatives: bool,
     ) -> PyResult<String> {
         Ok(self.poly.format_string(
             &PrintOptions {
@@ -8124,6 +8225,8 @@ impl PythonPrimeTwoPolynomial {
                 color_namespace: true,
                 hide_namespace: Some("python"),
                 max_terms,
+                pretty_derivatives,
+                latex_cdot: false,
             },
             PrintState::new(),
         ))
@@ -8722,7 +8825,8 @@ impl PythonGaloisFieldPrimeTwoPolynomial {
         latex = false,
         precision = None,
         show_namespaces = false,
-            max_terms = None)
+            max_terms = None,
+            pretty_derivatives = false)
     )]
     pub fn format(
         &self,
@@ -8741,6 +8845,7 @@ impl PythonGaloisFieldPrimeTwoPolynomial {
         precision: Option<usize>,
         show_namespaces: bool,
         max_terms: Option<usize>,
+        pretty_derivatives: bool,
     ) -> PyResult<String> {
         Ok(self.poly.format_string(
             &PrintOptions {
@@ -8762,6 +8867,8 @@ impl PythonGaloisFieldPrimeTwoPolynomial {
                 color_namespace: true,
                 hide_namespace: Some("python"),
                 max_terms,
+                pretty_derivatives,
+                latex_cdot: false,
             },
             PrintState::new(),
         ))
@@ -9431,7 +9538,8 @@ impl PythonGaloisFieldPolynomial {
             latex = false,
             precision = None,
             show_namespaces = false,
-            max_terms = None)
+            max_terms = None,
+            pretty_derivatives = false)
         )]
     pub fn format(
         &self,
@@ -9450,6 +9558,7 @@ impl PythonGaloisFieldPolynomial {
         precision: Option<usize>,
         show_namespaces: bool,
         max_terms: Option<usize>,
+        pretty_derivatives: bool,
     ) -> PyResult<String> {
         Ok(self.poly.format_string(
             &PrintOptions {
@@ -9471,6 +9580,8 @@ impl PythonGaloisFieldPolynomial {
                 color_namespace: true,
                 hide_namespace: Some("python"),
                 max_terms,
+                pretty_derivatives,
+                latex_cdot: false,
             },
             PrintState::new(),
         ))
@@ -10074,7 +10185,8 @@ impl PythonNumberFieldPolynomial {
         latex = false,
         precision = None,
             show_namespaces = false,
-            max_terms = None)
+            max_terms = None,
+            pretty_derivatives = false)
     )]
     pub fn format(
         &self,
@@ -10093,6 +10205,7 @@ impl PythonNumberFieldPolynomial {
         precision: Option<usize>,
         show_namespaces: bool,
         max_terms: Option<usize>,
+        pretty_derivatives: bool,
     ) -> PyResult<String> {
         Ok(self.poly.format_string(
             &PrintOptions {
@@ -10114,6 +10227,8 @@ impl PythonNumberFieldPolynomial {
                 color_namespace: true,
                 hide_namespace: Some("python"),
                 max_terms,
+                pretty_derivatives,
+                latex_cdot: false,
             },
             PrintState::new(),
         ))
@@ -11374,6 +11489,7 @@ impl PythonExpressionEvaluator {
     /// - `('pow', ('out', 0), ('param', 0), -1)` which means `out[0] = param[0]^-1`.
     /// - `('powf', ('out', 0), ('param', 0), ('param', 1))` which means `out[0] = param[0]^param[1]`.
     /// - `('fun', ('temp', 1), cos, ('param', 0))` which means `temp[1] = cos(param[0])`.
+    /// - `('fun', ('temp', 1), atan2, ('param', 0), ('param', 1))` which means `temp[1] = atan2(param[0], param[1])`.
     ///
     /// Examples
     /// --------
@@ -11468,6 +11584,20 @@ impl PythonExpressionEvaluator {
                         ],
                     )?);
                 }
+                Instruction::BinaryFun(o, f, a, b) => {
+                    v.push(PyTuple::new(
+                        py,
+                        &[
+                            "fun".into_pyobject(py)?.as_any(),
+                            slot_to_object(&o).into_pyobject(py)?.as_any(),
+                            PythonExpression::from(Atom::new_var(f.get_symbol()))
+                                .into_pyobject(py)?
+                                .as_any(),
+                            slot_to_object(&a).into_pyobject(py)?.as_any(),
+                            slot_to_object(&b).into_pyobject(py)?.as_any(),
+                        ],
+                    )?);
+                }
             }
         }
         Ok((
@@ -12093,7 +12223,8 @@ impl PythonMatrix {
             latex = false,
             precision = None,
             show_namespaces = false,
-            max_terms = None)
+            max_terms = None,
+            pretty_derivatives = false)
         )]
     pub fn format(
         &self,
@@ -12107,6 +12238,7 @@ impl PythonMatrix {
         precision: Option<usize>,
         show_namespaces: bool,
         max_terms: Option<usize>,
+        pretty_derivatives: bool,
     ) -> String {
         self.matrix.format_string(
             &PrintOptions {
@@ -12128,6 +12260,8 @@ impl PythonMatrix {
                 color_namespace: true,
                 hide_namespace: Some("python"),
                 max_terms,
+                pretty_derivatives,
+                latex_cdot: false,
             },
             PrintState::default(),
         )
@@ -48,6 +48,7 @@ use smartstring::{LazyCompact, SmartString};
 
 use crate::{
     coefficient::Coefficient,
+    domains::{integer::Integer, rational::Rational},
     parser::Token,
     printer::{AtomPrinter, PrintFunction, PrintOptions},
     state::{RecycledAtom, State, Workspace},
@@ -62,6 +63,7 @@ pub use self::representation::{
     NumView, Pow, PowView, Var, VarView,
 };
 use self::representation::{FunView, RawAtom};
+pub use crate::expand::ExpandIterator;
 
 /// A symbol with a namespace, and optional positional data (file and line) of its definition.
 /// Can be created with the [wrap_symbol!](crate::wrap_symbol) macro or by converting from a string that is
@@ -425,7 +427,7 @@ impl SymbolBuilder {
             && self.normalization_function.is_none()
             && self.print_function.is_none()
         {
-            Ok(State::get_state_mut().get_symbol(self.symbol))
+            Ok(State::get_or_insert_symbol(self.symbol))
         } else {
             State::get_state_mut().get_symbol_with_attributes(
                 self.symbol,
@@ -631,6 +633,13 @@ impl Symbol {
                 Atom::I => f.write_char('i'),
                 Atom::COS => f.write_str("\\cos"),
                 Atom::SIN => f.write_str("\\sin"),
+                Atom::TAN => f.write_str("\\tan"),
+                Atom::SINH => f.write_str("\\sinh"),
+                Atom::COSH => f.write_str("\\cosh"),
+                Atom::TANH => f.write_str("\\tanh"),
+                Atom::ASIN => f.write_str("\\arcsin"),
+                Atom::ACOS => f.write_str("\\arccos"),
+                Atom::ATAN => f.write_str("\\arctan"),
                 Atom::EXP => f.write_str("\\exp"),
                 Atom::LOG => f.write_str("\\log"),
                 _ => {
@@ -1046,6 +1055,69 @@ impl<'a> AtomView<'a> {
             AtomView::Add(a) => a.get_byte_size(),
         }
     }
+
+    /// Find the subexpressions where `self` and `other` differ. Arguments of functions,
+    /// products and sums with the same head and number of arguments are compared
+    /// pairwise, so that the reported differences are as deep as possible.
+    pub fn diff(&self, other: AtomView<'a>) -> Vec<DiffEntry<'a>> {
+        let mut path = vec![];
+        let mut out = vec![];
+        self.diff_impl(other, &mut path, &mut out);
+        out
+    }
+
+    fn diff_impl(&self, other: AtomView<'a>, path: &mut Vec<usize>, out: &mut Vec<DiffEntry<'a>>) {
+        if *self == other {
+            return;
+        }
+
+        match (self, other) {
+            (AtomView::Fun(f1), AtomView::Fun(f2))
+                if f1.get_symbol() == f2.get_symbol() && f1.get_nargs() == f2.get_nargs() =>
+            {
+                Self::diff_args(f1.iter(), f2.iter(), path, out);
+            }
+            (AtomView::Pow(p1), AtomView::Pow(p2)) => {
+                let (b1, e1) = p1.get_base_exp();
+                let (b2, e2) = p2.get_base_exp();
+                Self::diff_args([b1, e1].into_iter(), [b2, e2].into_iter(), path, out);
+            }
+            (AtomView::Mul(m1), AtomView::Mul(m2)) if m1.get_nargs() == m2.get_nargs() => {
+                Self::diff_args(m1.iter(), m2.iter(), path, out);
+            }
+            (AtomView::Add(a1), AtomView::Add(a2)) if a1.get_nargs() == a2.get_nargs() => {
+                Self::diff_args(a1.iter(), a2.iter(), path, out);
+            }
+            _ => out.push(DiffEntry {
+                path: path.clone(),
+                left: *self,
+                right: other,
+            }),
+        }
+    }
+
+    fn diff_args(
+        a: impl Iterator<Item = AtomView<'a>>,
+        b: impl Iterator<Item = AtomView<'a>>,
+        path: &mut Vec<usize>,
+        out: &mut Vec<DiffEntry<'a>>,
+    ) {
+        for (i, (a, b)) in a.zip(b).enumerate() {
+            path.push(i);
+            a.diff_impl(b, path, out);
+            path.pop();
+        }
+    }
+}
+
+/// A subexpression where two expressions differ, as found by [AtomView::diff].
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct DiffEntry<'a> {
+    /// The argument positions that lead from the root to the subexpressions.
+    /// The base and exponent of a power have position 0 and 1 respectively.
+    pub path: Vec<usize>,
+    pub left: AtomView<'a>,
+    pub right: AtomView<'a>,
 }
 
 /// A mathematical expression.
@@ -1149,6 +1221,32 @@ impl Atom {
     pub const I: Symbol = State::I;
     /// The mathematical constant `π`.
     pub const PI: Symbol = State::PI;
+    /// The two-argument arctangent function `atan2(y, x)`.
+    pub const ATAN2: Symbol = State::ATAN2;
+    /// The maximum of its (real) arguments. Its derivative evaluates to the
+    /// derivative of the active argument. At ties, the first maximal argument
+    /// is picked, which yields a subgradient as the function is not differentiable there.
+    pub const MAX: Symbol = State::MAX;
+    /// The minimum of its (real) arguments. Its derivative evaluates to the
+    /// derivative of the active argument. At ties, the first minimal argument
+    /// is picked, which yields a subgradient as the function is not differentiable there.
+    pub const MIN: Symbol = State::MIN;
+    /// The unevaluated integral `integrate(f, x)` of `f` with respect to `x`.
+    pub const INTEGRATE: Symbol = State::INTEGRATE;
+    /// The tangent function.
+    pub const TAN: Symbol = State::TAN;
+    /// The hyperbolic sine function.
+    pub const SINH: Symbol = State::SINH;
+    /// The hyperbolic cosine function.
+    pub const COSH: Symbol = State::COSH;
+    /// The hyperbolic tangent function.
+    pub const TANH: Symbol = State::TANH;
+    /// The inverse sine function.
+    pub const ASIN: Symbol = State::ASIN;
+    /// The inverse cosine function.
+    pub const ACOS: Symbol = State::ACOS;
+    /// The inverse tangent function.
+    pub const ATAN: Symbol = State::ATAN;
 
     /// Exponentiate the atom.
     pub fn exp(&self) -> Atom {
@@ -1286,6 +1384,18 @@ impl Atom {
         Workspace::get_local().with(|ws| Token::parse(&input.data)?.to_atom(&input, ws))
     }
 
+    /// Create the number zero.
+    #[inline]
+    pub fn zero() -> Atom {
+        Atom::Zero
+    }
+
+    /// Create the number one.
+    #[inline]
+    pub fn one() -> Atom {
+        Num::new(1.into()).into()
+    }
+
     #[inline]
     pub fn new_var(id: Symbol) -> Atom {
         Var::new(id).into()
@@ -1301,6 +1411,19 @@ impl Atom {
         }
     }
 
+    /// Create a new rational number `num/den`, reduced to lowest terms.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use symbolica::{atom::Atom, parse};
+    /// assert_eq!(Atom::rational(2, 4), parse!("1/2").unwrap());
+    /// ```
+    #[inline]
+    pub fn rational<T: Into<Integer>>(num: T, den: T) -> Atom {
+        Atom::new_num(Rational::from((num, den)))
+    }
+
     #[inline]
     pub fn is_zero(&self) -> bool {
         self.as_view().is_zero()
@@ -2346,10 +2469,104 @@ impl AsRef<Atom> for Atom {
 #[cfg(test)]
 mod test {
     use crate::{
-        atom::{Atom, AtomCore},
+        atom::{Atom, AtomCore, AtomView},
+        coefficient::CoefficientView,
         function,
+        state::Workspace,
     };
 
+    #[test]
+    fn zero_one() {
+        assert_eq!(Atom::one() + Atom::one(), parse!("2").unwrap());
+        assert_eq!(Atom::zero(), Atom::new_num(0));
+        assert!(Atom::zero().is_zero());
+        assert!(Atom::one().is_one());
+    }
+
+    #[test]
+    fn children() {
+        let f = parse!("f(v1,v2)").unwrap();
+        let c: Vec<_> = f.as_view().children().map(|x| x.to_owned()).collect();
+        assert_eq!(c, vec![parse!("v1").unwrap(), parse!("v2").unwrap()]);
+
+        let p = parse!("v1^2").unwrap();
+        let c: Vec<_> = p.as_view().children().map(|x| x.to_owned()).collect();
+        assert_eq!(c, vec![parse!("v1").unwrap(), Atom::new_num(2)]);
+
+        assert_eq!(parse!("v1+v2*v3").unwrap().as_view().children().count(), 2);
+        assert_eq!(parse!("v1").unwrap().as_view().children().count(), 0);
+    }
+
+    #[test]
+    fn as_accessors() {
+        let n = parse!("3/4").unwrap();
+        let v = parse!("v1").unwrap();
+        let f = parse!("f1(v1,2)").unwrap();
+
+        assert_eq!(
+            n.as_view().as_number(),
+            Some(CoefficientView::Natural(3, 4))
+        );
+        assert_eq!(v.as_view().as_number(), None);
+
+        assert_eq!(v.as_view().as_variable(), Some(symbol!("v1")));
+        assert_eq!(f.as_view().as_variable(), None);
+
+        let (s, args) = f.as_view().as_function().unwrap();
+        assert_eq!(s, symbol!("f1"));
+        assert_eq!(
+            args.map(|x| x.to_owned()).collect::<Vec<_>>(),
+            vec![v.clone(), Atom::new_num(2)]
+        );
+        assert!(n.as_view().as_function().is_none());
+    }
+
+    #[test]
+    fn fold() {
+        let a = parse!("2*v1 + 3*v2").unwrap();
+        let mut nums = a.as_view().fold(vec![], |mut acc, x| {
+            if let AtomView::Num(_) = x {
+                acc.push(x.to_owned());
+            }
+            acc
+        });
+        nums.sort();
+        assert_eq!(nums, vec![Atom::new_num(2), Atom::new_num(3)]);
+
+        let count = a.as_view().fold(0, |acc, _| acc + 1);
+        assert_eq!(count, 7);
+    }
+
+    #[test]
+    fn map_children() {
+        let x = parse!("v1").unwrap();
+        let y = parse!("v2").unwrap();
+        let a = parse!("f1(v1,v1^2)").unwrap();
+
+        let mut out = Atom::new();
+        Workspace::get_local().with(|ws| {
+            a.as_view().map_children(
+                |c, o| {
+                    if c == x.as_view() {
+                        o.set_from_view(&y.as_view());
+                    } else {
+                        o.set_from_view(&c);
+                    }
+                },
+                ws,
+                &mut out,
+            );
+        });
+        assert_eq!(out, parse!("f1(v2,v1^2)").unwrap());
+
+        let (px, py) = (x.to_pattern(), y.to_pattern());
+        Workspace::get_local().with(|ws| {
+            a.as_view()
+                .map_children(|c, o| *o = c.replace(&px).with(&py), ws, &mut out);
+        });
+        assert_eq!(out, parse!("f1(v2,v2^2)").unwrap());
+    }
+
     #[test]
     fn parse_macro() {
         assert_eq!(
@@ -2363,7 +2580,7 @@ mod test {
         let x = parse!("v1+f1(v2)").unwrap();
         assert_eq!(
             format!("{:?}", x),
-            "AddView { data: [5, 17, 2, 13, 2, 1, 12, 3, 5, 0, 0, 0, 1, 42, 2, 1, 13] }"
+            "AddView { data: [5, 17, 2, 13, 2, 1, 23, 3, 5, 0, 0, 0, 1, 53, 2, 1, 24] }"
         );
         assert_eq!(
             x.get_all_symbols(true),
@@ -2374,6 +2591,32 @@ mod test {
         assert_eq!(x.as_view().get_byte_size(), 17);
     }
 
+    #[test]
+    fn diff() {
+        let a = parse!("x+y").unwrap();
+        let b = parse!("x+z").unwrap();
+
+        let d = a.as_view().diff(b.as_view());
+        assert_eq!(d.len(), 1);
+        assert_eq!(d[0].path, [1]);
+        assert_eq!(d[0].left, parse!("y").unwrap().as_view());
+        assert_eq!(d[0].right, parse!("z").unwrap().as_view());
+
+        let c = parse!("f(x,y^2)").unwrap();
+        let e = parse!("f(x,y^3)").unwrap();
+        let d = c.diff(&e);
+        assert_eq!(d[0].path, [1, 1]);
+
+        assert!(a.diff(&a).is_empty());
+    }
+
+    #[test]
+    fn rational() {
+        assert_eq!(Atom::rational(2, 4), parse!("1/2").unwrap());
+        assert_eq!(Atom::rational(-6, 3), Atom::new_num(-2));
+        assert_eq!(Atom::rational(0, 5), Atom::new_num(0));
+    }
+
     #[test]
     fn composition() {
         let v1 = parse!("v1").unwrap();
@@ -18,7 +18,7 @@ use crate::{
         rational_polynomial::{
             FromNumeratorAndDenominator, RationalPolynomial, RationalPolynomialField,
         },
-        EuclideanDomain, InternalOrdering,
+        EuclideanDomain, InternalOrdering, Ring,
     },
     evaluate::{EvalTree, EvaluationFn, ExpressionEvaluator, FunctionMap, OptimizationSettings},
     id::{
@@ -38,7 +38,7 @@ use std::sync::Arc;
 
 use super::{
     representation::{InlineNum, InlineVar},
-    Atom, AtomOrView, AtomView, KeyLookup, Symbol,
+    Atom, AtomOrView, AtomView, DiffEntry, ExpandIterator, KeyLookup, Symbol,
 };
 
 /// All core features of expressions, such as expansion and
@@ -77,6 +77,12 @@ pub trait AtomCore {
     /// collect(x + x * y + x^2, x) = x * (1+y) + x^2
     /// ```
     ///
+    /// The key `x` may also be a general subexpression, such as `(a+b)`, which is then
+    /// treated as an indeterminate.
+    ///
+    /// The input does not have to be expanded: products and positive powers are expanded
+    /// in `x` only, so that `x*(1+x)` becomes `x+x^2` while `(1+y)^2` is left untouched.
+    ///
     /// Use [collect_symbol](AtomCore::collect_symbol) to collect using the name of a function only.
     ///
     /// Both the *key* (the quantity collected in) and its coefficient can be mapped using
@@ -188,6 +194,46 @@ pub trait AtomCore {
         self.as_atom_view().coefficient_list::<E, _>(xs)
     }
 
+    /// Collect terms involving the same power of `x` in `xs`, where `xs` is a list of indeterminates,
+    /// using parallel execution with the thread pool `p`.
+    /// Return the list of key-coefficient pairs, which is identical to the output of
+    /// [coefficient_list](AtomCore::coefficient_list).
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use symbolica::{atom::AtomCore, parse};
+    /// let expr = parse!("x + x * y + x^2 + z + z^2").unwrap();
+    /// let x = parse!("x").unwrap();
+    /// let z = parse!("z").unwrap();
+    /// let pool = rayon::ThreadPoolBuilder::new().num_threads(4).build().unwrap();
+    /// let coeff_list = expr.coefficient_list_with_pool::<u8>(&[x, z], &pool);
+    /// assert_eq!(coeff_list.len(), 4);
+    /// ```
+    fn coefficient_list_with_pool<E: Exponent + Send + Sync>(
+        &self,
+        xs: &[impl AtomCore],
+        p: &ThreadPool,
+    ) -> Vec<(Atom, Atom)> {
+        self.as_atom_view()
+            .coefficient_list_with_pool::<E, _>(xs, p)
+    }
+
+    /// Get the constant term with respect to the variables `vars`, i.e., the sum
+    /// of all terms that do not depend on any of the variables.
+    /// The expression should be expanded in `vars`.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use symbolica::{atom::AtomCore, parse, symbol};
+    /// let expr = parse!("x^2 + 3*y + 5").unwrap();
+    /// assert_eq!(expr.constant_term(&[symbol!("x")]), parse!("3*y + 5").unwrap());
+    /// ```
+    fn constant_term(&self, vars: &[Symbol]) -> Atom {
+        self.as_atom_view().constant_term(vars)
+    }
+
     /// Collect terms involving the literal occurrence of `x`.
     ///
     /// # Example
@@ -302,6 +348,36 @@ pub trait AtomCore {
         self.as_atom_view().collect_num()
     }
 
+    /// Iterate over the terms of the expanded expression without constructing the
+    /// full expansion, which is useful for streaming the terms of large products.
+    /// Like terms are not merged.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use symbolica::{atom::AtomCore, parse};
+    /// let expr = parse!("(a + b) * (c + d)").unwrap();
+    /// assert_eq!(expr.expand_iter().count(), 4);
+    /// ```
+    fn expand_iter(&self) -> ExpandIterator {
+        self.as_atom_view().expand_iter()
+    }
+
+    /// Estimate the number of terms of the expanded expression without expanding it,
+    /// which is useful to decide whether an expansion is feasible. The estimate
+    /// ignores cancellations and saturates at `u128::MAX`.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use symbolica::{atom::AtomCore, parse};
+    /// let expr = parse!("(a + b)^3 * (c + d)").unwrap();
+    /// assert_eq!(expr.estimate_expanded_terms(), 8);
+    /// ```
+    fn estimate_expanded_terms(&self) -> u128 {
+        self.as_atom_view().estimate_expanded_terms()
+    }
+
     /// Expand an expression. The function [AtomCore::expand_via_poly] may be faster.
     ///
     /// # Example
@@ -386,6 +462,56 @@ pub trait AtomCore {
             .expand_into(var.into().as_ref().map(|x| x.as_atom_view()), out)
     }
 
+    /// Rewrite exponentials with an imaginary exponent using Euler's formula:
+    /// `exp(𝑖*x)` -> `cos(x)+𝑖*sin(x)`. Other exponentials are left unchanged.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use symbolica::{atom::{Atom, AtomCore}, parse};
+    /// let x = parse!("x").unwrap();
+    /// let i = Atom::new_var(Atom::I);
+    /// let expr = (&i * &x).exp();
+    /// assert_eq!(expr.expand_complex_exp(), x.cos() + &i * x.sin());
+    /// ```
+    fn expand_complex_exp(&self) -> Atom {
+        self.as_atom_view().expand_complex_exp()
+    }
+
+    /// Apply the angle-addition identities to sines and cosines of sums, for example
+    /// `sin(x+y)` -> `sin(x)*cos(y)+cos(x)*sin(y)`. Sums with more than two terms are
+    /// expanded recursively.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use symbolica::{atom::AtomCore, parse};
+    /// let expr = parse!("cos(x + y)").unwrap();
+    /// let r = parse!("cos(x)*cos(y) - sin(x)*sin(y)").unwrap();
+    /// assert_eq!(expr.expand_trig(), r);
+    /// ```
+    fn expand_trig(&self) -> Atom {
+        self.as_atom_view().expand_trig()
+    }
+
+    /// Simplify squares of sines and cosines using `sin(x)^2+cos(x)^2 = 1`, for example
+    /// `1-cos(x)^2` -> `sin(x)^2`. Only exact squares of `sin` and `cos` are rewritten;
+    /// higher powers and other trigonometric functions are left as they are.
+    ///
+    /// If a rewrite reduces the number of terms, the expanded result is returned.
+    /// Otherwise, the expression is returned unchanged and unexpanded.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use symbolica::{atom::AtomCore, parse};
+    /// let expr = parse!("sin(x)^2 + cos(x)^2 + y").unwrap();
+    /// assert_eq!(expr.simplify_trig(), parse!("1 + y").unwrap());
+    /// ```
+    fn simplify_trig(&self) -> Atom {
+        self.as_atom_view().simplify_trig()
+    }
+
     /// Distribute numbers in the expression, for example:
     /// `2*(x+y)` -> `2*x+2*y`.
     ///
@@ -432,6 +558,52 @@ pub trait AtomCore {
         self.as_atom_view().derivative(x)
     }
 
+    /// Take the derivative of the expression with respect to each variable in `vars`.
+    /// The result has the same length as `vars`.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use symbolica::{atom::{Atom, AtomCore}, parse, symbol};
+    /// let expr = parse!("x^2*y + 1").unwrap();
+    /// let grad = expr.gradient(&[symbol!("x"), symbol!("y"), symbol!("z")]);
+    /// assert_eq!(grad, vec![parse!("2*x*y").unwrap(), parse!("x^2").unwrap(), Atom::new_num(0)]);
+    /// ```
+    fn gradient(&self, vars: &[Symbol]) -> Vec<Atom> {
+        self.as_atom_view().gradient(vars)
+    }
+
+    /// Compute the Hessian, the matrix of second derivatives with respect to `vars`.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use symbolica::{atom::{Atom, AtomCore}, parse, symbol};
+    /// let expr = parse!("x^2*y").unwrap();
+    /// let h = expr.hessian(&[symbol!("x"), symbol!("y")]);
+    /// assert_eq!(h[0][1], parse!("2*x").unwrap());
+    /// assert_eq!(h[1][0], parse!("2*x").unwrap());
+    /// assert_eq!(h[1][1], Atom::new_num(0));
+    /// ```
+    fn hessian(&self, vars: &[Symbol]) -> Vec<Vec<Atom>> {
+        self.as_atom_view().hessian(vars)
+    }
+
+    /// Take a derivative of the expression with respect to `x` and
+    /// evaluate it at `x = point`.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use symbolica::{atom::{Atom, AtomCore}, parse, symbol};
+    /// let expr = parse!("x^2 + 2*x + 1").unwrap();
+    /// let slope = expr.derivative_at(symbol!("x"), Atom::new_num(3));
+    /// assert_eq!(slope, Atom::new_num(8));
+    /// ```
+    fn derivative_at<T: AtomCore>(&self, x: Symbol, point: T) -> Atom {
+        self.as_atom_view().derivative_at(x, point.as_atom_view())
+    }
+
     /// Take a derivative of the expression with respect to `x` and
     /// write the result in `out`.
     /// Returns `true` if the derivative is non-zero.
@@ -450,6 +622,22 @@ pub trait AtomCore {
         self.as_atom_view().derivative_into(x, out)
     }
 
+    /// Integrate the expression in `x`. Sums, constant multiples, powers of `x`,
+    /// `1/x` and `exp(a*x+b)` are supported. The integration constant is omitted.
+    /// Returns `None` if the expression cannot be integrated.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use symbolica::{atom::{Atom, AtomCore}, parse, symbol};
+    /// let expr = parse!("3*x^2 + 1/x + exp(2*x)").unwrap();
+    /// let r = expr.integrate(symbol!("x")).unwrap();
+    /// assert_eq!(r, parse!("x^3 + log(x) + exp(2*x)/2").unwrap());
+    /// ```
+    fn integrate(&self, x: Symbol) -> Option<Atom> {
+        self.as_atom_view().integrate(x)
+    }
+
     /// Series expand in `x` around `expansion_point` to depth `depth`.
     ///
     /// # Example
@@ -884,7 +1072,7 @@ pub trait AtomCore {
     /// let poly = expr.to_polynomial::<_,u8>(&Q, Some(var_map));
     /// assert_eq!(poly.to_expression(), parse!("x^2 + 2 * x + 1").unwrap());
     /// ```
-    fn to_polynomial<R: EuclideanDomain + ConvertToRing, E: Exponent>(
+    fn to_polynomial<R: Ring + ConvertToRing, E: Exponent>(
         &self,
         field: &R,
         var_map: impl Into<Option<Arc<Vec<Variable>>>>,
@@ -892,6 +1080,53 @@ pub trait AtomCore {
         self.as_atom_view().to_polynomial(field, var_map.into())
     }
 
+    /// Convert the atom to a polynomial in the variables `vars` only.
+    /// If the atom contains other indeterminates, they are all
+    /// returned as an error.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use std::sync::Arc;
+    /// use symbolica::{atom::AtomCore, parse, symbol};
+    /// use symbolica::domains::integer::Z;
+    /// let var_map = Arc::new(vec![symbol!("x").into()]);
+    /// let expr = parse!("x^2 + 2*x*y + z").unwrap();
+    /// let err = expr.to_polynomial_in::<_, u8>(&Z, &var_map).unwrap_err();
+    /// assert_eq!(err.len(), 2);
+    /// ```
+    fn to_polynomial_in<R: EuclideanDomain + ConvertToRing, E: Exponent>(
+        &self,
+        field: &R,
+        vars: &Arc<Vec<Variable>>,
+    ) -> Result<MultivariatePolynomial<R, E>, Vec<Variable>> {
+        self.as_atom_view().to_polynomial_in(field, vars)
+    }
+
+    /// Convert an expanded atom to a polynomial, optionally in the variable ordering
+    /// specified by `var_map`. New variables are added to the variable map.
+    /// An error is returned instead of a panic if the atom is not an expanded
+    /// polynomial or if an exponent does not fit in `E`.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use symbolica::{atom::AtomCore, parse};
+    /// use symbolica::domains::integer::Z;
+    /// let expr = parse!("x^2 + 2*x + 1").unwrap();
+    /// assert!(expr.try_to_polynomial::<_, u8>(&Z, None).is_ok());
+    ///
+    /// let expr = parse!("x^300").unwrap();
+    /// assert!(expr.try_to_polynomial::<_, u8>(&Z, None).is_err());
+    /// ```
+    fn try_to_polynomial<R: Ring + ConvertToRing, E: Exponent>(
+        &self,
+        field: &R,
+        var_map: impl Into<Option<Arc<Vec<Variable>>>>,
+    ) -> Result<MultivariatePolynomial<R, E>, &'static str> {
+        self.as_atom_view().try_to_polynomial(field, var_map.into())
+    }
+
     /// Convert the atom to a polynomial in specific variables.
     /// All other parts will be collected into the coefficient, which
     /// is a general expression.
@@ -951,6 +1186,22 @@ pub trait AtomCore {
             .to_rational_polynomial(field, out_field, var_map.into())
     }
 
+    /// Check if the expression is mathematically equal to `other` as a rational function,
+    /// which is a stronger check than structural equality. Non-polynomial parts, such as `f(x)`,
+    /// are treated as independent variables. Returns `false` if either expression
+    /// contains a non-rational number, such as a float.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use symbolica::{atom::AtomCore, parse};
+    /// let expr = parse!("(x^2 - 1) / (x - 1)").unwrap();
+    /// assert!(expr.is_equal_rational(parse!("x + 1").unwrap()));
+    /// ```
+    fn is_equal_rational<T: AtomCore>(&self, other: T) -> bool {
+        self.as_atom_view().is_equal_rational(other.as_atom_view())
+    }
+
     /// Convert the atom to a rational polynomial with factorized denominators, optionally in the variable ordering
     /// specified by `var_map`. If new variables are encountered, they are
     /// added to the variable map. Similarly, non-rational polynomial parts are automatically
@@ -1045,6 +1296,21 @@ pub trait AtomCore {
         self.as_atom_view().to_canonical_string()
     }
 
+    /// Print the atom in Mathematica's `FullForm`. Namespaces are dropped and
+    /// builtin functions and constants are mapped to their Mathematica counterparts.
+    /// Only output is supported.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use symbolica::{atom::AtomCore, parse};
+    /// let expr = parse!("sin(x)^(-1/2)").unwrap();
+    /// assert_eq!(expr.to_mathematica(), "Power[Sin[x], Rational[-1, 2]]");
+    /// ```
+    fn to_mathematica(&self) -> String {
+        self.as_atom_view().to_mathematica()
+    }
+
     /// Map the function `f` over all terms.
     ///
     /// # Example
@@ -1196,6 +1462,23 @@ pub trait AtomCore {
         self.as_atom_view().contains(s.as_atom_view())
     }
 
+    /// Find the subexpressions where `self` and `other` differ, together with the
+    /// argument positions that lead to them.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use symbolica::{atom::AtomCore, parse};
+    /// let a = parse!("x + y").unwrap();
+    /// let b = parse!("x + z").unwrap();
+    /// let diff = a.diff(&b);
+    /// assert_eq!(diff[0].path, [1]);
+    /// assert_eq!(diff[0].left, parse!("y").unwrap().as_view());
+    /// ```
+    fn diff<'b, T: AtomCore>(&'b self, other: &'b T) -> Vec<DiffEntry<'b>> {
+        self.as_atom_view().diff(other.as_atom_view())
+    }
+
     /// Check if the expression can be considered a polynomial in some variables, including
     /// redefinitions. For example `f(x)+y` is considered a polynomial in `f(x)` and `y`, whereas
     /// `f(x)+x` is not a polynomial.
@@ -1306,6 +1589,38 @@ pub trait AtomCore {
         self.as_atom_view().replace(pattern)
     }
 
+    /// Evaluate the expression symbolically at `x = value`, i.e., substitute
+    /// every occurrence of the variable `x` by `value` and simplify.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use symbolica::{atom::AtomCore, parse, symbol};
+    /// let expr = parse!("x^2 + x").unwrap();
+    /// let r = expr.eval_at(symbol!("x"), parse!("y+1").unwrap());
+    /// assert_eq!(r, parse!("(y+1)^2 + y + 1").unwrap());
+    /// ```
+    fn eval_at<T: AtomCore>(&self, x: Symbol, value: T) -> Atom {
+        self.as_atom_view().eval_at(x, value.as_atom_view())
+    }
+
+    /// Fill the placeholder `{name}` of a template with `value`. Placeholders are
+    /// written as `{name}` in the parser and are distinct from wildcards.
+    /// A `{` directly after an identifier is part of its name, e.g. `x_{1}` is a single symbol.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use symbolica::{atom::AtomCore, parse};
+    /// let template = parse!("a*{slot}+b").unwrap();
+    /// let r = template.fill_template("slot", parse!("x^2").unwrap());
+    /// assert_eq!(r, parse!("a*x^2+b").unwrap());
+    /// ```
+    fn fill_template<T: AtomCore>(&self, name: &str, value: T) -> Atom {
+        self.as_atom_view()
+            .fill_template(name, value.as_atom_view())
+    }
+
     /// Replace all occurrences of the patterns, where replacements are tested in the order that they are given.
     /// To repeatedly replace multiple patterns, wrap the call in [Atom::replace_map].
     ///
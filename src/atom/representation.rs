@@ -1656,6 +1656,118 @@ impl<'a> AtomView<'a> {
         }
     }
 
+    /// Get the coefficient if the atom is a number.
+    #[inline]
+    pub fn as_number(&self) -> Option<CoefficientView<'a>> {
+        if let AtomView::Num(n) = self {
+            Some(n.get_coeff_view())
+        } else {
+            None
+        }
+    }
+
+    /// Get the symbol if the atom is a variable.
+    #[inline]
+    pub fn as_variable(&self) -> Option<Symbol> {
+        if let AtomView::Var(v) = self {
+            Some(v.get_symbol())
+        } else {
+            None
+        }
+    }
+
+    /// Get the symbol and an iterator over the arguments if the atom is a function.
+    #[inline]
+    pub fn as_function(&self) -> Option<(Symbol, ListIterator<'a>)> {
+        if let AtomView::Fun(f) = self {
+            Some((f.get_symbol(), f.iter()))
+        } else {
+            None
+        }
+    }
+
+    /// Iterate over the immediate subexpressions: the arguments of a function,
+    /// the factors of a product, the terms of a sum or the base and exponent of a power.
+    /// Numbers and variables have no children.
+    #[inline]
+    pub fn children(&self) -> ListSliceIterator<'a> {
+        let slice = match self {
+            AtomView::Num(_) | AtomView::Var(_) => ListSlice {
+                data: &[],
+                length: 0,
+                slice_type: SliceType::Empty,
+            },
+            AtomView::Fun(f) => f.to_slice(),
+            AtomView::Pow(p) => p.to_slice(),
+            AtomView::Mul(m) => m.to_slice(),
+            AtomView::Add(a) => a.to_slice(),
+        };
+        slice.iter()
+    }
+
+    /// Fold over all subexpressions in post-order: the children of an atom
+    /// are visited before the atom itself.
+    pub fn fold<T>(&self, init: T, mut f: impl FnMut(T, AtomView<'a>) -> T) -> T {
+        self.fold_impl(init, &mut f)
+    }
+
+    fn fold_impl<T>(&self, init: T, f: &mut impl FnMut(T, AtomView<'a>) -> T) -> T {
+        let mut acc = init;
+        for c in self.children() {
+            acc = c.fold_impl(acc, f);
+        }
+        f(acc, *self)
+    }
+
+    /// Apply `f` to every immediate subexpression and rebuild the atom
+    /// from the results. The rebuilt atom is normalized and written in `out`.
+    /// Numbers and variables are copied unchanged.
+    pub fn map_children(
+        &self,
+        mut f: impl FnMut(AtomView<'a>, &mut Atom),
+        workspace: &Workspace,
+        out: &mut Atom,
+    ) {
+        let mut new = workspace.new_atom();
+        let mut child = workspace.new_atom();
+        match self {
+            AtomView::Num(_) | AtomView::Var(_) => {
+                out.set_from_view(self);
+                return;
+            }
+            AtomView::Fun(fun) => {
+                let nf = new.to_fun(fun.get_symbol());
+                for c in self.children() {
+                    f(c, &mut child);
+                    nf.add_arg(child.as_view());
+                }
+            }
+            AtomView::Pow(p) => {
+                let (base, exp) = p.get_base_exp();
+                let mut new_base = workspace.new_atom();
+                f(base, &mut new_base);
+                f(exp, &mut child);
+                new.to_pow(new_base.as_view(), child.as_view());
+            }
+            AtomView::Mul(_) => {
+                let nm = new.to_mul();
+                for c in self.children() {
+                    f(c, &mut child);
+                    nm.extend(child.as_view());
+                }
+            }
+            AtomView::Add(_) => {
+                let na = new.to_add();
+                for c in self.children() {
+                    f(c, &mut child);
+                    na.extend(child.as_view());
+                }
+            }
+        }
+
+        new.as_view().normalize(workspace, out);
+    }
+
     /// Export the atom and state to a binary stream. It can be loaded
     /// with [Atom::import].
     #[inline(always)]
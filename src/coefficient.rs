@@ -22,7 +22,7 @@ use rug::{integer::Order, ops::NegAssign};
 use smallvec::{smallvec, SmallVec};
 
 use crate::{
-    atom::{Atom, AtomView},
+    atom::{Atom, AtomView, Symbol},
     domains::{
         atom::AtomField,
         finite_field::{
@@ -48,6 +48,13 @@ pub trait ConvertToRing: Ring {
 
     /// Convert from a Symbolica `CoefficientView` to a Ring.
     fn element_from_coefficient_view(&self, number: CoefficientView<'_>) -> Self::Element;
+
+    /// Convert a symbol to an element of the ring, if the symbol
+    /// is part of the ring, such as the generator of a number field.
+    /// Other symbols become polynomial variables.
+    fn element_from_symbol(&self, _symbol: Symbol) -> Option<Self::Element> {
+        None
+    }
 }
 
 /// A coefficient that can appear in a Symbolica expression.
@@ -632,6 +639,8 @@ impl CoefficientView<'_> {
         }
     }
 
+    /// Raise the coefficient to the power `other`. The result is returned as `(b, e)`
+    /// and represents `b^e`, where `e` is one if the result is exact.
     pub fn pow(&self, other: &CoefficientView<'_>) -> (Coefficient, Coefficient) {
         // TODO: normalize 4^1/3 to 2^(2/3)?
         match (self, other) {
@@ -648,15 +657,11 @@ impl CoefficientView<'_> {
                 if n2 <= u32::MAX as i64 {
                     if let Some(pn) = n1.checked_pow(n2 as u32) {
                         if let Some(pd) = d1.checked_pow(n2 as u32) {
-                            // TODO: simplify 4^(1/2)
-                            return ((pn, pd).into(), Rational::from_unchecked(1, d2).into());
+                            return Self::root(Rational::from_unchecked(pn, pd), d2);
                         }
                     }
 
-                    (
-                        Rational::from_unchecked(n1, d1).pow(n2 as u64).into(),
-                        Rational::from_unchecked(1, d2).into(),
-                    )
+                    Self::root(Rational::from_unchecked(n1, d1).pow(n2 as u64), d2)
                 } else {
                     panic!("Power is too large: {}", n2);
                 }
@@ -686,15 +691,9 @@ impl CoefficientView<'_> {
 
                 if n2 < 0 {
                     let r = r.to_rat().clone().inv();
-                    (
-                        r.pow(n2.unsigned_abs()).into(),
-                        Rational::from_unchecked(1, d2).into(),
-                    )
+                    Self::root(r.pow(n2.unsigned_abs()), d2)
                 } else {
-                    (
-                        r.to_rat().pow(n2 as u64).into(),
-                        Rational::from_unchecked(1, d2).into(),
-                    )
+                    Self::root(r.to_rat().pow(n2 as u64), d2)
                 }
             }
             (&CoefficientView::Float(f), &CoefficientView::Natural(n2, d2)) => {
@@ -748,6 +747,23 @@ impl CoefficientView<'_> {
         }
     }
 
+    /// Compute `r^(1/n)` as `(b, e)`, representing `b^e`. If `r` is non-negative and
+    /// the root is rational, it is computed exactly and `e` is one.
+    fn root(r: Rational, n: i64) -> (Coefficient, Coefficient) {
+        if n > 1 && n <= u32::MAX as i64 && !r.is_negative() {
+            if let Some(num) = r.numerator_ref().nth_root(n as u32) {
+                if let Some(den) = r.denominator_ref().nth_root(n as u32) {
+                    return (
+                        Rational::from_unchecked(num, den).into(),
+                        Coefficient::one(),
+                    );
+                }
+            }
+        }
+
+        (r.into(), Rational::from_unchecked(1, n).into())
+    }
+
     pub fn is_integer(&self) -> bool {
         match self {
             CoefficientView::Natural(_, d) => *d == 1,
@@ -1,11 +1,18 @@
 use ahash::HashMap;
 
+use rayon::{prelude::*, ThreadPool};
+
 use crate::{
     atom::{Add, Atom, AtomCore, AtomOrView, AtomView, Symbol},
     coefficient::{Coefficient, CoefficientView},
-    domains::{integer::Z, rational::Q},
+    domains::{
+        atom::AtomField,
+        integer::{Integer, Z},
+        rational::{Rational, Q},
+    },
     poly::{factor::Factorize, polynomial::MultivariatePolynomial, Exponent},
     state::Workspace,
+    LicenseManager,
 };
 use std::sync::Arc;
 
@@ -18,6 +25,8 @@ impl<'a> AtomView<'a> {
     ///
     /// Both the *key* (the quantity collected in) and its coefficient can be mapped using
     /// `key_map` and `coeff_map` respectively.
+    ///
+    /// The input is expanded in `x` only, so it does not have to be expanded beforehand.
     pub(crate) fn collect<E: Exponent, T: AtomCore>(
         &self,
         x: T,
@@ -113,7 +122,50 @@ impl<'a> AtomView<'a> {
             .collect::<Vec<_>>();
 
         let p = self.to_polynomial_in_vars::<E>(&Arc::new(vars));
+        Self::polynomial_to_coefficient_list(p, xs)
+    }
+
+    /// Collect terms involving the same powers of `x` in `xs`, where `x` is an indeterminate,
+    /// using parallel execution with the thread pool `p`.
+    /// Return the list of key-coefficient pairs, in the same order as the single-threaded version.
+    pub(crate) fn coefficient_list_with_pool<E: Exponent + Send + Sync, T: AtomCore>(
+        &self,
+        xs: &[T],
+        p: &ThreadPool,
+    ) -> Vec<(Atom, Atom)> {
+        if !LicenseManager::is_licensed() {
+            return self.coefficient_list::<E, T>(xs);
+        }
+
+        let AtomView::Add(a) = self else {
+            return self.coefficient_list::<E, T>(xs);
+        };
+
+        let vars = Arc::new(
+            xs.iter()
+                .map(|x| x.as_atom_view().to_owned().into())
+                .collect::<Vec<_>>(),
+        );
+
+        // the coefficients of every term are independent, so the conversion
+        // can be done in parallel and merged afterwards
+        let args = a.iter().collect::<Vec<_>>();
+        let poly = p.install(|| {
+            args.par_iter()
+                .map(|arg| arg.to_polynomial_in_vars::<E>(&vars))
+                .reduce_with(|a, b| a + b)
+        });
 
+        match poly {
+            Some(poly) => Self::polynomial_to_coefficient_list(poly, xs),
+            None => vec![],
+        }
+    }
+
+    fn polynomial_to_coefficient_list<E: Exponent, T: AtomCore>(
+        p: MultivariatePolynomial<AtomField, E>,
+        xs: &[T],
+    ) -> Vec<(Atom, Atom)> {
         let mut coeffs = vec![];
         for t in p.into_iter() {
             let mut key = Atom::new_num(1);
@@ -130,6 +182,33 @@ impl<'a> AtomView<'a> {
         coeffs
     }
 
+    /// Get the sum of all terms that do not depend on any of the variables in `vars`.
+    pub(crate) fn constant_term(&self, vars: &[Symbol]) -> Atom {
+        let is_constant = |t: &AtomView| vars.iter().all(|v| !t.contains_symbol(*v));
+
+        let AtomView::Add(a) = self else {
+            return if is_constant(self) {
+                self.to_owned()
+            } else {
+                Atom::new_num(0)
+            };
+        };
+
+        Workspace::get_local().with(|ws| {
+            let mut add_h = ws.new_atom();
+            let add = add_h.to_add();
+            for t in a {
+                if is_constant(&t) {
+                    add.extend(t);
+                }
+            }
+
+            let mut out = Atom::new();
+            add_h.as_view().normalize(ws, &mut out);
+            out
+        })
+    }
+
     /// Collect terms involving the literal occurrence of `x`.
     pub fn coefficient_with_ws(&self, x: AtomView<'_>, workspace: &Workspace) -> Atom {
         let mut coeffs = workspace.new_atom();
@@ -468,6 +547,125 @@ impl<'a> AtomView<'a> {
         })
     }
 
+    /// Extract the greatest common divisor of the numerical coefficients of the terms of
+    /// an addition, writing the factored expression in `out` and returning the factor.
+    /// For example, `6*x + 9*y` will be transformed into `3*(2*x + 3*y)` and `3` is returned.
+    ///
+    /// Unlike [AtomView::collect_num], only the top-level addition is considered.
+    /// If a term has a coefficient that is not rational, such as a float or a finite
+    /// field element, the expression is left unchanged and `1` is returned.
+    pub fn factor_numeric_gcd(&self, out: &mut Atom) -> Coefficient {
+        let AtomView::Add(a) = self else {
+            out.set_from_view(self);
+            return Coefficient::one();
+        };
+
+        let mut gcd: Option<Rational> = None;
+        for arg in a {
+            let num = match arg {
+                AtomView::Num(n) => n.get_coeff_view().to_owned(),
+                AtomView::Mul(m) if m.has_coefficient() => m
+                    .iter()
+                    .find_map(|aa| {
+                        if let AtomView::Num(n) = aa {
+                            Some(n.get_coeff_view().to_owned())
+                        } else {
+                            None
+                        }
+                    })
+                    .unwrap(),
+                _ => Coefficient::one(),
+            };
+
+            let Coefficient::Rational(num) = num else {
+                out.set_from_view(self);
+                return Coefficient::one();
+            };
+
+            gcd = Some(match gcd {
+                Some(g) => g.gcd(&num),
+                None => num,
+            });
+        }
+
+        let gcd: Coefficient = gcd.unwrap_or_else(Rational::one).into();
+        if gcd.is_one() {
+            out.set_from_view(self);
+            return gcd;
+        }
+
+        Workspace::get_local().with(|ws| {
+            let v = ws.new_num(gcd.clone());
+            let mut r = ws.new_atom();
+            let ra = r.to_add();
+            let mut div = ws.new_atom();
+            for arg in a {
+                arg.div_with_ws_into(ws, v.as_view(), &mut div);
+                ra.extend(div.as_view());
+            }
+
+            let m = div.to_mul();
+            m.extend(r.as_view());
+            m.extend(v.as_view());
+            m.as_view().normalize(ws, out);
+        });
+
+        gcd
+    }
+
+    /// Multiply the terms of an addition by the least common multiple of the denominators
+    /// of their numerical coefficients, writing the result in `out` and returning the factor.
+    /// For example, `x/2 + y/3` will be transformed into `3*x + 2*y` and `6` is returned.
+    ///
+    /// Only the top-level addition is considered, as in [AtomView::factor_numeric_gcd].
+    pub fn clear_denominators(&self, out: &mut Atom) -> Coefficient {
+        let terms: Vec<_> = if let AtomView::Add(a) = self {
+            a.iter().collect()
+        } else {
+            vec![*self]
+        };
+
+        let mut lcm = Integer::one();
+        for t in &terms {
+            let num = match t {
+                AtomView::Num(n) => Some(n.get_coeff_view().to_owned()),
+                AtomView::Mul(m) if m.has_coefficient() => m.iter().find_map(|aa| {
+                    if let AtomView::Num(n) = aa {
+                        Some(n.get_coeff_view().to_owned())
+                    } else {
+                        None
+                    }
+                }),
+                _ => None,
+            };
+
+            if let Some(Coefficient::Rational(r)) = num {
+                lcm = lcm.lcm(r.denominator_ref());
+            }
+        }
+
+        if lcm.is_one() {
+            out.set_from_view(self);
+            return Coefficient::one();
+        }
+
+        let factor = Coefficient::from(lcm);
+        Workspace::get_local().with(|ws| {
+            let v = ws.new_num(factor.clone());
+            let mut r = ws.new_atom();
+            let ra = r.to_add();
+            let mut mul = ws.new_atom();
+            for t in terms {
+                t.mul_with_ws_into(ws, v.as_view(), &mut mul);
+                ra.extend(mul.as_view());
+            }
+
+            r.as_view().normalize(ws, out);
+        });
+
+        factor
+    }
+
     fn collect_num_impl(&self, ws: &Workspace, out: &mut Atom) -> bool {
         fn get_num(a: AtomView) -> Option<Coefficient> {
             match a {
@@ -777,6 +975,7 @@ impl<'a> AtomView<'a> {
 mod test {
     use crate::{
         atom::{representation::InlineVar, Atom, AtomCore},
+        coefficient::Coefficient,
         function, parse, symbol,
     };
 
@@ -816,6 +1015,49 @@ mod test {
         assert_eq!(out, ref_out);
     }
 
+    #[test]
+    fn factor_numeric_gcd() {
+        let input = parse!("6*v1+9*v2").unwrap();
+        let mut out = Atom::new();
+        let gcd = input.as_view().factor_numeric_gcd(&mut out);
+        assert_eq!(gcd, Coefficient::from(3));
+        assert_eq!(out, parse!("3*(2*v1+3*v2)").unwrap());
+
+        let input = parse!("6*v1+v2").unwrap();
+        let gcd = input.as_view().factor_numeric_gcd(&mut out);
+        assert!(gcd.is_one());
+        assert_eq!(out, input);
+
+        let input = parse!("0.5*v1+v2").unwrap();
+        let gcd = input.as_view().factor_numeric_gcd(&mut out);
+        assert!(gcd.is_one());
+        assert_eq!(out, input);
+    }
+
+    #[test]
+    fn clear_denominators() {
+        let input = parse!("v1/2+v2/3").unwrap();
+        let mut out = Atom::new();
+        let factor = input.as_view().clear_denominators(&mut out);
+        assert_eq!(factor, Coefficient::from(6));
+        assert_eq!(out, parse!("3*v1+2*v2").unwrap());
+
+        let input = parse!("2*v1+v2").unwrap();
+        let factor = input.as_view().clear_denominators(&mut out);
+        assert!(factor.is_one());
+        assert_eq!(out, input);
+    }
+
+    #[test]
+    fn collect_subexpression() {
+        let input = parse!("v3*(v1+v2) + v4*(v1+v2) + v5*(v1+v2)^2 + v6").unwrap();
+        let x = parse!("v1+v2").unwrap();
+
+        let r = input.collect::<u8>(x, None, None);
+        let res = parse!("(v3+v4)*(v1+v2) + v5*(v1+v2)^2 + v6").unwrap();
+        assert_eq!(r, res);
+    }
+
     #[test]
     fn coefficient_list() {
         let input = parse!("v1*(1+v3)+v1*5*v2+f1(5,v1)+2+v2^2+v1^2+v1^3").unwrap();
@@ -833,6 +1075,21 @@ mod test {
         assert_eq!(r, res);
     }
 
+    #[test]
+    fn coefficient_list_with_pool() {
+        let input =
+            parse!("v1*(1+v3)+v1*5*v2+f1(5,v1)+2+v2^2+v1^2+v1^3+v1^2*v2*v4+v3*v1^3").unwrap();
+        let xs = [parse!("v1").unwrap(), parse!("v2").unwrap()];
+
+        let pool = rayon::ThreadPoolBuilder::new()
+            .num_threads(4)
+            .build()
+            .unwrap();
+        let r = input.coefficient_list_with_pool::<i8>(&xs, &pool);
+
+        assert_eq!(r, input.coefficient_list::<i8>(&xs));
+    }
+
     #[test]
     fn collect() {
         let input = parse!("v1*(1+v3)+v1*5*v2+f1(5,v1)+2+v2^2+v1^2+v1^3").unwrap();
@@ -855,6 +1112,40 @@ mod test {
         assert_eq!(out, ref_out)
     }
 
+    #[test]
+    fn constant_term() {
+        let (v1, v2) = (symbol!("v1"), symbol!("v2"));
+        let input = parse!("v1^2 + 3*v2 + 5").unwrap();
+
+        assert_eq!(input.constant_term(&[v1]), parse!("3*v2 + 5").unwrap());
+        assert_eq!(input.constant_term(&[v1, v2]), Atom::new_num(5));
+        assert_eq!(
+            parse!("v1*v2").unwrap().constant_term(&[v1]),
+            Atom::new_num(0)
+        );
+        assert_eq!(
+            parse!("v2^2").unwrap().constant_term(&[v1]),
+            parse!("v2^2").unwrap()
+        );
+    }
+
+    #[test]
+    fn collect_unexpanded() {
+        let x = symbol!("v1");
+
+        let out = parse!("v1*(1+v1)")
+            .unwrap()
+            .collect::<i8>(InlineVar::new(x), None, None);
+        assert_eq!(out, parse!("v1+v1^2").unwrap());
+
+        // only expand in the collection variable
+        let out =
+            parse!("v1*(v1+v2)*(1+v2)^2")
+                .unwrap()
+                .collect::<i8>(InlineVar::new(x), None, None);
+        assert_eq!(out, parse!("v1^2*(1+v2)^2+v1*v2*(1+v2)^2").unwrap());
+    }
+
     #[test]
     fn collect_wrap() {
         let input = parse!("v1*(1+v3)+v1*5*v2+f1(5,v1)+2+v2^2+v1^2+v1^3").unwrap();
@@ -4,12 +4,13 @@ use std::{
 };
 
 use crate::{
-    atom::{Atom, AtomView, FunctionBuilder, Symbol},
+    atom::{Atom, AtomCore, AtomView, FunctionBuilder, Symbol},
     coefficient::{Coefficient, CoefficientView},
     combinatorics::CombinationWithReplacementIterator,
     domains::{atom::AtomField, integer::Integer, rational::Rational},
+    id::{MatchSettings, PatternAtomTreeIterator},
     poly::{series::Series, Variable},
-    state::Workspace,
+    state::{RecycledAtom, State, Workspace},
 };
 
 impl<'a> AtomView<'a> {
@@ -22,6 +23,238 @@ impl<'a> AtomView<'a> {
         })
     }
 
+    /// Take the derivative of the expression with respect to every variable in `vars`.
+    /// All derivatives are computed in a single traversal of the expression.
+    pub(crate) fn gradient(&self, vars: &[Symbol]) -> Vec<Atom> {
+        Workspace::get_local().with(|ws| {
+            let mut out: Vec<_> = vars.iter().map(|_| ws.new_atom()).collect();
+            self.gradient_with_ws_into(vars, ws, &mut out);
+            out.into_iter().map(|x| x.into_inner()).collect()
+        })
+    }
+
+    /// Take the derivative of the expression with respect to every variable in `vars`
+    /// and write the results in `out`, which has the same length as `vars`. The
+    /// derivatives of the arguments of sums, products and powers are computed once
+    /// for all variables.
+    /// Returns `true` if any of the derivatives is non-zero.
+    pub(crate) fn gradient_with_ws_into(
+        &self,
+        vars: &[Symbol],
+        workspace: &Workspace,
+        out: &mut [RecycledAtom],
+    ) -> bool {
+        match self {
+            AtomView::Num(_) => {
+                for o in out.iter_mut() {
+                    o.to_num(Coefficient::zero());
+                }
+                false
+            }
+            AtomView::Var(v) => {
+                let mut non_zero = false;
+                for (o, x) in out.iter_mut().zip(vars) {
+                    if v.get_symbol() == *x {
+                        o.to_num(1.into());
+                        non_zero = true;
+                    } else {
+                        o.to_num(Coefficient::zero());
+                    }
+                }
+                non_zero
+            }
+            AtomView::Fun(_) => {
+                // functions are derived for every variable separately, as their rules
+                // depend on the variable
+                let mut non_zero = false;
+                for (o, x) in out.iter_mut().zip(vars) {
+                    if self.contains_symbol(*x) {
+                        non_zero |= self.derivative_with_ws_into(*x, workspace, o);
+                    } else {
+                        o.to_num(Coefficient::zero());
+                    }
+                }
+                non_zero
+            }
+            AtomView::Pow(p) => {
+                let (base, exp) = p.get_base_exp();
+
+                let mut exp_der: Vec<_> = vars.iter().map(|_| workspace.new_atom()).collect();
+                let exp_der_non_zero = exp.gradient_with_ws_into(vars, workspace, &mut exp_der);
+
+                let mut base_der: Vec<_> = vars.iter().map(|_| workspace.new_atom()).collect();
+                let base_der_non_zero = base.gradient_with_ws_into(vars, workspace, &mut base_der);
+
+                if !exp_der_non_zero && !base_der_non_zero {
+                    for o in out.iter_mut() {
+                        o.to_num(Coefficient::zero());
+                    }
+                    return false;
+                }
+
+                // d(b^e) = e*b^(e-1)*db + b^e*log(b)*de, where the factors of db and de
+                // are shared by all variables
+                let mut base_factor = workspace.new_atom();
+                if base_der_non_zero {
+                    let mut new_exp = workspace.new_atom();
+                    if let AtomView::Num(n) = exp {
+                        new_exp.to_num(n.get_coeff_view() + -1);
+                    } else {
+                        let ao = new_exp.to_add();
+                        ao.extend(exp);
+                        ao.extend(workspace.new_num(-1).as_view());
+                    }
+
+                    let mut pow_h = workspace.new_atom();
+                    pow_h.to_pow(base, new_exp.as_view());
+
+                    let m = base_factor.to_mul();
+                    m.extend(exp);
+                    m.extend(pow_h.as_view());
+                }
+
+                let mut exp_factor = workspace.new_atom();
+                if exp_der_non_zero {
+                    let mut log_base = workspace.new_atom();
+                    log_base.to_fun(Atom::LOG).add_arg(base);
+
+                    let m = exp_factor.to_mul();
+                    m.extend(*self);
+                    m.extend(log_base.as_view());
+                }
+
+                let mut add_h = workspace.new_atom();
+                let mut mul_h = workspace.new_atom();
+                for ((o, db), de) in out.iter_mut().zip(&base_der).zip(&exp_der) {
+                    let add = add_h.to_add();
+                    for (d, f) in [(db, &base_factor), (de, &exp_factor)] {
+                        if !d.is_zero() {
+                            let m = mul_h.to_mul();
+                            m.extend(d.as_view());
+                            m.extend(f.as_view());
+                            add.extend(mul_h.as_view());
+                        }
+                    }
+
+                    add_h.as_view().normalize(workspace, o);
+                }
+
+                true
+            }
+            AtomView::Mul(args) => {
+                let factors: Vec<_> = args.iter().collect();
+
+                let mut ders = vec![];
+                for (i, arg) in factors.iter().enumerate() {
+                    let mut arg_der: Vec<_> = vars.iter().map(|_| workspace.new_atom()).collect();
+                    if arg.gradient_with_ws_into(vars, workspace, &mut arg_der) {
+                        ders.push((i, arg_der));
+                    }
+                }
+
+                if ders.is_empty() {
+                    for o in out.iter_mut() {
+                        o.to_num(Coefficient::zero());
+                    }
+                    return false;
+                }
+
+                let mut adds: Vec<_> = vars
+                    .iter()
+                    .map(|_| {
+                        let mut a = workspace.new_atom();
+                        a.to_add();
+                        a
+                    })
+                    .collect();
+
+                let mut cofactor = workspace.new_atom();
+                let mut mul_h = workspace.new_atom();
+                for (i, arg_der) in ders {
+                    // the product of the other factors is shared by all variables
+                    let c = cofactor.to_mul();
+                    for (j, f) in factors.iter().enumerate() {
+                        if j != i {
+                            c.extend(*f);
+                        }
+                    }
+
+                    for (add, d) in adds.iter_mut().zip(&arg_der) {
+                        if let Atom::Add(a) = add.deref_mut() {
+                            if !d.is_zero() {
+                                let m = mul_h.to_mul();
+                                m.extend(d.as_view());
+                                m.extend(cofactor.as_view());
+                                a.extend(mul_h.as_view());
+                            }
+                        }
+                    }
+                }
+
+                for (o, add) in out.iter_mut().zip(&adds) {
+                    add.as_view().normalize(workspace, o);
+                }
+                true
+            }
+            AtomView::Add(args) => {
+                let mut adds: Vec<_> = vars
+                    .iter()
+                    .map(|_| {
+                        let mut a = workspace.new_atom();
+                        a.to_add();
+                        a
+                    })
+                    .collect();
+
+                let mut arg_der: Vec<_> = vars.iter().map(|_| workspace.new_atom()).collect();
+                let mut non_zero = false;
+                for arg in args.iter() {
+                    if arg.gradient_with_ws_into(vars, workspace, &mut arg_der) {
+                        non_zero = true;
+                        for (add, d) in adds.iter_mut().zip(&arg_der) {
+                            if let Atom::Add(a) = add.deref_mut() {
+                                if !d.is_zero() {
+                                    a.extend(d.as_view());
+                                }
+                            }
+                        }
+                    }
+                }
+
+                if !non_zero {
+                    for o in out.iter_mut() {
+                        o.to_num(Coefficient::zero());
+                    }
+                    return false;
+                }
+
+                for (o, add) in out.iter_mut().zip(&adds) {
+                    add.as_view().normalize(workspace, o);
+                }
+                true
+            }
+        }
+    }
+
+    /// Compute the matrix of second derivatives with respect to `vars`.
+    /// Only the upper triangle is computed, as the matrix is symmetric.
+    pub(crate) fn hessian(&self, vars: &[Symbol]) -> Vec<Vec<Atom>> {
+        let mut hessian = vec![vec![Atom::new(); vars.len()]; vars.len()];
+        for (i, d) in self.gradient(vars).into_iter().enumerate() {
+            for (j, dd) in d.as_view().gradient(&vars[i..]).into_iter().enumerate() {
+                hessian[i + j][i] = dd.clone();
+                hessian[i][i + j] = dd;
+            }
+        }
+        hessian
+    }
+
+    /// Take a derivative of the expression with respect to `x` and
+    /// evaluate it at `x = point`.
+    pub(crate) fn derivative_at(&self, x: Symbol, point: AtomView) -> Atom {
+        self.derivative(x).replace(x).with(point.to_owned())
+    }
+
     /// Take a derivative of the expression with respect to `x` and
     /// write the result in `out`.
     /// Returns `true` if the derivative is non-zero.
@@ -54,6 +287,33 @@ impl<'a> AtomView<'a> {
                 }
             }
             AtomView::Fun(f_orig) => {
+                // apply the fundamental theorem of calculus to integrate(f, x) and
+                // move derivatives with respect to other variables under the integral sign
+                if f_orig.get_symbol() == Atom::INTEGRATE && f_orig.get_nargs() == 2 {
+                    let mut it = f_orig.iter();
+                    let (integrand, var) = (it.next().unwrap(), it.next().unwrap());
+
+                    if let AtomView::Var(v) = var {
+                        if v.get_symbol() == x {
+                            out.set_from_view(&integrand);
+                            return !out.is_zero();
+                        }
+
+                        let mut integrand_der = workspace.new_atom();
+                        if !integrand.derivative_with_ws_into(x, workspace, &mut integrand_der) {
+                            out.to_num(Coefficient::zero());
+                            return false;
+                        }
+
+                        let mut fun = workspace.new_atom();
+                        let ff = fun.to_fun(Atom::INTEGRATE);
+                        ff.add_arg(integrand_der.as_view());
+                        ff.add_arg(var);
+                        fun.as_view().normalize(workspace, out);
+                        return true;
+                    }
+                }
+
                 // detect if the function to derive is the derivative function itself
                 // if so, derive the last argument of the derivative function and set
                 // a flag to later accumulate previous derivatives
@@ -87,7 +347,20 @@ impl<'a> AtomView<'a> {
 
                 // derive special functions
                 if f.get_nargs() == 1
-                    && [Atom::EXP, Atom::LOG, Atom::SIN, Atom::COS].contains(&f.get_symbol())
+                    && [
+                        Atom::EXP,
+                        Atom::LOG,
+                        Atom::SIN,
+                        Atom::COS,
+                        Atom::TAN,
+                        Atom::SINH,
+                        Atom::COSH,
+                        Atom::TANH,
+                        Atom::ASIN,
+                        Atom::ACOS,
+                        Atom::ATAN,
+                    ]
+                    .contains(&f.get_symbol())
                 {
                     let mut fn_der = workspace.new_atom();
                     match f.get_symbol() {
@@ -116,6 +389,74 @@ impl<'a> AtomView<'a> {
                             m.extend(sin.as_view());
                             m.extend(n.as_view());
                         }
+                        Atom::SINH => {
+                            let p = fn_der.to_fun(Atom::COSH);
+                            p.add_arg(f.iter().next().unwrap());
+                        }
+                        Atom::COSH => {
+                            let p = fn_der.to_fun(Atom::SINH);
+                            p.add_arg(f.iter().next().unwrap());
+                        }
+                        Atom::TAN | Atom::TANH => {
+                            // 1 + tan(x)^2 and 1 - tanh(x)^2
+                            let mut n = workspace.new_atom();
+                            n.to_num(2.into());
+                            let mut sq = workspace.new_atom();
+                            sq.to_pow(*self, n.as_view());
+
+                            if f.get_symbol() == Atom::TANH {
+                                n.to_num((-1).into());
+                                let mut neg = workspace.new_atom();
+                                let m = neg.to_mul();
+                                m.extend(sq.as_view());
+                                m.extend(n.as_view());
+                                std::mem::swap(&mut sq, &mut neg);
+                            }
+
+                            n.to_num(1.into());
+                            let a = fn_der.to_add();
+                            a.extend(n.as_view());
+                            a.extend(sq.as_view());
+                        }
+                        Atom::ASIN | Atom::ACOS | Atom::ATAN => {
+                            // (1-u^2)^(-1/2), -(1-u^2)^(-1/2) and (1+u^2)^-1
+                            let mut n = workspace.new_atom();
+                            n.to_num(2.into());
+                            let mut sq = workspace.new_atom();
+                            sq.to_pow(f.iter().next().unwrap(), n.as_view());
+
+                            if f.get_symbol() != Atom::ATAN {
+                                n.to_num((-1).into());
+                                let mut neg = workspace.new_atom();
+                                let m = neg.to_mul();
+                                m.extend(sq.as_view());
+                                m.extend(n.as_view());
+                                std::mem::swap(&mut sq, &mut neg);
+                            }
+
+                            n.to_num(1.into());
+                            let mut base = workspace.new_atom();
+                            let a = base.to_add();
+                            a.extend(n.as_view());
+                            a.extend(sq.as_view());
+
+                            if f.get_symbol() == Atom::ATAN {
+                                n.to_num((-1).into());
+                            } else {
+                                n.to_num((-1, 2).into());
+                            }
+
+                            if f.get_symbol() == Atom::ACOS {
+                                let mut pow = workspace.new_atom();
+                                pow.to_pow(base.as_view(), n.as_view());
+                                n.to_num((-1).into());
+                                let m = fn_der.to_mul();
+                                m.extend(pow.as_view());
+                                m.extend(n.as_view());
+                            } else {
+                                fn_der.to_pow(base.as_view(), n.as_view());
+                            }
+                        }
                         _ => unreachable!(),
                     }
 
@@ -134,6 +475,49 @@ impl<'a> AtomView<'a> {
                     return true;
                 }
 
+                // derive atan2(y, x) as (x*y' - y*x')/(x^2+y^2)
+                if f.get_symbol() == Atom::ATAN2 && f.get_nargs() == 2 && !is_der {
+                    let mut it = f.iter();
+                    let (y, x) = (it.next().unwrap(), it.next().unwrap());
+
+                    let mut n = workspace.new_atom();
+                    n.to_num((-1).into());
+                    let mut two = workspace.new_atom();
+                    two.to_num(2.into());
+
+                    let mut num = workspace.new_atom();
+                    let na = num.to_add();
+                    let mut mul = workspace.new_atom();
+                    for (index, arg_der) in &args_der {
+                        let m = mul.to_mul();
+                        if *index == 0 {
+                            m.extend(x);
+                        } else {
+                            m.extend(y);
+                            m.extend(n.as_view());
+                        }
+                        m.extend(arg_der.as_view());
+                        na.extend(mul.as_view());
+                    }
+
+                    let mut den = workspace.new_atom();
+                    let da = den.to_add();
+                    let mut sq = workspace.new_atom();
+                    sq.to_pow(x, two.as_view());
+                    da.extend(sq.as_view());
+                    sq.to_pow(y, two.as_view());
+                    da.extend(sq.as_view());
+
+                    let mut inv = workspace.new_atom();
+                    inv.to_pow(den.as_view(), n.as_view());
+
+                    let m = mul.to_mul();
+                    m.extend(num.as_view());
+                    m.extend(inv.as_view());
+                    mul.as_view().normalize(workspace, out);
+                    return true;
+                }
+
                 // create a derivative function that tags which index was derived
                 let mut add = workspace.new_atom();
                 let a = add.to_add();
@@ -141,6 +525,28 @@ impl<'a> AtomView<'a> {
                 let mut n = workspace.new_atom();
                 let mut mul = workspace.new_atom();
                 for (index, arg_der) in args_der {
+                    // use a registered derivative rule, if any
+                    if !is_der {
+                        if let Some(rule) = State::get_derivative_rule(f.get_symbol(), index) {
+                            let (lhs, rhs) = rule.as_ref();
+                            let settings = MatchSettings {
+                                anchored: true,
+                                ..MatchSettings::new()
+                            };
+                            let mut it =
+                                PatternAtomTreeIterator::new(lhs, to_derive, None, Some(&settings));
+                            if let Some(m) = it.next_detailed() {
+                                let r = rhs.replace_wildcards_with_matches(m.match_stack);
+
+                                let m = mul.to_mul();
+                                m.extend(r.as_view());
+                                m.extend(arg_der.as_view());
+                                a.extend(mul.as_view());
+                                continue;
+                            }
+                        }
+                    }
+
                     let p = fn_der.to_fun(Atom::DERIVATIVE);
 
                     if is_der {
@@ -222,6 +628,7 @@ impl<'a> AtomView<'a> {
                 if let AtomView::Num(n) = exp {
                     mul.extend(exp);
 
+                    // exact subtraction, so that fractional exponents remain rational
                     let res = n.get_coeff_view() + -1;
                     new_exp.to_num(res);
                 } else {
@@ -256,43 +663,40 @@ impl<'a> AtomView<'a> {
                 true
             }
             AtomView::Mul(args) => {
-                let mut add_h = workspace.new_atom();
-                let add = add_h.to_add();
-                let mut mul_h = workspace.new_atom();
-                let mut non_zero = false;
-                for arg in args.iter() {
+                let factors: Vec<_> = args.iter().collect();
+
+                let mut ders = vec![];
+                for (i, arg) in factors.iter().enumerate() {
                     let mut arg_der = workspace.new_atom();
                     if arg.derivative_with_ws_into(x, workspace, &mut arg_der) {
-                        if let Atom::Mul(mm) = arg_der.deref_mut() {
-                            for other_arg in args.iter() {
-                                if other_arg != arg {
-                                    mm.extend(other_arg);
-                                }
-                            }
-
-                            add.extend(arg_der.as_view());
-                        } else {
-                            let mm = mul_h.to_mul();
-                            mm.extend(arg_der.as_view());
-                            for other_arg in args.iter() {
-                                if other_arg != arg {
-                                    mm.extend(other_arg);
-                                }
-                            }
-                            add.extend(mul_h.as_view());
-                        }
-
-                        non_zero = true;
+                        ders.push((i, arg_der));
                     }
                 }
 
-                if non_zero {
-                    add_h.as_view().normalize(workspace, out);
-                    true
-                } else {
+                if ders.is_empty() {
                     out.to_num(0.into());
-                    false
+                    return false;
                 }
+
+                let mut add_h = workspace.new_atom();
+                let add = add_h.to_add();
+                let mut mul_h = workspace.new_atom();
+                for (i, arg_der) in ders {
+                    let mm = mul_h.to_mul();
+                    mm.extend(arg_der.as_view());
+
+                    // skip the differentiated factor by position, as equal factors may repeat
+                    for (j, f) in factors.iter().enumerate() {
+                        if j != i {
+                            mm.extend(*f);
+                        }
+                    }
+
+                    add.extend(mul_h.as_view());
+                }
+
+                add_h.as_view().normalize(workspace, out);
+                true
             }
             AtomView::Add(args) => {
                 let mut add_h = workspace.new_atom();
@@ -317,6 +721,157 @@ impl<'a> AtomView<'a> {
         }
     }
 
+    /// Integrate the expression in `x`, without an integration constant.
+    /// Returns `None` if the expression cannot be integrated.
+    pub(crate) fn integrate(&self, x: Symbol) -> Option<Atom> {
+        Workspace::get_local().with(|ws| {
+            let mut out = ws.new_atom();
+            if self.integrate_with_ws_into(x, ws, &mut out) {
+                Some(out.into_inner())
+            } else {
+                None
+            }
+        })
+    }
+
+    /// Integrate the expression in `x` and write the result in `out`.
+    /// Sums, constant multiples, powers `x^n`, `1/x` and `exp(a*x+b)`
+    /// with `a` and `b` independent of `x` are supported. No integration
+    /// constant is added.
+    ///
+    /// Returns `false` if the expression cannot be integrated,
+    /// in which case `out` is undefined.
+    pub(crate) fn integrate_with_ws_into(
+        &self,
+        x: Symbol,
+        workspace: &Workspace,
+        out: &mut Atom,
+    ) -> bool {
+        if !self.contains_symbol(x) {
+            let mut mul = workspace.new_atom();
+            let m = mul.to_mul();
+            m.extend(*self);
+            m.extend(workspace.new_var(x).as_view());
+            mul.as_view().normalize(workspace, out);
+            return true;
+        }
+
+        match self {
+            AtomView::Num(_) => unreachable!(),
+            AtomView::Var(_) => {
+                // x -> x^2/2
+                let mut pow = workspace.new_atom();
+                pow.to_pow(*self, workspace.new_num(2).as_view());
+                let mut mul = workspace.new_atom();
+                let m = mul.to_mul();
+                m.extend(pow.as_view());
+                m.extend(workspace.new_num((1, 2)).as_view());
+                mul.as_view().normalize(workspace, out);
+                true
+            }
+            AtomView::Pow(p) => {
+                let (base, exp) = p.get_base_exp();
+
+                let AtomView::Num(n) = exp else {
+                    return false;
+                };
+
+                if let AtomView::Var(v) = base {
+                    if v.get_symbol() != x {
+                        return false;
+                    }
+                } else {
+                    return false;
+                }
+
+                let minus_one = workspace.new_num(-1);
+                if exp == minus_one.as_view() {
+                    let mut log = workspace.new_atom();
+                    log.to_fun(Atom::LOG).add_arg(base);
+                    log.as_view().normalize(workspace, out);
+                    return true;
+                }
+
+                // x^n -> x^(n+1)/(n+1)
+                let n1 = workspace.new_num(n.get_coeff_view() + 1);
+                let mut pow = workspace.new_atom();
+                pow.to_pow(base, n1.as_view());
+                let mut inv = workspace.new_atom();
+                inv.to_pow(n1.as_view(), minus_one.as_view());
+                let mut mul = workspace.new_atom();
+                let m = mul.to_mul();
+                m.extend(pow.as_view());
+                m.extend(inv.as_view());
+                mul.as_view().normalize(workspace, out);
+                true
+            }
+            AtomView::Fun(f) => {
+                if f.get_symbol() != Atom::EXP || f.get_nargs() != 1 {
+                    return false;
+                }
+
+                // exp(a*x+b) -> exp(a*x+b)/a
+                let arg = f.iter().next().unwrap();
+                let mut a = workspace.new_atom();
+                if !arg.derivative_with_ws_into(x, workspace, &mut a)
+                    || a.as_view().contains_symbol(x)
+                {
+                    return false;
+                }
+
+                let mut inv = workspace.new_atom();
+                inv.to_pow(a.as_view(), workspace.new_num(-1).as_view());
+                let mut mul = workspace.new_atom();
+                let m = mul.to_mul();
+                m.extend(*self);
+                m.extend(inv.as_view());
+                mul.as_view().normalize(workspace, out);
+                true
+            }
+            AtomView::Mul(m) => {
+                // pull out the factors that are independent of x
+                let mut constant = workspace.new_atom();
+                let c = constant.to_mul();
+                let mut dependent = None;
+                for f in m {
+                    if f.contains_symbol(x) {
+                        if dependent.is_some() {
+                            return false;
+                        }
+                        dependent = Some(f);
+                    } else {
+                        c.extend(f);
+                    }
+                }
+
+                let mut integral = workspace.new_atom();
+                if !dependent
+                    .unwrap()
+                    .integrate_with_ws_into(x, workspace, &mut integral)
+                {
+                    return false;
+                }
+
+                c.extend(integral.as_view());
+                constant.as_view().normalize(workspace, out);
+                true
+            }
+            AtomView::Add(a) => {
+                let mut add = workspace.new_atom();
+                let aa = add.to_add();
+                let mut integral = workspace.new_atom();
+                for t in a {
+                    if !t.integrate_with_ws_into(x, workspace, &mut integral) {
+                        return false;
+                    }
+                    aa.extend(integral.as_view());
+                }
+                add.as_view().normalize(workspace, out);
+                true
+            }
+        }
+    }
+
     /// Series expand in `x` around `expansion_point` to depth `depth`.
     pub fn series(
         &self,
@@ -745,9 +1300,173 @@ impl Sub<&Atom> for &Series<AtomField> {
 mod test {
     use crate::{
         atom::{Atom, AtomCore},
-        parse, symbol,
+        parse,
+        state::State,
+        symbol,
     };
 
+    #[test]
+    fn long_product() {
+        let vars: Vec<_> = (0..20).map(|i| format!("x{}", i)).collect();
+        let expr = parse!(&vars.join("*")).unwrap();
+
+        let r = expr.derivative(symbol!("x0"));
+        assert_eq!(r, parse!(&vars[1..].join("*")).unwrap());
+
+        let r = expr
+            .replace(symbol!("x1"))
+            .with(symbol!("x0"))
+            .derivative(symbol!("x0"));
+        assert_eq!(r, parse!(&format!("2*x0*{}", vars[2..].join("*"))).unwrap());
+    }
+
+    #[test]
+    fn product_rule() {
+        let x = symbol!("x");
+
+        let r = parse!("x*x*y").unwrap().derivative(x);
+        assert_eq!(r, parse!("2*x*y").unwrap());
+
+        let r = parse!("x*sin(x)*exp(x)*y").unwrap().derivative(x);
+        assert_eq!(
+            r,
+            parse!("sin(x)*exp(x)*y + x*cos(x)*exp(x)*y + x*sin(x)*exp(x)*y").unwrap()
+        );
+    }
+
+    #[test]
+    fn integral() {
+        let v1 = symbol!("v1");
+        let v2 = symbol!("v2");
+
+        let r = parse!("integrate(v1^2*v2, v1)").unwrap().derivative(v1);
+        assert_eq!(r, parse!("v1^2*v2").unwrap());
+
+        let r = parse!("integrate(v1^2*v2^2, v1)").unwrap().derivative(v2);
+        assert_eq!(r, parse!("integrate(2*v1^2*v2, v1)").unwrap());
+
+        let r = parse!("integrate(v1^2, v1)").unwrap().derivative(v2);
+        assert_eq!(r, Atom::new_num(0));
+    }
+
+    #[test]
+    fn integrate() {
+        let v1 = symbol!("v1");
+
+        let r = parse!("v1^3 + 2*v2*v1 + 5").unwrap().integrate(v1).unwrap();
+        assert_eq!(r, parse!("v1^4/4 + v2*v1^2 + 5*v1").unwrap());
+
+        let r = parse!("v2/v1 + v1^-2").unwrap().integrate(v1).unwrap();
+        assert_eq!(r, parse!("v2*log(v1) - v1^-1").unwrap());
+
+        let r = parse!("exp(3*v2*v1+1)").unwrap().integrate(v1).unwrap();
+        assert_eq!(r, parse!("exp(3*v2*v1+1)/(3*v2)").unwrap());
+
+        assert_eq!(r.derivative(v1), parse!("exp(3*v2*v1+1)").unwrap());
+
+        assert!(parse!("sin(v1)").unwrap().integrate(v1).is_none());
+        assert!(parse!("v1*exp(v1)").unwrap().integrate(v1).is_none());
+        assert!(parse!("exp(v1^2)").unwrap().integrate(v1).is_none());
+    }
+
+    #[test]
+    fn gradient() {
+        let e = parse!("x^2*y*sin(x*z) + (x+y)^z + exp(y)/z + x*y*z").unwrap();
+        let vars = [symbol!("x"), symbol!("y"), symbol!("z"), symbol!("w")];
+
+        let grad = e.gradient(&vars);
+        for (g, x) in grad.iter().zip(&vars) {
+            assert_eq!(*g, e.derivative(*x));
+        }
+        assert_eq!(grad[3], Atom::new_num(0));
+
+        let (v1, v2) = (symbol!("v1"), symbol!("v2"));
+        let h = parse!("v1^2*v2+sin(v2)").unwrap().hessian(&[v1, v2]);
+        assert_eq!(h[0][0], parse!("2*v2").unwrap());
+        assert_eq!(h[0][1], parse!("2*v1").unwrap());
+        assert_eq!(h[1][0], parse!("2*v1").unwrap());
+        assert_eq!(h[1][1], parse!("-sin(v2)").unwrap());
+    }
+
+    #[test]
+    fn registered_derivative() {
+        State::register_derivative(
+            parse!("lambert_w(x_)").unwrap().to_pattern(),
+            0,
+            parse!("lambert_w(x_)/(x_*(1+lambert_w(x_)))")
+                .unwrap()
+                .to_pattern(),
+        )
+        .unwrap();
+        State::register_derivative(
+            parse!("der_rule_f(x_,y_)").unwrap().to_pattern(),
+            1,
+            parse!("x_*der_rule_f(x_,y_)").unwrap().to_pattern(),
+        )
+        .unwrap();
+
+        let v1 = symbol!("v1");
+
+        let r = parse!("lambert_w(3*v1)").unwrap().derivative(v1);
+        assert_eq!(
+            r,
+            parse!("3*lambert_w(3*v1)/(3*v1*(1+lambert_w(3*v1)))").unwrap()
+        );
+
+        let r = parse!("der_rule_f(v1,v1^2)").unwrap().derivative(v1);
+        assert_eq!(
+            r,
+            parse!("der(1,0,der_rule_f(v1,v1^2))+2*v1^2*der_rule_f(v1,v1^2)").unwrap()
+        );
+
+        assert!(State::register_derivative(
+            parse!("v1").unwrap().to_pattern(),
+            0,
+            Atom::new_num(1).to_pattern()
+        )
+        .is_err());
+    }
+
+    #[test]
+    fn trig_hyperbolic() {
+        let v1 = symbol!("v1");
+
+        let r = parse!("tanh(v1^2)").unwrap().derivative(v1);
+        assert_eq!(r, parse!("2*v1*(1-tanh(v1^2)^2)").unwrap());
+
+        let r = parse!("tan(2*v1)").unwrap().derivative(v1);
+        assert_eq!(r, parse!("2*(1+tan(2*v1)^2)").unwrap());
+
+        let r = parse!("sinh(v1)+cosh(v1)").unwrap().derivative(v1);
+        assert_eq!(r, parse!("cosh(v1)+sinh(v1)").unwrap());
+    }
+
+    #[test]
+    fn inverse_trig() {
+        let v1 = symbol!("v1");
+
+        let r = parse!("atan(v1)").unwrap().derivative(v1);
+        assert_eq!(r, parse!("(1+v1^2)^-1").unwrap());
+
+        let r = parse!("asin(2*v1)").unwrap().derivative(v1);
+        assert_eq!(r, parse!("2*(1-4*v1^2)^(-1/2)").unwrap());
+
+        let r = parse!("acos(v1)").unwrap().derivative(v1);
+        assert_eq!(r, parse!("-(1-v1^2)^(-1/2)").unwrap());
+    }
+
+    #[test]
+    fn derivative_at() {
+        let v1 = symbol!("v1");
+        let r = parse!("v1^2").unwrap().derivative_at(v1, Atom::new_num(3));
+        assert_eq!(r, Atom::new_num(6));
+
+        let r = parse!("v1^3*v2")
+            .unwrap()
+            .derivative_at(v1, Atom::new_num(2));
+        assert_eq!(r, parse!("12*v2").unwrap());
+    }
+
     #[test]
     fn derivative() {
         let v1 = symbol!("v1");
@@ -770,6 +1489,31 @@ mod test {
         assert_eq!(r, res);
     }
 
+    #[test]
+    fn atan2() {
+        let input = parse!("atan2(v1, v2)").unwrap();
+
+        let r = input.derivative(symbol!("v1"));
+        assert_eq!(r, parse!("v2*(v1^2+v2^2)^-1").unwrap());
+
+        let r = input.derivative(symbol!("v2"));
+        assert_eq!(r, parse!("-v1*(v1^2+v2^2)^-1").unwrap());
+
+        let r = parse!("atan2(v1^2, v1)").unwrap().derivative(symbol!("v1"));
+        assert_eq!(r, parse!("v1^2*(v1^2+v1^4)^-1").unwrap());
+    }
+
+    #[test]
+    fn fractional_power() {
+        let v1 = symbol!("v1");
+
+        let r = parse!("v1^(1/2)").unwrap().derivative(v1);
+        assert_eq!(r, parse!("1/2*v1^(-1/2)").unwrap());
+
+        let r = parse!("(2*v1)^(-3/2)").unwrap().derivative(v1);
+        assert_eq!(r, parse!("-3*(2*v1)^(-5/2)").unwrap());
+    }
+
     #[test]
     fn series() {
         let v1 = symbol!("v1");
@@ -787,6 +1531,26 @@ mod test {
         assert_eq!(t, res);
     }
 
+    #[test]
+    fn series_taylor() {
+        let v1 = symbol!("v1");
+
+        let t = parse!("exp(v1)")
+            .unwrap()
+            .series(v1, Atom::new_num(0).as_view(), 3.into(), true)
+            .unwrap()
+            .to_atom();
+        assert_eq!(t, parse!("1+v1+v1^2/2+v1^3/6").unwrap());
+
+        // vanishing coefficients do not appear
+        let t = parse!("sin(v1)")
+            .unwrap()
+            .series(v1, Atom::new_num(0).as_view(), 4.into(), true)
+            .unwrap()
+            .to_atom();
+        assert_eq!(t, parse!("v1-v1^3/6").unwrap());
+    }
+
     #[test]
     fn series_shift() {
         let v1 = symbol!("v1");
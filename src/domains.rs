@@ -20,6 +20,7 @@ pub mod factorized_rational_polynomial;
 pub mod finite_field;
 pub mod float;
 pub mod integer;
+pub mod modular;
 pub mod rational;
 pub mod rational_polynomial;
 
@@ -216,6 +217,20 @@ impl<T: Field> UpgradeToField for T {
     }
 }
 
+/// A map from the ring `R` to the ring `S` that preserves addition, multiplication
+/// and the unit, such as the embedding of the integers in the rationals or
+/// the reduction of integers modulo a prime.
+pub trait RingHomomorphism<R: Ring, S: Ring> {
+    /// Map an element of `R` to `S`.
+    fn apply(&self, a: &R::Element) -> S::Element;
+}
+
+impl<R: Ring, S: Ring, F: Fn(&R::Element) -> S::Element> RingHomomorphism<R, S> for F {
+    fn apply(&self, a: &R::Element) -> S::Element {
+        self(a)
+    }
+}
+
 /// Provides an interface for printing elements of a ring with optional customization,
 /// suitable as an argument to [format!]. Internally, it will call [Ring::format].
 pub struct RingPrinter<'a, R: Ring> {
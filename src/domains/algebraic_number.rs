@@ -5,6 +5,7 @@ use std::sync::Arc;
 use rand::Rng;
 
 use crate::{
+    atom::Atom,
     coefficient::ConvertToRing,
     combinatorics::CombinationIterator,
     poly::{
@@ -19,7 +20,7 @@ use super::{
         FiniteField, FiniteFieldCore, FiniteFieldWorkspace, GaloisField, ToFiniteField,
     },
     integer::Integer,
-    rational::Rational,
+    rational::{Rational, RationalField, Q},
     EuclideanDomain, Field, InternalOrdering, Ring, SelfRing,
 };
 
@@ -186,6 +187,48 @@ where
     }
 }
 
+impl ConvertToRing for AlgebraicExtension<RationalField> {
+    fn element_from_integer(&self, number: Integer) -> Self::Element {
+        self.constant(Q.element_from_integer(number))
+    }
+
+    fn element_from_coefficient(&self, number: crate::coefficient::Coefficient) -> Self::Element {
+        self.constant(Q.element_from_coefficient(number))
+    }
+
+    fn element_from_coefficient_view(
+        &self,
+        number: crate::coefficient::CoefficientView<'_>,
+    ) -> Self::Element {
+        self.constant(Q.element_from_coefficient_view(number))
+    }
+
+    fn element_from_symbol(&self, symbol: crate::atom::Symbol) -> Option<Self::Element> {
+        if self.poly.variables[0] != Variable::Symbol(symbol) {
+            return None;
+        }
+
+        Some(AlgebraicNumber {
+            poly: self.poly.monomial(Q.one(), vec![1]),
+        })
+    }
+}
+
+impl AlgebraicExtension<RationalField> {
+    /// Construct the Gaussian rationals `Q[𝑖]`, where the built-in
+    /// imaginary unit `𝑖` is the root of `𝑖^2+1`.
+    ///
+    /// When converting an expression to a polynomial over this field,
+    /// `𝑖` becomes part of the coefficients instead of a variable.
+    pub fn gaussian_rationals() -> Self {
+        let mut poly =
+            MultivariatePolynomial::new(&Q, Some(2), Arc::new(vec![Variable::Symbol(Atom::I)]));
+        poly.append_monomial(Q.one(), &[0]);
+        poly.append_monomial(Q.one(), &[2]);
+        AlgebraicExtension::new(poly)
+    }
+}
+
 impl<UField: FiniteFieldWorkspace> AlgebraicExtension<FiniteField<UField>>
 where
     FiniteField<UField>: FiniteFieldCore<UField> + PolynomialGCD<u16>,
@@ -802,7 +845,7 @@ mod tests {
     use crate::domains::finite_field::{PrimeIteratorU64, Zp, Z2};
     use crate::domains::integer::Z;
     use crate::domains::rational::Q;
-    use crate::domains::Ring;
+    use crate::domains::{Field, Ring};
     use crate::{parse, symbol};
 
     #[test]
@@ -825,6 +868,48 @@ mod tests {
         Ok(())
     }
 
+    #[test]
+    fn gaussian_rationals() {
+        let ring = AlgebraicExtension::gaussian_rationals();
+
+        let a = parse!("v1+𝑖")
+            .unwrap()
+            .to_polynomial::<_, u8>(&Q, None)
+            .to_number_field(&ring);
+        let b = parse!("v1-𝑖")
+            .unwrap()
+            .to_polynomial::<_, u8>(&Q, a.variables.clone())
+            .to_number_field(&ring);
+        let r = parse!("v1^2+1")
+            .unwrap()
+            .to_polynomial::<_, u8>(&Q, a.variables.clone())
+            .to_number_field(&ring);
+        assert_eq!(&a * &b, r);
+
+        // 𝑖 is mapped into the coefficient ring directly
+        let a = parse!("v1+𝑖").unwrap().to_polynomial::<_, u8>(&ring, None);
+        let b = parse!("v1-𝑖")
+            .unwrap()
+            .to_polynomial::<_, u8>(&ring, a.variables.clone());
+        let r = parse!("v1^2+1")
+            .unwrap()
+            .to_polynomial::<_, u8>(&ring, a.variables.clone());
+        assert_eq!(a.nvars(), 1);
+        assert_eq!(&a * &b, r);
+
+        let c = parse!("(v1+𝑖)^2-𝑖^3")
+            .unwrap()
+            .to_polynomial::<_, u8>(&ring, a.variables.clone());
+        let r = parse!("v1^2+2*𝑖*v1-1+𝑖")
+            .unwrap()
+            .to_polynomial::<_, u8>(&ring, a.variables.clone());
+        assert_eq!(c, r);
+
+        let i = ring.to_element(parse!("𝑖").unwrap().to_polynomial(&Q, None));
+        assert_eq!(ring.mul(&i, &i), ring.neg(&ring.one()));
+        assert_eq!(ring.inv(&i), ring.neg(&i));
+    }
+
     #[test]
     fn galois() {
         for j in 1..10 {
@@ -839,6 +924,22 @@ mod tests {
         }
     }
 
+    #[test]
+    fn quotient_ring() {
+        let f = parse!("x^2+x+1")
+            .unwrap()
+            .to_polynomial::<_, u16>(&Z2, None);
+        let ring = AlgebraicExtension::new(f);
+
+        let x = ring.to_element(parse!("x").unwrap().to_polynomial(&Z2, None));
+        let x2 = ring.mul(&x, &x);
+        assert_eq!(
+            x2,
+            ring.to_element(parse!("x+1").unwrap().to_polynomial(&Z2, None))
+        );
+        assert_eq!(ring.mul(&x2, &x), ring.one());
+    }
+
     #[test]
     fn norm() {
         let a = parse!("z^4+z^3+(2+a-a^2)z^2+(1+a^2-2a^3)z-2")
@@ -395,6 +395,28 @@ pub trait Real: NumericalFloatLike {
     fn acosh(&self) -> Self;
     fn atanh(&self) -> Self;
     fn powf(&self, e: &Self) -> Self;
+
+    /// Get the largest of `self` and `other`, returning `self` at ties.
+    /// Complex numbers are compared by their real parts and SIMD types lane by lane.
+    fn max(&self, other: &Self) -> Self {
+        let d = other.clone() - self;
+        if d != d.zero() && d.norm() == d {
+            other.clone()
+        } else {
+            self.clone()
+        }
+    }
+
+    /// Get the smallest of `self` and `other`, returning `self` at ties.
+    /// Complex numbers are compared by their real parts and SIMD types lane by lane.
+    fn min(&self, other: &Self) -> Self {
+        let d = self.clone() - other;
+        if d != d.zero() && d.norm() == d {
+            other.clone()
+        } else {
+            self.clone()
+        }
+    }
 }
 
 impl NumericalFloatLike for f64 {
@@ -2935,6 +2957,16 @@ macro_rules! simd_impl {
             fn powf(&self, e: &Self) -> Self {
                 (*self).$p(*e)
             }
+
+            #[inline(always)]
+            fn max(&self, other: &Self) -> Self {
+                <$t>::max(*self, *other)
+            }
+
+            #[inline(always)]
+            fn min(&self, other: &Self) -> Self {
+                <$t>::min(*self, *other)
+            }
         }
 
         impl From<&Rational> for $t {
@@ -3932,6 +3964,24 @@ impl<T: Real> Real for Complex<T> {
             (e * self.log()).exp()
         }
     }
+
+    #[inline]
+    fn max(&self, other: &Self) -> Self {
+        if self.re.max(&other.re) == self.re {
+            self.clone()
+        } else {
+            other.clone()
+        }
+    }
+
+    #[inline]
+    fn min(&self, other: &Self) -> Self {
+        if self.re.min(&other.re) == self.re {
+            self.clone()
+        } else {
+            other.clone()
+        }
+    }
 }
 
 impl<T: NumericalFloatLike> From<T> for Complex<T> {
@@ -533,6 +533,33 @@ impl Integer {
         }
     }
 
+    /// Get the number of bits required to represent the absolute value of the integer.
+    pub fn bit_length(&self) -> u64 {
+        match self {
+            Integer::Natural(n) => (64 - n.unsigned_abs().leading_zeros()) as u64,
+            Integer::Double(n) => (128 - n.unsigned_abs().leading_zeros()) as u64,
+            Integer::Large(n) => n.significant_bits() as u64,
+        }
+    }
+
+    /// Compute the `n`-th root of the integer if it is exact.
+    /// Returns `None` if the integer is not a perfect `n`-th power.
+    pub fn nth_root(&self, n: u32) -> Option<Integer> {
+        if n == 0 || (n % 2 == 0 && self.is_negative()) {
+            return None;
+        }
+
+        let (r, rem) = self
+            .clone()
+            .to_multi_prec()
+            .root_rem(MultiPrecisionInteger::new(), n);
+        if rem == 0 {
+            Some(r.into())
+        } else {
+            None
+        }
+    }
+
     pub fn abs_cmp(&self, other: &Self) -> Ordering {
         match (self, other) {
             (Integer::Large(n1), Integer::Large(n2)) => n1.as_abs().cmp(&n2.as_abs()),
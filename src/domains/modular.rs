@@ -0,0 +1,315 @@
+//! Integers modulo a (not necessarily prime) modulus.
+
+use std::fmt::{Display, Error, Formatter};
+
+use rand::Rng;
+
+use crate::{
+    coefficient::{Coefficient, CoefficientView, ConvertToRing},
+    printer::{PrintOptions, PrintState},
+};
+
+use super::{
+    integer::{Integer, Z},
+    rational::Rational,
+    Ring,
+};
+
+/// The ring of integers modulo `n`, where `n` does not have to be prime.
+/// For a prime modulus, prefer the much faster [FiniteField](super::finite_field::FiniteField).
+///
+/// Since `Z/nZ` has zero divisors for composite `n`, it is not a Euclidean domain.
+/// Elements are stored as their representative in `[0, n)`.
+///
+/// # Examples
+///
+/// ```
+/// use symbolica::domains::{modular::ZMod, Ring};
+/// let ring = ZMod::new(6);
+/// let a = ring.mul(&ring.to_element(2), &ring.to_element(3));
+/// assert!(ring.is_zero(&a));
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct ZMod {
+    modulus: u64,
+}
+
+impl ZMod {
+    /// Create the ring of integers modulo `modulus`.
+    pub fn new(modulus: u64) -> ZMod {
+        if modulus == 0 {
+            panic!("Modulus must be positive");
+        }
+
+        ZMod { modulus }
+    }
+
+    /// Get the modulus of the ring.
+    pub fn get_modulus(&self) -> u64 {
+        self.modulus
+    }
+
+    /// Convert a number to an element of the ring.
+    pub fn to_element(&self, a: u64) -> u64 {
+        a % self.modulus
+    }
+
+    /// Compute the inverse of `a`, if it exists, i.e., if `a` is coprime to the modulus.
+    pub fn try_inv(&self, a: &u64) -> Option<u64> {
+        let (mut r0, mut r1) = (self.modulus as i128, *a as i128);
+        let (mut s0, mut s1) = (0i128, 1i128);
+        while r1 != 0 {
+            let q = r0 / r1;
+            (r0, r1) = (r1, r0 - q * r1);
+            (s0, s1) = (s1, s0 - q * s1);
+        }
+
+        if r0 != 1 {
+            return None;
+        }
+
+        Some(s0.rem_euclid(self.modulus as i128) as u64)
+    }
+
+    fn from_integer(&self, n: &Integer) -> u64 {
+        let mut r = n % &Integer::from(self.modulus);
+        if r.is_negative() {
+            r += &Integer::from(self.modulus);
+        }
+
+        match r {
+            Integer::Natural(n) => n as u64,
+            Integer::Double(n) => n as u64,
+            Integer::Large(_) => unreachable!("Remainder is smaller than the modulus"),
+        }
+    }
+
+    fn from_rational(&self, r: &Rational) -> u64 {
+        let num = self.from_integer(&r.numerator());
+        let den = self.from_integer(&r.denominator());
+        let inv = self.try_inv(&den).unwrap_or_else(|| {
+            panic!(
+                "Denominator {} is not invertible modulo {}",
+                r.denominator(),
+                self.modulus
+            )
+        });
+        self.mul(&num, &inv)
+    }
+}
+
+impl Display for ZMod {
+    fn fmt(&self, f: &mut Formatter<'_>) -> Result<(), Error> {
+        write!(f, " % {}", self.modulus)
+    }
+}
+
+impl Ring for ZMod {
+    type Element = u64;
+
+    #[inline]
+    fn add(&self, a: &Self::Element, b: &Self::Element) -> Self::Element {
+        ((*a as u128 + *b as u128) % self.modulus as u128) as u64
+    }
+
+    #[inline]
+    fn sub(&self, a: &Self::Element, b: &Self::Element) -> Self::Element {
+        if a >= b {
+            a - b
+        } else {
+            ((*a as u128 + self.modulus as u128 - *b as u128) % self.modulus as u128) as u64
+        }
+    }
+
+    #[inline]
+    fn mul(&self, a: &Self::Element, b: &Self::Element) -> Self::Element {
+        ((*a as u128 * *b as u128) % self.modulus as u128) as u64
+    }
+
+    #[inline]
+    fn add_assign(&self, a: &mut Self::Element, b: &Self::Element) {
+        *a = self.add(a, b);
+    }
+
+    #[inline]
+    fn sub_assign(&self, a: &mut Self::Element, b: &Self::Element) {
+        *a = self.sub(a, b);
+    }
+
+    #[inline]
+    fn mul_assign(&self, a: &mut Self::Element, b: &Self::Element) {
+        *a = self.mul(a, b);
+    }
+
+    fn add_mul_assign(&self, a: &mut Self::Element, b: &Self::Element, c: &Self::Element) {
+        self.add_assign(a, &self.mul(b, c));
+    }
+
+    fn sub_mul_assign(&self, a: &mut Self::Element, b: &Self::Element, c: &Self::Element) {
+        self.sub_assign(a, &self.mul(b, c));
+    }
+
+    #[inline]
+    fn neg(&self, a: &Self::Element) -> Self::Element {
+        if *a == 0 {
+            0
+        } else {
+            self.modulus - a
+        }
+    }
+
+    #[inline]
+    fn zero(&self) -> Self::Element {
+        0
+    }
+
+    #[inline]
+    fn one(&self) -> Self::Element {
+        1 % self.modulus
+    }
+
+    #[inline]
+    fn nth(&self, n: Integer) -> Self::Element {
+        self.from_integer(&n)
+    }
+
+    fn pow(&self, b: &Self::Element, mut e: u64) -> Self::Element {
+        let mut x = *b;
+        let mut y = self.one();
+        while e > 0 {
+            if e % 2 == 1 {
+                y = self.mul(&y, &x);
+            }
+
+            x = self.mul(&x, &x);
+            e /= 2;
+        }
+        y
+    }
+
+    #[inline]
+    fn is_zero(&self, a: &Self::Element) -> bool {
+        *a == 0
+    }
+
+    #[inline]
+    fn is_one(&self, a: &Self::Element) -> bool {
+        *a == self.one()
+    }
+
+    fn one_is_gcd_unit() -> bool {
+        false
+    }
+
+    fn characteristic(&self) -> Integer {
+        self.modulus.into()
+    }
+
+    fn size(&self) -> Integer {
+        self.modulus.into()
+    }
+
+    /// Divide `a` by `b` if `b` is a unit. Otherwise, the result
+    /// is not unique and `None` is returned.
+    fn try_div(&self, a: &Self::Element, b: &Self::Element) -> Option<Self::Element> {
+        self.try_inv(b).map(|inv| self.mul(a, &inv))
+    }
+
+    fn sample(&self, rng: &mut impl rand::RngCore, range: (i64, i64)) -> Self::Element {
+        let r = rng.gen_range(range.0..range.1);
+        (r as i128).rem_euclid(self.modulus as i128) as u64
+    }
+
+    fn format<W: std::fmt::Write>(
+        &self,
+        element: &Self::Element,
+        opts: &PrintOptions,
+        state: PrintState,
+        f: &mut W,
+    ) -> Result<bool, Error> {
+        Z.format(&(*element).into(), opts, state, f)
+    }
+}
+
+impl ConvertToRing for ZMod {
+    fn element_from_integer(&self, number: Integer) -> Self::Element {
+        self.from_integer(&number)
+    }
+
+    fn element_from_coefficient(&self, number: Coefficient) -> Self::Element {
+        match number {
+            Coefficient::Rational(r) => self.from_rational(&r),
+            Coefficient::Float(_) => panic!("Cannot convert float coefficient to integer modulo n"),
+            Coefficient::FiniteField(_, _) => {
+                panic!("Cannot convert finite field coefficient to integer modulo n")
+            }
+            Coefficient::RationalPolynomial(_) => {
+                panic!("Cannot convert rational polynomial coefficient to integer modulo n")
+            }
+        }
+    }
+
+    fn element_from_coefficient_view(&self, number: CoefficientView<'_>) -> Self::Element {
+        match number {
+            CoefficientView::Natural(n, d) => self.from_rational(&Rational::from((n, d))),
+            CoefficientView::Large(l) => self.from_rational(&l.to_rat()),
+            CoefficientView::Float(_) => {
+                panic!("Cannot convert float coefficient to integer modulo n")
+            }
+            CoefficientView::FiniteField(_, _) => {
+                panic!("Cannot convert finite field coefficient to integer modulo n")
+            }
+            CoefficientView::RationalPolynomial(_) => {
+                panic!("Cannot convert rational polynomial coefficient to integer modulo n")
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use crate::{
+        atom::AtomCore,
+        domains::{modular::ZMod, Ring},
+        parse,
+    };
+
+    #[test]
+    fn arithmetic() {
+        let ring = ZMod::new(6);
+        let (two, three) = (ring.to_element(2), ring.to_element(3));
+
+        assert!(ring.is_zero(&ring.mul(&two, &three)));
+        assert_eq!(ring.add(&three, &ring.to_element(4)), 1);
+        assert_eq!(ring.neg(&two), 4);
+        assert_eq!(ring.pow(&ring.to_element(5), 3), 5);
+        assert_eq!(ring.nth((-1).into()), 5);
+
+        // zero divisors have no inverse
+        assert_eq!(ring.try_div(&two, &three), None);
+        assert_eq!(ring.try_div(&two, &ring.to_element(5)), Some(4));
+
+        // negative ranges are reduced modulo n
+        let r = ring.sample(&mut rand::thread_rng(), (-3, -2));
+        assert_eq!(r, 3);
+
+        let ring = ZMod::new(u64::MAX - 1);
+        assert_eq!(ring.sub(&1, &3), u64::MAX - 3);
+    }
+
+    #[test]
+    fn polynomial() {
+        let ring = ZMod::new(6);
+        let a = parse!("2*v1 + 1/5")
+            .unwrap()
+            .to_polynomial::<_, u8>(&ring, None);
+        let b = parse!("3*v1 - 1")
+            .unwrap()
+            .to_polynomial::<_, u8>(&ring, a.variables.clone());
+
+        let r = parse!("v1 + 1")
+            .unwrap()
+            .to_polynomial::<_, u8>(&ring, a.variables.clone());
+        assert_eq!(&a * &b, r);
+    }
+}
@@ -173,6 +173,21 @@ impl<R: Ring, E: PositiveExponent> RationalPolynomial<R, E> {
         self.numerator.is_constant() && self.denominator.is_constant()
     }
 
+    /// Returns `true` iff the denominator is one.
+    pub fn is_polynomial(&self) -> bool {
+        self.denominator.is_one()
+    }
+
+    /// Get a copy of the numerator.
+    pub fn numerator(&self) -> MultivariatePolynomial<R, E> {
+        self.numerator.clone()
+    }
+
+    /// Get a copy of the denominator.
+    pub fn denominator(&self) -> MultivariatePolynomial<R, E> {
+        self.denominator.clone()
+    }
+
     /// Convert the coefficient from the current field to a finite field.
     pub fn to_finite_field<UField: FiniteFieldWorkspace>(
         &self,
@@ -195,6 +210,18 @@ impl<R: Ring, E: PositiveExponent> RationalPolynomial<R, E> {
     }
 }
 
+impl<E: PositiveExponent> RationalPolynomial<IntegerRing, E> {
+    /// Write the numerator and denominator as separate C expressions in Horner form,
+    /// using `var_names` as the names of the variables.
+    /// See [MultivariatePolynomial::to_c_code].
+    pub fn to_c_code(&self, var_names: &[&str]) -> Result<(String, String), String> {
+        Ok((
+            self.numerator.to_c_code(var_names)?,
+            self.denominator.to_c_code(var_names)?,
+        ))
+    }
+}
+
 impl<R: Ring, E: PositiveExponent> SelfRing for RationalPolynomial<R, E> {
     fn is_zero(&self) -> bool {
         self.is_zero()
@@ -920,20 +947,11 @@ where
         let dn = self.numerator.derivative(var);
         let dd = self.denominator.derivative(var);
 
-        let a = RationalPolynomial::from_num_den(
-            dn,
-            self.denominator.clone(),
-            &self.numerator.ring,
-            false,
-        );
-        let b = RationalPolynomial::from_num_den(
-            &self.numerator * &dd,
-            &self.denominator * &self.denominator,
-            &self.numerator.ring,
-            false,
-        );
+        // apply the quotient rule (n'd - nd')/d^2 and reduce once
+        let num = &(&dn * &self.denominator) - &(&self.numerator * &dd);
+        let den = &self.denominator * &self.denominator;
 
-        &a - &b
+        RationalPolynomial::from_num_den(num, den, &self.numerator.ring, true)
     }
 }
 
@@ -1436,6 +1454,56 @@ mod test {
         assert_eq!(t, "1");
     }
 
+    #[test]
+    fn numerator_denominator() {
+        let vars = Some(Arc::new(vec![symbol!("v1").into(), symbol!("v2").into()]));
+        let p: RationalPolynomial<_, u8> = parse!("(v1+v2)/(2*v1^2)")
+            .unwrap()
+            .to_rational_polynomial(&Q, &Z, vars.clone());
+
+        assert!(!p.is_polynomial());
+        assert_eq!(p.numerator().to_expression(), parse!("v1+v2").unwrap());
+        assert_eq!(p.denominator().to_expression(), parse!("2*v1^2").unwrap());
+        assert_eq!(p.denominator().get_vars(), p.numerator().get_vars());
+
+        let p: RationalPolynomial<_, u8> = parse!("v1*v2+1")
+            .unwrap()
+            .to_rational_polynomial(&Q, &Z, vars);
+        assert!(p.is_polynomial());
+        assert_eq!(p.numerator().to_expression(), parse!("v1*v2+1").unwrap());
+    }
+
+    #[test]
+    fn apart() {
+        let vars = Some(Arc::new(vec![symbol!("v1").into()]));
+        let p: RationalPolynomial<_, u8> = parse!("1/((v1-1)*(v1-2))")
+            .unwrap()
+            .to_rational_polynomial(&Q, &Z, vars.clone());
+
+        let r = p.apart(0);
+        assert_eq!(r.len(), 2);
+        for e in ["-1/(v1-1)", "1/(v1-2)"] {
+            let e = parse!(e)
+                .unwrap()
+                .to_rational_polynomial(&Q, &Z, vars.clone());
+            assert!(r.contains(&e));
+        }
+
+        // the polynomial part of an improper fraction is the first entry
+        let p: RationalPolynomial<_, u8> = parse!("v1^3/((v1-1)*(v1-2))")
+            .unwrap()
+            .to_rational_polynomial(&Q, &Z, vars.clone());
+        let r = p.apart(0);
+        assert_eq!(r.len(), 3);
+        assert_eq!(
+            r[0],
+            parse!("v1+3")
+                .unwrap()
+                .to_rational_polynomial(&Q, &Z, vars.clone())
+        );
+        assert_eq!(r.iter().skip(1).fold(r[0].clone(), |acc, x| &acc + x), p);
+    }
+
     #[test]
     fn hermite_reduction() {
         let p: RationalPolynomial<_, _> = parse!("1/(v1 + 1)^5")
@@ -1665,4 +1733,107 @@ mod test {
             ]
         );
     }
+
+    #[test]
+    fn wide_product() {
+        let n = 100;
+        let expr = (0..n)
+            .map(|i| format!("y{i}/(y{i}+{})", i + 1))
+            .collect::<Vec<_>>()
+            .join("*");
+
+        let p: RationalPolynomial<_, _> = parse!(&expr)
+            .unwrap()
+            .to_rational_polynomial::<_, _, u8>(&Q, &Z, None);
+
+        let vars = p.get_variables().clone();
+        assert_eq!(vars.len(), n);
+        for (i, v) in vars.iter().enumerate() {
+            assert_eq!(*v, symbol!(format!("y{i}")).into());
+        }
+
+        let num = (0..n)
+            .map(|i| format!("y{i}"))
+            .collect::<Vec<_>>()
+            .join("*");
+        assert_eq!(
+            p.numerator,
+            parse!(&num).unwrap().to_polynomial(&Z, Some(vars))
+        );
+        assert_eq!(p.denominator.degree(0), 1);
+    }
+
+    #[test]
+    fn mostly_polynomial_sum() {
+        let poly = (1..=50)
+            .map(|i| format!("{i}*v1^{i}*v2"))
+            .collect::<Vec<_>>()
+            .join("+");
+
+        let p: RationalPolynomial<_, _> = parse!(&format!("{poly} + 1/(v1+1)"))
+            .unwrap()
+            .to_rational_polynomial::<_, _, u8>(&Q, &Z, None);
+
+        let vars = p.get_variables().clone();
+        assert_eq!(
+            p.numerator,
+            parse!(&format!("({poly})*(v1+1)+1"))
+                .unwrap()
+                .expand()
+                .to_polynomial(&Z, Some(vars.clone()))
+        );
+        assert_eq!(
+            p.denominator,
+            parse!("v1+1").unwrap().to_polynomial(&Z, Some(vars))
+        );
+    }
+
+    #[test]
+    fn derivative() {
+        let p: RationalPolynomial<_, _> = parse!("1/v1")
+            .unwrap()
+            .to_rational_polynomial::<_, _, u8>(&Q, &Z, None);
+        assert_eq!(
+            p.derivative(0),
+            parse!("-1/v1^2")
+                .unwrap()
+                .to_rational_polynomial::<_, _, u8>(&Q, &Z, p.get_variables().clone())
+        );
+
+        let p: RationalPolynomial<_, _> = parse!("v1^2/(v1+1)")
+            .unwrap()
+            .to_rational_polynomial::<_, _, u8>(&Q, &Z, None);
+        let d = p.derivative(0);
+        assert_eq!(
+            d,
+            parse!("(v1^2+2*v1)/(v1+1)^2")
+                .unwrap()
+                .to_rational_polynomial::<_, _, u8>(&Q, &Z, p.get_variables().clone())
+        );
+        assert_eq!(d.denominator.degree(0), 2);
+    }
+
+    #[test]
+    fn is_equal_rational() {
+        let a = parse!("(v1^2-1)/(v1-1)").unwrap();
+        assert!(a.is_equal_rational(parse!("v1+1").unwrap()));
+        assert!(!a.is_equal_rational(parse!("v1-1").unwrap()));
+        assert!(parse!("f1(v2)/v2 + 1/v3")
+            .unwrap()
+            .is_equal_rational(parse!("(v3*f1(v2)+v2)/(v2*v3)").unwrap()));
+        assert!(!parse!("1.5*v1")
+            .unwrap()
+            .is_equal_rational(parse!("3/2*v1").unwrap()));
+    }
+
+    #[test]
+    fn to_c_code() {
+        let p: RationalPolynomial<_, u8> = parse!("(v1+1)/(v1-1)")
+            .unwrap()
+            .to_rational_polynomial(&Z, &Z, None);
+        assert_eq!(
+            p.to_c_code(&["x"]).unwrap(),
+            ("1+x".to_string(), "-1+x".to_string())
+        );
+    }
 }
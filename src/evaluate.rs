@@ -15,6 +15,7 @@ use ahash::{AHasher, HashMap};
 use rand::{thread_rng, Rng};
 
 use self_cell::self_cell;
+use wide::f64x4;
 
 use crate::{
     atom::{Atom, AtomCore, AtomView, KeyLookup, Symbol},
@@ -326,6 +327,7 @@ pub enum Expression<T> {
     Powf(Box<(Expression<T>, Expression<T>)>),
     ReadArg(usize), // read nth function argument
     BuiltinFun(BuiltinSymbol, Box<Expression<T>>),
+    BuiltinBinaryFun(BuiltinSymbol, Box<(Expression<T>, Expression<T>)>),
     SubExpression(usize),
 }
 
@@ -345,6 +347,11 @@ pub enum HashedExpression<T> {
     ),
     ReadArg(ExpressionHash, usize), // read nth function argument
     BuiltinFun(ExpressionHash, BuiltinSymbol, Box<HashedExpression<T>>),
+    BuiltinBinaryFun(
+        ExpressionHash,
+        BuiltinSymbol,
+        Box<(HashedExpression<T>, HashedExpression<T>)>,
+    ),
     SubExpression(ExpressionHash, usize),
 }
 
@@ -360,6 +367,7 @@ impl<T> HashedExpression<T> {
             HashedExpression::Powf(h, _) => *h,
             HashedExpression::ReadArg(h, _) => *h,
             HashedExpression::BuiltinFun(h, _, _) => *h,
+            HashedExpression::BuiltinBinaryFun(h, _, _) => *h,
             HashedExpression::SubExpression(h, _) => *h,
         }
     }
@@ -387,6 +395,10 @@ impl<T: Clone> HashedExpression<T> {
             HashedExpression::BuiltinFun(_, s, a) => {
                 Expression::BuiltinFun(*s, Box::new(a.to_expression()))
             }
+            HashedExpression::BuiltinBinaryFun(_, s, a) => Expression::BuiltinBinaryFun(
+                *s,
+                Box::new((a.0.to_expression(), a.1.to_expression())),
+            ),
             HashedExpression::SubExpression(_, s) => Expression::SubExpression(*s),
         }
     }
@@ -414,6 +426,10 @@ impl<T: Ord> Ord for HashedExpression<T> {
             (HashedExpression::BuiltinFun(_, a, b), HashedExpression::BuiltinFun(_, c, d)) => {
                 a.cmp(c).then_with(|| b.cmp(d))
             }
+            (
+                HashedExpression::BuiltinBinaryFun(_, a, b),
+                HashedExpression::BuiltinBinaryFun(_, c, d),
+            ) => a.cmp(c).then_with(|| b.cmp(d)),
             (HashedExpression::SubExpression(_, s1), HashedExpression::SubExpression(_, s2)) => {
                 s1.cmp(s2)
             }
@@ -435,6 +451,8 @@ impl<T: Ord> Ord for HashedExpression<T> {
             (_, HashedExpression::ReadArg(_, _)) => std::cmp::Ordering::Greater,
             (HashedExpression::BuiltinFun(_, _, _), _) => std::cmp::Ordering::Less,
             (_, HashedExpression::BuiltinFun(_, _, _)) => std::cmp::Ordering::Greater,
+            (HashedExpression::BuiltinBinaryFun(_, _, _), _) => std::cmp::Ordering::Less,
+            (_, HashedExpression::BuiltinBinaryFun(_, _, _)) => std::cmp::Ordering::Greater,
         }
     }
 }
@@ -483,7 +501,7 @@ impl<T: Eq + Hash + Clone + Ord> HashedExpression<T> {
             HashedExpression::Pow(_, p) => {
                 p.0.find_subexpression(subexp);
             }
-            HashedExpression::Powf(_, p) => {
+            HashedExpression::Powf(_, p) | HashedExpression::BuiltinBinaryFun(_, _, p) => {
                 p.0.find_subexpression(subexp);
                 p.1.find_subexpression(subexp);
             }
@@ -523,7 +541,7 @@ impl<T: Eq + Hash + Clone + Ord> HashedExpression<T> {
             HashedExpression::Pow(_, p) => {
                 p.0.replace_subexpression(subexp, false);
             }
-            HashedExpression::Powf(_, p) => {
+            HashedExpression::Powf(_, p) | HashedExpression::BuiltinBinaryFun(_, _, p) => {
                 p.0.replace_subexpression(subexp, false);
                 p.1.replace_subexpression(subexp, false);
             }
@@ -600,6 +618,11 @@ impl<T: Eq + Hash + Clone + Ord> HashedExpression<T> {
             HashedExpression::BuiltinFun(_, _, b) => {
                 b.count_operations_with_subexpression(sub_expr)
             } // not clear how to count this, third arg?
+            HashedExpression::BuiltinBinaryFun(_, _, p) => {
+                let (a, m) = p.0.count_operations_with_subexpression(sub_expr);
+                let (a2, m2) = p.1.count_operations_with_subexpression(sub_expr);
+                (a + a2, m + m2)
+            }
             HashedExpression::SubExpression(_, _) => (0, 0),
         }
     }
@@ -702,6 +725,20 @@ impl<T: std::hash::Hash + Clone> Expression<T> {
                 let h = hasher.finish();
                 (h, HashedExpression::BuiltinFun(h, *s, Box::new(va)))
             }
+            Expression::BuiltinBinaryFun(s, a) => {
+                let mut hasher = AHasher::default();
+                hasher.write_u8(10);
+                s.hash(&mut hasher);
+                let (ha, va) = a.0.to_hashed_expression();
+                let (hb, vb) = a.1.to_hashed_expression();
+                hasher.write_u64(ha);
+                hasher.write_u64(hb);
+                let h = hasher.finish();
+                (
+                    h,
+                    HashedExpression::BuiltinBinaryFun(h, *s, Box::new((va, vb))),
+                )
+            }
             Expression::SubExpression(i) => {
                 let mut hasher = AHasher::default();
                 hasher.write_u8(9);
@@ -732,6 +769,21 @@ impl<T: Real> ExpressionEvaluator<T> {
         res
     }
 
+    /// Evaluate the expressions at every point in `points`, reusing the evaluation
+    /// buffer between points. The results of all expressions are concatenated per point.
+    pub fn evaluate_batch(&mut self, points: &[Vec<T>]) -> Vec<T> {
+        let n = self.result_indices.len();
+        if n == 0 {
+            return vec![];
+        }
+
+        let mut out = vec![T::new_zero(); points.len() * n];
+        for (p, o) in points.iter().zip(out.chunks_mut(n)) {
+            self.evaluate(p, o);
+        }
+        out
+    }
+
     pub fn evaluate(&mut self, params: &[T], out: &mut [T]) {
         for (t, p) in self.stack.iter_mut().zip(params) {
             *t = p.clone();
@@ -766,12 +818,25 @@ impl<T: Real> ExpressionEvaluator<T> {
                 Instr::Powf(r, b, e) => {
                     self.stack[*r] = self.stack[*b].powf(&self.stack[*e]);
                 }
+                Instr::BuiltinBinaryFun(r, s, a, b) => match s.0 {
+                    Atom::ATAN2 => self.stack[*r] = self.stack[*a].atan2(&self.stack[*b]),
+                    Atom::MAX => self.stack[*r] = self.stack[*a].max(&self.stack[*b]),
+                    Atom::MIN => self.stack[*r] = self.stack[*a].min(&self.stack[*b]),
+                    _ => unreachable!(),
+                },
                 Instr::BuiltinFun(r, s, arg) => match s.0 {
                     Atom::EXP => self.stack[*r] = self.stack[*arg].exp(),
                     Atom::LOG => self.stack[*r] = self.stack[*arg].log(),
                     Atom::SIN => self.stack[*r] = self.stack[*arg].sin(),
                     Atom::COS => self.stack[*r] = self.stack[*arg].cos(),
                     Atom::SQRT => self.stack[*r] = self.stack[*arg].sqrt(),
+                    Atom::TAN => self.stack[*r] = self.stack[*arg].tan(),
+                    Atom::SINH => self.stack[*r] = self.stack[*arg].sinh(),
+                    Atom::COSH => self.stack[*r] = self.stack[*arg].cosh(),
+                    Atom::TANH => self.stack[*r] = self.stack[*arg].tanh(),
+                    Atom::ASIN => self.stack[*r] = self.stack[*arg].asin(),
+                    Atom::ACOS => self.stack[*r] = self.stack[*arg].acos(),
+                    Atom::ATAN => self.stack[*r] = self.stack[*arg].atan2(&self.stack[*arg].one()),
                     _ => unreachable!(),
                 },
             }
@@ -781,6 +846,259 @@ impl<T: Real> ExpressionEvaluator<T> {
             *o = self.stack[*i].clone();
         }
     }
+
+    /// Create an evaluator that computes the gradient of every expression with respect
+    /// to the parameters with indices `vars`, alongside its value, in a single pass
+    /// using forward-mode automatic differentiation.
+    pub fn with_gradient(self, vars: &[usize]) -> GradientEvaluator<T> {
+        assert!(
+            vars.iter().all(|v| *v < self.param_count),
+            "Variable index out of range"
+        );
+
+        GradientEvaluator {
+            gradient: vec![T::new_zero(); self.stack.len() * vars.len()],
+            vars: vars.to_vec(),
+            evaluator: self,
+        }
+    }
+}
+
+/// An evaluator that computes the values of expressions together with their gradient
+/// with respect to a subset of the parameters. Create it using [ExpressionEvaluator::with_gradient].
+#[derive(Clone)]
+pub struct GradientEvaluator<T> {
+    evaluator: ExpressionEvaluator<T>,
+    vars: Vec<usize>,
+    gradient: Vec<T>,
+}
+
+impl<T: Real> GradientEvaluator<T> {
+    /// Evaluate the expressions and their gradients at `params`. The derivative of
+    /// expression `i` with respect to the `j`th variable is written to `grad[i * vars.len() + j]`.
+    pub fn evaluate(&mut self, params: &[T], out: &mut [T], grad: &mut [T]) {
+        let n = self.vars.len();
+        let stack = &mut self.evaluator.stack;
+        let g = &mut self.gradient;
+
+        for (t, p) in stack.iter_mut().zip(params) {
+            *t = p.clone();
+        }
+
+        // the parameters are seeded with unit tangents and the constants have zero tangents
+        let zero = params.first().map(|p| p.zero()).unwrap_or(T::new_zero());
+        for x in g[..self.evaluator.reserved_indices * n].iter_mut() {
+            *x = zero.clone();
+        }
+        for (j, v) in self.vars.iter().enumerate() {
+            g[v * n + j] = zero.one();
+        }
+
+        let mut tmp_grad = vec![zero.clone(); n];
+        for i in &self.evaluator.instructions {
+            match i {
+                Instr::Add(r, v) => {
+                    let mut tmp = stack[v[0]].clone();
+                    tmp_grad.clone_from_slice(&g[v[0] * n..(v[0] + 1) * n]);
+                    for x in &v[1..] {
+                        tmp += &stack[*x];
+                        for (t, d) in tmp_grad.iter_mut().zip(&g[*x * n..(*x + 1) * n]) {
+                            *t += d;
+                        }
+                    }
+                    stack[*r] = tmp;
+                    g[*r * n..(*r + 1) * n].clone_from_slice(&tmp_grad);
+                }
+                Instr::Mul(r, v) => {
+                    let mut tmp = stack[v[0]].clone();
+                    tmp_grad.clone_from_slice(&g[v[0] * n..(v[0] + 1) * n]);
+                    for x in &v[1..] {
+                        for (t, d) in tmp_grad.iter_mut().zip(&g[*x * n..(*x + 1) * n]) {
+                            *t = t.clone() * &stack[*x] + tmp.clone() * d;
+                        }
+                        tmp *= &stack[*x];
+                    }
+                    stack[*r] = tmp;
+                    g[*r * n..(*r + 1) * n].clone_from_slice(&tmp_grad);
+                }
+                Instr::Pow(r, b, e) => {
+                    // d(b^e) = e * b^(e-1) * db
+                    let f = if *e == 0 {
+                        stack[*b].zero()
+                    } else if *e > 0 {
+                        stack[*b].pow(*e as u64 - 1) * stack[*b].from_i64(*e)
+                    } else {
+                        stack[*b].pow(e.unsigned_abs() + 1).inv() * stack[*b].from_i64(*e)
+                    };
+
+                    stack[*r] = if *e >= 0 {
+                        stack[*b].pow(*e as u64)
+                    } else {
+                        stack[*b].pow(e.unsigned_abs()).inv()
+                    };
+
+                    for (t, d) in tmp_grad.iter_mut().zip(&g[*b * n..(*b + 1) * n]) {
+                        *t = f.clone() * d;
+                    }
+                    g[*r * n..(*r + 1) * n].clone_from_slice(&tmp_grad);
+                }
+                Instr::Powf(r, b, e) => {
+                    // d(b^e) = b^e * (de * log(b) + e * db / b)
+                    let res = stack[*b].powf(&stack[*e]);
+                    let log_b = stack[*b].log();
+                    let e_over_b = stack[*e].clone() / &stack[*b];
+
+                    for ((t, db), de) in tmp_grad
+                        .iter_mut()
+                        .zip(&g[*b * n..(*b + 1) * n])
+                        .zip(&g[*e * n..(*e + 1) * n])
+                    {
+                        *t = res.clone() * (de.clone() * &log_b + e_over_b.clone() * db);
+                    }
+
+                    stack[*r] = res;
+                    g[*r * n..(*r + 1) * n].clone_from_slice(&tmp_grad);
+                }
+                Instr::BuiltinFun(r, s, arg) => {
+                    let a = &stack[*arg];
+                    let (res, f) = match s.0 {
+                        Atom::EXP => {
+                            let res = a.exp();
+                            (res.clone(), res)
+                        }
+                        Atom::LOG => (a.log(), a.inv()),
+                        Atom::SIN => (a.sin(), a.cos()),
+                        Atom::COS => (a.cos(), -a.sin()),
+                        Atom::SQRT => {
+                            let res = a.sqrt();
+                            let f = (res.clone() * a.from_usize(2)).inv();
+                            (res, f)
+                        }
+                        Atom::TAN => {
+                            let res = a.tan();
+                            let f = res.clone() * &res + a.one();
+                            (res, f)
+                        }
+                        Atom::SINH => (a.sinh(), a.cosh()),
+                        Atom::COSH => (a.cosh(), a.sinh()),
+                        Atom::TANH => {
+                            let res = a.tanh();
+                            let f = a.one() - res.clone() * &res;
+                            (res, f)
+                        }
+                        Atom::ASIN => {
+                            let f = (a.one() - a.clone() * a).sqrt().inv();
+                            (a.asin(), f)
+                        }
+                        Atom::ACOS => {
+                            let f = -(a.one() - a.clone() * a).sqrt().inv();
+                            (a.acos(), f)
+                        }
+                        Atom::ATAN => {
+                            let f = (a.one() + a.clone() * a).inv();
+                            (a.atan2(&a.one()), f)
+                        }
+                        _ => unreachable!(),
+                    };
+
+                    for (t, d) in tmp_grad.iter_mut().zip(&g[*arg * n..(*arg + 1) * n]) {
+                        *t = f.clone() * d;
+                    }
+                    stack[*r] = res;
+                    g[*r * n..(*r + 1) * n].clone_from_slice(&tmp_grad);
+                }
+                Instr::BuiltinBinaryFun(r, s, a, b) => {
+                    let (x, y) = (&stack[*a], &stack[*b]);
+                    // the tangent of the result is `fa * dx + fb * dy`
+                    let (res, fa, fb) = match s.0 {
+                        Atom::ATAN2 => {
+                            // d(atan2(x, y)) = (y * dx - x * dy) / (x^2 + y^2)
+                            let norm = (x.clone() * x + y.clone() * y).inv();
+                            (x.atan2(y), y.clone() * &norm, -(x.clone() * &norm))
+                        }
+                        Atom::MAX | Atom::MIN => {
+                            // the tangent is that of the active argument
+                            let res = if s.0 == Atom::MAX { x.max(y) } else { x.min(y) };
+                            if res == *x {
+                                (res, x.one(), x.zero())
+                            } else if res == *y {
+                                (res, y.zero(), y.one())
+                            } else {
+                                // the SIMD lanes disagree, so select the tangent per lane
+                                let fa = (res.clone() - y) / &(x.clone() - y);
+                                let fb = fa.one() - &fa;
+                                (res, fa, fb)
+                            }
+                        }
+                        _ => unreachable!(),
+                    };
+
+                    for ((t, da), db) in tmp_grad
+                        .iter_mut()
+                        .zip(&g[*a * n..(*a + 1) * n])
+                        .zip(&g[*b * n..(*b + 1) * n])
+                    {
+                        *t = fa.clone() * da + fb.clone() * db;
+                    }
+
+                    stack[*r] = res;
+                    g[*r * n..(*r + 1) * n].clone_from_slice(&tmp_grad);
+                }
+            }
+        }
+
+        for (o, i) in out.iter_mut().zip(&self.evaluator.result_indices) {
+            *o = stack[*i].clone();
+        }
+
+        for (o, i) in grad
+            .chunks_mut(n.max(1))
+            .zip(&self.evaluator.result_indices)
+        {
+            o.clone_from_slice(&g[*i * n..(*i + 1) * n]);
+        }
+    }
+}
+
+impl ExpressionEvaluator<f64> {
+    /// Convert the evaluator to one that evaluates four points at a time in SIMD lanes.
+    /// The conversion copies the evaluator, so the result should be kept to evaluate
+    /// multiple batches with [ExpressionEvaluator::evaluate_batch_simd].
+    pub fn to_simd(&self) -> ExpressionEvaluator<f64x4> {
+        self.clone().map_coeff(&|x| f64x4::splat(*x))
+    }
+}
+
+impl ExpressionEvaluator<f64x4> {
+    /// Evaluate the expressions at every point in `points`, like [ExpressionEvaluator::evaluate_batch],
+    /// but process four points at a time in SIMD lanes. If the number of points is not a multiple
+    /// of four, the unused lanes of the last evaluation are filled with copies of the last point.
+    pub fn evaluate_batch_simd(&mut self, points: &[Vec<f64>]) -> Vec<f64> {
+        let n = self.result_indices.len();
+        if n == 0 {
+            return vec![];
+        }
+
+        let mut params = vec![f64x4::ZERO; self.param_count];
+        let mut res = vec![f64x4::ZERO; n];
+
+        let mut out = vec![0.; points.len() * n];
+        for (c, o) in points.chunks(4).zip(out.chunks_mut(4 * n)) {
+            for (i, p) in params.iter_mut().enumerate() {
+                *p = f64x4::new(std::array::from_fn(|lane| c[lane.min(c.len() - 1)][i]));
+            }
+
+            self.evaluate(&params, &mut res);
+
+            for (i, r) in res.iter().enumerate() {
+                for (lane, v) in r.to_array().into_iter().take(c.len()).enumerate() {
+                    o[lane * n + i] = v;
+                }
+            }
+        }
+
+        out
+    }
 }
 
 impl<T: Default> ExpressionEvaluator<T> {
@@ -1009,7 +1327,7 @@ impl<T: Default> ExpressionEvaluator<T> {
                         *b = rename!(*b);
                         *p = new_pos;
                     }
-                    Instr::Powf(p, a, b) => {
+                    Instr::Powf(p, a, b) | Instr::BuiltinBinaryFun(p, _, a, b) => {
                         *a = rename!(*a);
                         *b = rename!(*b);
                         *p = new_pos;
@@ -1082,7 +1400,7 @@ impl<T: Default + Clone + Eq + Hash> ExpressionEvaluator<T> {
                             *b += delta;
                         }
                     }
-                    Instr::Powf(r, b, e) => {
+                    Instr::Powf(r, b, e) | Instr::BuiltinBinaryFun(r, _, b, e) => {
                         *r += delta;
                         if *b >= self.reserved_indices {
                             *b += delta;
@@ -1120,7 +1438,7 @@ impl<T: Default + Clone + Eq + Hash> ExpressionEvaluator<T> {
                         *b = self.param_count + constants[&other.stack[*b]];
                     }
                 }
-                Instr::Powf(r, b, e) => {
+                Instr::Powf(r, b, e) | Instr::BuiltinBinaryFun(r, _, b, e) => {
                     *r += delta;
                     if *b >= other.reserved_indices {
                         *b += delta;
@@ -1169,7 +1487,7 @@ impl<T: Default + Clone + Eq + Hash> ExpressionEvaluator<T> {
                     unfold.insert(*r, index + self.reserved_indices);
                     *r = index + self.reserved_indices;
                 }
-                Instr::Powf(r, b, e) => {
+                Instr::Powf(r, b, e) | Instr::BuiltinBinaryFun(r, _, b, e) => {
                     if *b >= self.reserved_indices {
                         *b = unfold[b];
                     }
@@ -1205,6 +1523,18 @@ impl<T: Default + Clone + Eq + Hash> ExpressionEvaluator<T> {
 }
 
 impl<T> ExpressionEvaluator<T> {
+    fn get_binary_functions(&self) -> Vec<BuiltinSymbol> {
+        let mut funs = vec![];
+        for i in &self.instructions {
+            if let Instr::BuiltinBinaryFun(_, s, _, _) = i {
+                if !funs.contains(s) {
+                    funs.push(*s);
+                }
+            }
+        }
+        funs
+    }
+
     pub fn optimize_stack(&mut self) {
         let mut last_use: Vec<usize> = vec![0; self.stack.len()];
 
@@ -1218,7 +1548,7 @@ impl<T> ExpressionEvaluator<T> {
                 Instr::Pow(_, b, _) | Instr::BuiltinFun(_, _, b) => {
                     last_use[*b] = i;
                 }
-                Instr::Powf(_, a, b) => {
+                Instr::Powf(_, a, b) | Instr::BuiltinBinaryFun(_, _, a, b) => {
                     last_use[*a] = i;
                     last_use[*b] = i;
                 }
@@ -1244,7 +1574,8 @@ impl<T> ExpressionEvaluator<T> {
                 | Instr::Mul(r, _)
                 | Instr::Pow(r, _, _)
                 | Instr::Powf(r, _, _)
-                | Instr::BuiltinFun(r, _, _) => *r,
+                | Instr::BuiltinFun(r, _, _)
+                | Instr::BuiltinBinaryFun(r, _, _, _) => *r,
             };
 
             let cur_last_use = last_use[cur_reg];
@@ -1276,7 +1607,7 @@ impl<T> ExpressionEvaluator<T> {
                     *r = new_reg;
                     *b = rename_map[*b];
                 }
-                Instr::Powf(r, a, b) => {
+                Instr::Powf(r, a, b) | Instr::BuiltinBinaryFun(r, _, a, b) => {
                     *r = new_reg;
                     *a = rename_map[*a];
                     *b = rename_map[*b];
@@ -1292,12 +1623,49 @@ impl<T> ExpressionEvaluator<T> {
     }
 }
 
+/// Get the C++ code for the binary built-in function `s` applied to `a` and `b`.
+fn cpp_binary_fun(s: BuiltinSymbol, a: &str, b: &str) -> String {
+    match s.0 {
+        Atom::ATAN2 => format!("symbolica_atan2({}, {})", a, b),
+        Atom::MAX => format!("symbolica_max({}, {})", a, b),
+        Atom::MIN => format!("symbolica_min({}, {})", a, b),
+        _ => unreachable!(),
+    }
+}
+
+/// Get the C++ definitions of the helper functions that [cpp_binary_fun] uses for `funs`.
+/// The helpers are templated so that they work for `double` and `std::complex<double>`,
+/// for which the real part of the arguments is used.
+fn cpp_helpers(funs: &[BuiltinSymbol]) -> String {
+    let mut res = String::new();
+    for s in funs {
+        match s.0 {
+            Atom::ATAN2 => {
+                res += "template<typename T>\nT symbolica_atan2(T y, T x) {\n\treturn std::atan2(y, x);\n}\n\n";
+                res += "template<typename T>\nstd::complex<T> symbolica_atan2(std::complex<T> y, std::complex<T> x) {\n\tstatic_assert(sizeof(T) == 0, \"atan2 is not supported for complex arguments\");\n\treturn y;\n}\n\n";
+            }
+            Atom::MAX => {
+                res += "template<typename T>\nT symbolica_max(T a, T b) {\n\treturn std::real(b - a) > 0 ? b : a;\n}\n\n";
+            }
+            Atom::MIN => {
+                res += "template<typename T>\nT symbolica_min(T a, T b) {\n\treturn std::real(a - b) > 0 ? b : a;\n}\n\n";
+            }
+            _ => unreachable!(),
+        }
+    }
+    res
+}
+
 impl<T: std::fmt::Display> ExpressionEvaluator<T> {
     /// Create a C++ code representation of the evaluation tree.
     /// With `inline_asm` set to any value other than `None`,
     /// high-performance inline ASM code will be generated for most
     /// evaluation instructions. This often gives better performance than
     /// the `O3` optimization level and results in very fast compilation.
+    ///
+    /// The helpers for `max` and `min` compare the real parts of complex numbers.
+    /// `atan2` is only supported for real numbers: instantiating it with complex
+    /// numbers is a compile-time error.
     pub fn export_cpp(
         &self,
         filename: &str,
@@ -1335,6 +1703,8 @@ impl<T: std::fmt::Display> ExpressionEvaluator<T> {
             self.stack.len()
         );
 
+        res += &cpp_helpers(&self.get_binary_functions());
+
         res += &format!(
             "\ntemplate<typename T>\nvoid {}(T* params, T* Z, T* out) {{\n",
             function_name
@@ -1421,8 +1791,41 @@ impl<T: std::fmt::Display> ExpressionEvaluator<T> {
                         let arg = format!("Z{}", a);
                         *out += format!("\tZ{} = sqrt({});\n", o, arg).as_str();
                     }
+                    Atom::TAN => {
+                        let arg = format!("Z{}", a);
+                        *out += format!("\tZ{} = tan({});\n", o, arg).as_str();
+                    }
+                    Atom::SINH => {
+                        let arg = format!("Z{}", a);
+                        *out += format!("\tZ{} = sinh({});\n", o, arg).as_str();
+                    }
+                    Atom::COSH => {
+                        let arg = format!("Z{}", a);
+                        *out += format!("\tZ{} = cosh({});\n", o, arg).as_str();
+                    }
+                    Atom::TANH => {
+                        let arg = format!("Z{}", a);
+                        *out += format!("\tZ{} = tanh({});\n", o, arg).as_str();
+                    }
+                    Atom::ASIN => {
+                        let arg = format!("Z{}", a);
+                        *out += format!("\tZ{} = asin({});\n", o, arg).as_str();
+                    }
+                    Atom::ACOS => {
+                        let arg = format!("Z{}", a);
+                        *out += format!("\tZ{} = acos({});\n", o, arg).as_str();
+                    }
+                    Atom::ATAN => {
+                        let arg = format!("Z{}", a);
+                        *out += format!("\tZ{} = atan({});\n", o, arg).as_str();
+                    }
                     _ => unreachable!(),
                 },
+                Instr::BuiltinBinaryFun(o, s, a, b) => {
+                    let arg1 = format!("Z{}", a);
+                    let arg2 = format!("Z{}", b);
+                    *out += format!("\tZ{} = {};\n", o, cpp_binary_fun(*s, &arg1, &arg2)).as_str();
+                }
             }
         }
     }
@@ -1444,6 +1847,8 @@ impl<T: std::fmt::Display> ExpressionEvaluator<T> {
             self.stack.len()
         );
 
+        res += &cpp_helpers(&self.get_binary_functions());
+
         res += &format!(
             "static const std::complex<double> {}_CONSTANTS_complex[{}] = {{{}}};\n\n",
             function_name,
@@ -1627,7 +2032,7 @@ impl<T: std::fmt::Display> ExpressionEvaluator<T> {
                     }
                     stack_to_reg.insert(r, i);
                 }
-                Instr::Powf(r, b, e) => {
+                Instr::Powf(r, b, e) | Instr::BuiltinBinaryFun(r, _, b, e) => {
                     if b >= &self.reserved_indices {
                         reg_last_use[stack_to_reg[b]] = i;
                     }
@@ -1658,6 +2063,7 @@ impl<T: std::fmt::Display> ExpressionEvaluator<T> {
             Pow(MemOrReg, u16, MemOrReg, i64),
             Powf(usize, usize, usize),
             BuiltinFun(usize, BuiltinSymbol, usize),
+            BuiltinBinaryFun(usize, BuiltinSymbol, usize, usize),
         }
 
         let mut new_instr: Vec<RegInstr> = instr
@@ -1678,6 +2084,7 @@ impl<T: std::fmt::Display> ExpressionEvaluator<T> {
                 }
                 Instr::Powf(r, b, e) => RegInstr::Powf(*r, *b, *e),
                 Instr::BuiltinFun(r, s, a) => RegInstr::BuiltinFun(*r, *s, *a),
+                Instr::BuiltinBinaryFun(r, s, a, b) => RegInstr::BuiltinBinaryFun(*r, *s, *a, *b),
             })
             .collect();
 
@@ -1751,7 +2158,7 @@ impl<T: std::fmt::Display> ExpressionEvaluator<T> {
                         RegInstr::Pow(_, _, _, _) => {
                             panic!("use outside of ASM block");
                         }
-                        RegInstr::Powf(_, a, b) => {
+                        RegInstr::Powf(_, a, b) | RegInstr::BuiltinBinaryFun(_, _, a, b) => {
                             if *a == old_reg {
                                 panic!("use outside of ASM block");
                             }
@@ -2306,9 +2713,38 @@ impl<T: std::fmt::Display> ExpressionEvaluator<T> {
                         Atom::SQRT => {
                             *out += format!("\tZ[{}] = sqrt({});\n", o, arg).as_str();
                         }
+                        Atom::TAN => {
+                            *out += format!("\tZ[{}] = tan({});\n", o, arg).as_str();
+                        }
+                        Atom::SINH => {
+                            *out += format!("\tZ[{}] = sinh({});\n", o, arg).as_str();
+                        }
+                        Atom::COSH => {
+                            *out += format!("\tZ[{}] = cosh({});\n", o, arg).as_str();
+                        }
+                        Atom::TANH => {
+                            *out += format!("\tZ[{}] = tanh({});\n", o, arg).as_str();
+                        }
+                        Atom::ASIN => {
+                            *out += format!("\tZ[{}] = asin({});\n", o, arg).as_str();
+                        }
+                        Atom::ACOS => {
+                            *out += format!("\tZ[{}] = acos({});\n", o, arg).as_str();
+                        }
+                        Atom::ATAN => {
+                            *out += format!("\tZ[{}] = atan({});\n", o, arg).as_str();
+                        }
                         _ => unreachable!(),
                     }
                 }
+                RegInstr::BuiltinBinaryFun(o, s, a, b) => {
+                    end_asm_block!(in_asm_block);
+
+                    let arg1 = get_input!(*a);
+                    let arg2 = get_input!(*b);
+                    *out +=
+                        format!("\tZ[{}] = {};\n", o, cpp_binary_fun(*s, &arg1, &arg2)).as_str();
+                }
             }
         }
 
@@ -2726,9 +3162,38 @@ impl<T: std::fmt::Display> ExpressionEvaluator<T> {
                         Atom::SQRT => {
                             *out += format!("\tZ[{}] = sqrt({});\n", o, arg).as_str();
                         }
+                        Atom::TAN => {
+                            *out += format!("\tZ[{}] = tan({});\n", o, arg).as_str();
+                        }
+                        Atom::SINH => {
+                            *out += format!("\tZ[{}] = sinh({});\n", o, arg).as_str();
+                        }
+                        Atom::COSH => {
+                            *out += format!("\tZ[{}] = cosh({});\n", o, arg).as_str();
+                        }
+                        Atom::TANH => {
+                            *out += format!("\tZ[{}] = tanh({});\n", o, arg).as_str();
+                        }
+                        Atom::ASIN => {
+                            *out += format!("\tZ[{}] = asin({});\n", o, arg).as_str();
+                        }
+                        Atom::ACOS => {
+                            *out += format!("\tZ[{}] = acos({});\n", o, arg).as_str();
+                        }
+                        Atom::ATAN => {
+                            *out += format!("\tZ[{}] = atan({});\n", o, arg).as_str();
+                        }
                         _ => unreachable!(),
                     }
                 }
+                Instr::BuiltinBinaryFun(o, s, a, b) => {
+                    end_asm_block!(in_asm_block);
+
+                    let arg1 = get_input!(*a);
+                    let arg2 = get_input!(*b);
+                    *out +=
+                        format!("\tZ[{}] = {};\n", o, cpp_binary_fun(*s, &arg1, &arg2)).as_str();
+                }
             }
         }
 
@@ -2842,6 +3307,9 @@ pub enum Instruction {
     /// `Fun(o, s, a)` means `o = s(a)`, where `s` is assumed to
     /// be a built-in function such as `sin`.
     Fun(Slot, BuiltinSymbol, Slot),
+    /// `BinaryFun(o, s, a, b)` means `o = s(a, b)`, where `s` is assumed to
+    /// be a built-in function such as `atan2`.
+    BinaryFun(Slot, BuiltinSymbol, Slot, Slot),
 }
 
 impl std::fmt::Display for Instruction {
@@ -2878,6 +3346,9 @@ impl std::fmt::Display for Instruction {
             Instruction::Fun(o, s, a) => {
                 write!(f, "{} = {}({})", o, s.0, a)
             }
+            Instruction::BinaryFun(o, s, a, b) => {
+                write!(f, "{} = {}({}, {})", o, s.0, a, b)
+            }
         }
     }
 }
@@ -2935,6 +3406,14 @@ impl<T: Clone> ExpressionEvaluator<T> {
                 Instr::BuiltinFun(o, s, a) => {
                     instr.push(Instruction::Fun(get_slot!(*o), s.clone(), get_slot!(*a)));
                 }
+                Instr::BuiltinBinaryFun(o, s, a, b) => {
+                    instr.push(Instruction::BinaryFun(
+                        get_slot!(*o),
+                        s.clone(),
+                        get_slot!(*a),
+                        get_slot!(*b),
+                    ));
+                }
             }
         }
 
@@ -2951,6 +3430,7 @@ enum Instr {
     Pow(usize, usize, i64),
     Powf(usize, usize, usize),
     BuiltinFun(usize, BuiltinSymbol, usize),
+    BuiltinBinaryFun(usize, BuiltinSymbol, usize, usize),
 }
 
 impl<T: Clone + PartialEq> SplitExpression<T> {
@@ -2988,6 +3468,10 @@ impl<T: Clone + PartialEq> Expression<T> {
             }
             Expression::ReadArg(s) => Expression::ReadArg(*s),
             Expression::BuiltinFun(s, a) => Expression::BuiltinFun(*s, Box::new(a.map_coeff(f))),
+            Expression::BuiltinBinaryFun(s, a) => {
+                let (a, b) = &**a;
+                Expression::BuiltinBinaryFun(*s, Box::new((a.map_coeff(f), b.map_coeff(f))))
+            }
             Expression::SubExpression(i) => Expression::SubExpression(*i),
         }
     }
@@ -3016,7 +3500,7 @@ impl<T: Clone + PartialEq> Expression<T> {
             Expression::Pow(p) => {
                 p.0.strip_constants(stack, param_len);
             }
-            Expression::Powf(p) => {
+            Expression::Powf(p) | Expression::BuiltinBinaryFun(_, p) => {
                 p.0.strip_constants(stack, param_len);
                 p.1.strip_constants(stack, param_len);
             }
@@ -3027,6 +3511,37 @@ impl<T: Clone + PartialEq> Expression<T> {
             Expression::SubExpression(_) => {}
         }
     }
+
+    fn get_binary_functions(&self, funs: &mut Vec<BuiltinSymbol>) {
+        match self {
+            Expression::Const(_)
+            | Expression::Parameter(_)
+            | Expression::ReadArg(_)
+            | Expression::SubExpression(_) => {}
+            Expression::Eval(_, a) | Expression::Add(a) | Expression::Mul(a) => {
+                for arg in a {
+                    arg.get_binary_functions(funs);
+                }
+            }
+            Expression::Pow(p) => {
+                p.0.get_binary_functions(funs);
+            }
+            Expression::Powf(p) => {
+                p.0.get_binary_functions(funs);
+                p.1.get_binary_functions(funs);
+            }
+            Expression::BuiltinFun(_, a) => {
+                a.get_binary_functions(funs);
+            }
+            Expression::BuiltinBinaryFun(s, p) => {
+                if !funs.contains(s) {
+                    funs.push(*s);
+                }
+                p.0.get_binary_functions(funs);
+                p.1.get_binary_functions(funs);
+            }
+        }
+    }
 }
 
 impl<T: Clone + PartialEq> EvalTree<T> {
@@ -3217,6 +3732,14 @@ impl<T: Clone + Default + PartialEq> EvalTree<T> {
                 instr.push(c);
                 stack.len() - 1
             }
+            Expression::BuiltinBinaryFun(s, v) => {
+                let a = self.linearize_impl(&v.0, subexpressions, stack, instr, sub_expr_pos, args);
+                let b = self.linearize_impl(&v.1, subexpressions, stack, instr, sub_expr_pos, args);
+                stack.push(T::default());
+                let c = Instr::BuiltinBinaryFun(stack.len() - 1, *s, a, b);
+                instr.push(c);
+                stack.len() - 1
+            }
             Expression::SubExpression(id) => {
                 if sub_expr_pos.contains_key(id) {
                     *sub_expr_pos.get(id).unwrap()
@@ -3375,7 +3898,7 @@ impl Expression<Rational> {
                 b.0.apply_horner_scheme(scheme);
                 return;
             }
-            Expression::Powf(b) => {
+            Expression::Powf(b) | Expression::BuiltinBinaryFun(_, b) => {
                 b.0.apply_horner_scheme(scheme);
                 b.1.apply_horner_scheme(scheme);
                 return;
@@ -3595,7 +4118,7 @@ impl Expression<Rational> {
             Expression::Pow(p) => {
                 p.0.occurrence_order_horner_scheme();
             }
-            Expression::Powf(p) => {
+            Expression::Powf(p) | Expression::BuiltinBinaryFun(_, p) => {
                 p.0.occurrence_order_horner_scheme();
                 p.1.occurrence_order_horner_scheme();
             }
@@ -3787,7 +4310,7 @@ impl Expression<Rational> {
             Expression::Pow(p) => {
                 p.0.find_all_variables(vars);
             }
-            Expression::Powf(p) => {
+            Expression::Powf(p) | Expression::BuiltinBinaryFun(_, p) => {
                 p.0.find_all_variables(vars);
                 p.1.find_all_variables(vars);
             }
@@ -3917,7 +4440,7 @@ impl<T: Clone + Default + std::fmt::Debug + Eq + std::hash::Hash + Ord> Expressi
             Expression::Pow(p) => {
                 p.0.rename_subexpression(subexp);
             }
-            Expression::Powf(p) => {
+            Expression::Powf(p) | Expression::BuiltinBinaryFun(_, p) => {
                 p.0.rename_subexpression(subexp);
                 p.1.rename_subexpression(subexp);
             }
@@ -3946,7 +4469,7 @@ impl<T: Clone + Default + std::fmt::Debug + Eq + std::hash::Hash + Ord> Expressi
             Expression::Pow(p) => {
                 p.0.get_dependent_subexpressions(dep);
             }
-            Expression::Powf(p) => {
+            Expression::Powf(p) | Expression::BuiltinBinaryFun(_, p) => {
                 p.0.get_dependent_subexpressions(dep);
                 p.1.get_dependent_subexpressions(dep);
             }
@@ -4027,6 +4550,11 @@ impl<T: Clone + Default + std::fmt::Debug + Eq + std::hash::Hash + Ord> Expressi
             }
             Expression::ReadArg(_) => (0, 0),
             Expression::BuiltinFun(_, b) => b.count_operations(), // not clear how to count this, third arg?
+            Expression::BuiltinBinaryFun(_, p) => {
+                let (a, m) = p.0.count_operations();
+                let (a2, m2) = p.1.count_operations();
+                (a + a2, m + m2)
+            }
             Expression::SubExpression(_) => (0, 0),
         }
     }
@@ -4095,6 +4623,11 @@ impl<T: Clone + Default + std::fmt::Debug + Eq + std::hash::Hash + Ord> Expressi
             }
             Expression::ReadArg(_) => (0, 0),
             Expression::BuiltinFun(_, b) => b.count_operations_with_subexpression(sub_expr), // not clear how to count this, third arg?
+            Expression::BuiltinBinaryFun(_, p) => {
+                let (a, m) = p.0.count_operations_with_subexpression(sub_expr);
+                let (a2, m2) = p.1.count_operations_with_subexpression(sub_expr);
+                (a + a2, m + m2)
+            }
             Expression::SubExpression(_) => (0, 0),
         }
     }
@@ -4166,6 +4699,23 @@ impl<T: Real> EvalTree<T> {
                     Atom::SIN => arg.sin(),
                     Atom::COS => arg.cos(),
                     Atom::SQRT => arg.sqrt(),
+                    Atom::TAN => arg.tan(),
+                    Atom::SINH => arg.sinh(),
+                    Atom::COSH => arg.cosh(),
+                    Atom::TANH => arg.tanh(),
+                    Atom::ASIN => arg.asin(),
+                    Atom::ACOS => arg.acos(),
+                    Atom::ATAN => arg.atan2(&arg.one()),
+                    _ => unreachable!(),
+                }
+            }
+            Expression::BuiltinBinaryFun(s, p) => {
+                let a = self.evaluate_impl(&p.0, subexpressions, params, args);
+                let b = self.evaluate_impl(&p.1, subexpressions, params, args);
+                match s.0 {
+                    Atom::ATAN2 => a.atan2(&b),
+                    Atom::MAX => a.max(&b),
+                    Atom::MIN => a.min(&b),
                     _ => unreachable!(),
                 }
             }
@@ -4469,6 +5019,18 @@ impl<T: NumericalFloatLike> EvalTree<T> {
             String::new()
         };
 
+        let mut binary_funs = vec![];
+        for e in self
+            .functions
+            .iter()
+            .flat_map(|(_, _, body)| body.tree.iter().chain(&body.subexpressions))
+            .chain(&self.expressions.tree)
+            .chain(&self.expressions.subexpressions)
+        {
+            e.get_binary_functions(&mut binary_funs);
+        }
+        res += &cpp_helpers(&binary_funs);
+
         for (name, arg_names, body) in &self.functions {
             let mut args = arg_names
                 .iter()
@@ -4601,8 +5163,55 @@ impl<T: NumericalFloatLike> EvalTree<T> {
                     r.push(')');
                     r
                 }
+                Atom::TAN => {
+                    let mut r = "tan(".to_string();
+                    r += &self.export_cpp_impl(a, args);
+                    r.push(')');
+                    r
+                }
+                Atom::SINH => {
+                    let mut r = "sinh(".to_string();
+                    r += &self.export_cpp_impl(a, args);
+                    r.push(')');
+                    r
+                }
+                Atom::COSH => {
+                    let mut r = "cosh(".to_string();
+                    r += &self.export_cpp_impl(a, args);
+                    r.push(')');
+                    r
+                }
+                Atom::TANH => {
+                    let mut r = "tanh(".to_string();
+                    r += &self.export_cpp_impl(a, args);
+                    r.push(')');
+                    r
+                }
+                Atom::ASIN => {
+                    let mut r = "asin(".to_string();
+                    r += &self.export_cpp_impl(a, args);
+                    r.push(')');
+                    r
+                }
+                Atom::ACOS => {
+                    let mut r = "acos(".to_string();
+                    r += &self.export_cpp_impl(a, args);
+                    r.push(')');
+                    r
+                }
+                Atom::ATAN => {
+                    let mut r = "atan(".to_string();
+                    r += &self.export_cpp_impl(a, args);
+                    r.push(')');
+                    r
+                }
                 _ => unreachable!(),
             },
+            Expression::BuiltinBinaryFun(s, p) => cpp_binary_fun(
+                *s,
+                &self.export_cpp_impl(&p.0, args),
+                &self.export_cpp_impl(&p.1, args),
+            ),
             Expression::SubExpression(id) => {
                 format!("Z{}_", id)
             }
@@ -4610,6 +5219,24 @@ impl<T: NumericalFloatLike> EvalTree<T> {
     }
 }
 
+/// Get the index of the largest (`max = true`) or smallest argument, using [Real::max] and
+/// [Real::min]. At ties, the first argument is picked.
+fn extremum_index<T: Real>(args: &[T], max: bool) -> usize {
+    let mut index = 0;
+    for (i, a) in args.iter().enumerate().skip(1) {
+        let m = if max {
+            args[index].max(a)
+        } else {
+            args[index].min(a)
+        };
+
+        if m != args[index] {
+            index = i;
+        }
+    }
+    index
+}
+
 impl<'a> AtomView<'a> {
     /// Convert nested expressions to a tree.
     pub fn to_evaluation_tree(
@@ -4686,7 +5313,22 @@ impl<'a> AtomView<'a> {
             }
             AtomView::Fun(f) => {
                 let name = f.get_symbol();
-                if [Atom::EXP, Atom::LOG, Atom::SIN, Atom::COS, Atom::SQRT].contains(&name) {
+                if [
+                    Atom::EXP,
+                    Atom::LOG,
+                    Atom::SIN,
+                    Atom::COS,
+                    Atom::SQRT,
+                    Atom::TAN,
+                    Atom::SINH,
+                    Atom::COSH,
+                    Atom::TANH,
+                    Atom::ASIN,
+                    Atom::ACOS,
+                    Atom::ATAN,
+                ]
+                .contains(&name)
+                {
                     assert!(f.get_nargs() == 1);
                     let arg = f.iter().next().unwrap();
                     let arg_eval = arg.to_eval_tree_impl(fn_map, params, args, funcs)?;
@@ -4697,6 +5339,38 @@ impl<'a> AtomView<'a> {
                     ));
                 }
 
+                if name == Atom::ATAN2 {
+                    assert!(f.get_nargs() == 2);
+                    let mut it = f.iter();
+                    let y = it.next().unwrap();
+                    let x = it.next().unwrap();
+                    let y_eval = y.to_eval_tree_impl(fn_map, params, args, funcs)?;
+                    let x_eval = x.to_eval_tree_impl(fn_map, params, args, funcs)?;
+
+                    return Ok(Expression::BuiltinBinaryFun(
+                        BuiltinSymbol(name),
+                        Box::new((y_eval, x_eval)),
+                    ));
+                }
+
+                if name == Atom::MAX || name == Atom::MIN {
+                    // fold into nested binary functions, so that the first argument is
+                    // still picked at ties
+                    let mut res = None;
+                    for arg in f.iter() {
+                        let a = arg.to_eval_tree_impl(fn_map, params, args, funcs)?;
+                        res = Some(match res {
+                            Some(r) => {
+                                Expression::BuiltinBinaryFun(BuiltinSymbol(name), Box::new((r, a)))
+                            }
+                            None => a,
+                        });
+                    }
+
+                    return res
+                        .ok_or_else(|| format!("Function {} called without arguments", name));
+                }
+
                 let Some(fun) = fn_map.get(*self) else {
                     return Err(format!("Undefined function {}", self));
                 };
@@ -4847,7 +5521,22 @@ impl<'a> AtomView<'a> {
             },
             AtomView::Fun(f) => {
                 let name = f.get_symbol();
-                if [Atom::EXP, Atom::LOG, Atom::SIN, Atom::COS, Atom::SQRT].contains(&name) {
+                if [
+                    Atom::EXP,
+                    Atom::LOG,
+                    Atom::SIN,
+                    Atom::COS,
+                    Atom::SQRT,
+                    Atom::TAN,
+                    Atom::SINH,
+                    Atom::COSH,
+                    Atom::TANH,
+                    Atom::ASIN,
+                    Atom::ACOS,
+                    Atom::ATAN,
+                ]
+                .contains(&name)
+                {
                     assert!(f.get_nargs() == 1);
                     let arg = f.iter().next().unwrap();
                     let arg_eval = arg.evaluate_impl(coeff_map, const_map, function_map, cache)?;
@@ -4858,10 +5547,84 @@ impl<'a> AtomView<'a> {
                         Atom::SIN => arg_eval.sin(),
                         Atom::COS => arg_eval.cos(),
                         Atom::SQRT => arg_eval.sqrt(),
+                        Atom::TAN => arg_eval.tan(),
+                        Atom::SINH => arg_eval.sinh(),
+                        Atom::COSH => arg_eval.cosh(),
+                        Atom::TANH => arg_eval.tanh(),
+                        Atom::ASIN => arg_eval.asin(),
+                        Atom::ACOS => arg_eval.acos(),
+                        Atom::ATAN => arg_eval.atan2(&arg_eval.one()),
                         _ => unreachable!(),
                     });
                 }
 
+                if name == Atom::ATAN2 && f.get_nargs() == 2 {
+                    let mut it = f.iter();
+                    let y = it.next().unwrap();
+                    let x = it.next().unwrap();
+                    let y_eval = y.evaluate_impl(coeff_map, const_map, function_map, cache)?;
+                    let x_eval = x.evaluate_impl(coeff_map, const_map, function_map, cache)?;
+                    return Ok(y_eval.atan2(&x_eval));
+                }
+
+                if (name == Atom::MAX || name == Atom::MIN) && f.get_nargs() > 0 {
+                    let mut args = Vec::with_capacity(f.get_nargs());
+                    for arg in f {
+                        args.push(arg.evaluate_impl(coeff_map, const_map, function_map, cache)?);
+                    }
+
+                    return Ok(args
+                        .into_iter()
+                        .reduce(|a, b| {
+                            if name == Atom::MAX {
+                                a.max(&b)
+                            } else {
+                                a.min(&b)
+                            }
+                        })
+                        .unwrap());
+                }
+
+                // the derivative of max and min is the derivative of the active argument
+                if name == Atom::DERIVATIVE {
+                    if let Some(AtomView::Fun(g)) = f.iter().last() {
+                        if (g.get_symbol() == Atom::MAX || g.get_symbol() == Atom::MIN)
+                            && f.get_nargs() == g.get_nargs() + 1
+                        {
+                            let mut orders = Vec::with_capacity(g.get_nargs());
+                            for o in f.iter().take(g.get_nargs()) {
+                                if let AtomView::Num(n) = o {
+                                    if let CoefficientView::Natural(n, 1) = n.get_coeff_view() {
+                                        orders.push(n);
+                                        continue;
+                                    }
+                                }
+
+                                return Err("Derivative orders must be integers".to_string());
+                            }
+
+                            let mut args = Vec::with_capacity(g.get_nargs());
+                            for arg in g {
+                                args.push(arg.evaluate_impl(
+                                    coeff_map,
+                                    const_map,
+                                    function_map,
+                                    cache,
+                                )?);
+                            }
+
+                            // the function is linear in the active argument, so
+                            // all higher-order derivatives vanish
+                            let index = extremum_index(&args, g.get_symbol() == Atom::MAX);
+                            return Ok(if orders.iter().sum::<i64>() == 1 && orders[index] == 1 {
+                                args[index].one()
+                            } else {
+                                args[index].zero()
+                            });
+                        }
+                    }
+                }
+
                 if let Some(eval) = cache.get(self) {
                     return Ok(eval.clone());
                 }
@@ -5075,7 +5838,10 @@ mod test {
 
     use crate::{
         atom::{Atom, AtomCore},
-        domains::{float::Float, rational::Rational},
+        domains::{
+            float::{Complex, Float},
+            rational::Rational,
+        },
         evaluate::{EvaluationFn, FunctionMap, OptimizationSettings},
         id::ConditionResult,
         parse, symbol,
@@ -5119,6 +5885,25 @@ mod test {
         assert_eq!(r, 2905.761021719902);
     }
 
+    #[test]
+    fn max_subgradient() {
+        let x = symbol!("v1");
+        let v = Atom::new_var(x);
+        let e = parse!("max(v1, v1^2)").unwrap();
+        let d = e.derivative(x);
+
+        let fn_map: HashMap<_, EvaluationFn<_, f64>> = HashMap::default();
+        for (p, value, der) in [(0.5, 0.5, 1.), (2., 4., 4.)] {
+            let mut const_map = HashMap::default();
+            const_map.insert(v.as_view(), p);
+
+            let r = e.evaluate(|x| x.into(), &const_map, &fn_map).unwrap();
+            assert_eq!(r, value);
+            let r = d.evaluate(|x| x.into(), &const_map, &fn_map).unwrap();
+            assert_eq!(r, der);
+        }
+    }
+
     #[test]
     fn arb_prec() {
         let x = symbol!("v1");
@@ -5194,6 +5979,250 @@ mod test {
         assert!((r - 1622709.2254269677).abs() / 1622709.2254269677 < 1e-10);
     }
 
+    #[test]
+    fn evaluate_batch() {
+        let e1 = parse!("v1^2 + cos(v1)*v2").unwrap();
+        let e2 = parse!("v1 - v2").unwrap();
+        let params = vec![parse!("v1").unwrap(), parse!("v2").unwrap()];
+
+        let evaluator = Atom::evaluator_multiple(
+            &[e1, e2],
+            &FunctionMap::new(),
+            &params,
+            OptimizationSettings::default(),
+        )
+        .unwrap();
+        let mut e_f64 = evaluator.map_coeff(&|x| x.into());
+
+        let points: Vec<Vec<f64>> = (0..10)
+            .map(|i| vec![i as f64 * 0.3, 2. - i as f64])
+            .collect();
+        let r = e_f64.evaluate_batch(&points);
+
+        let mut single = vec![0.; 2];
+        for (p, rr) in points.iter().zip(r.chunks(2)) {
+            e_f64.evaluate(p, &mut single);
+            assert_eq!(rr, single);
+        }
+    }
+
+    #[test]
+    fn evaluate_batch_simd() {
+        let e = parse!("v1^2*v2 + 3*v1 - v2/(1+v1^2)").unwrap();
+        let params = vec![parse!("v1").unwrap(), parse!("v2").unwrap()];
+
+        let evaluator = e
+            .evaluator(
+                &FunctionMap::new(),
+                &params,
+                OptimizationSettings::default(),
+            )
+            .unwrap();
+        let mut e_f64 = evaluator.map_coeff(&|x| x.into());
+        let mut e_simd = e_f64.to_simd();
+
+        for len in [0, 1, 3, 4, 5, 8, 11] {
+            let points: Vec<Vec<f64>> = (0..len)
+                .map(|i| vec![i as f64 * 0.7 - 1., 0.5 + i as f64])
+                .collect();
+
+            let r = e_f64.evaluate_batch(&points);
+            let r_simd = e_simd.evaluate_batch_simd(&points);
+
+            assert_eq!(r.len(), r_simd.len());
+            for (a, b) in r.iter().zip(&r_simd) {
+                assert!((a - b).abs() <= 1e-10 * a.abs().max(1.));
+            }
+        }
+    }
+
+    #[test]
+    fn gradient() {
+        let e = parse!("v1^2*v2 + sin(v1)").unwrap();
+        let params = vec![parse!("v1").unwrap(), parse!("v2").unwrap()];
+
+        let ders = [e.derivative(symbol!("v1")), e.derivative(symbol!("v2"))];
+
+        let evaluator = e
+            .evaluator(
+                &FunctionMap::new(),
+                &params,
+                OptimizationSettings::default(),
+            )
+            .unwrap();
+        let mut grad_eval = evaluator.map_coeff(&|x| x.into()).with_gradient(&[0, 1]);
+
+        let mut der_eval = Atom::evaluator_multiple(
+            &ders,
+            &FunctionMap::new(),
+            &params,
+            OptimizationSettings::default(),
+        )
+        .unwrap()
+        .map_coeff(&|x| x.into());
+
+        let mut value = [0.];
+        let mut grad = [0.; 2];
+        let mut ders = [0.; 2];
+        for p in [[0.5, 2.], [1.3, -0.7], [-2., 3.]] {
+            grad_eval.evaluate(&p, &mut value, &mut grad);
+            der_eval.evaluate(&p, &mut ders);
+
+            let (x, y): (f64, f64) = (p[0], p[1]);
+            let r = x * x * y + x.sin();
+            assert!((value[0] - r).abs() < 1e-10 * r.abs().max(1.));
+            for (a, b) in grad.iter().zip(&ders) {
+                assert!((a - b).abs() < 1e-10 * b.abs().max(1.));
+            }
+        }
+    }
+
+    #[test]
+    fn atan2() {
+        let e = parse!("atan2(v1, v2)").unwrap();
+        let params = vec![parse!("v1").unwrap(), parse!("v2").unwrap()];
+
+        let evaluator = e
+            .evaluator(
+                &FunctionMap::new(),
+                &params,
+                OptimizationSettings::default(),
+            )
+            .unwrap();
+
+        let code = evaluator.export_cpp_str("atan2", true);
+        assert!(code.contains("T symbolica_atan2(T y, T x)"));
+        assert!(code.contains("symbolica_atan2(Z0, Z1)"));
+
+        let mut eval = evaluator.map_coeff(&|x| x.into()).with_gradient(&[0, 1]);
+        let mut value = [0.];
+        let mut grad = [0.; 2];
+        for (y, x) in [(0.5, 2.), (1.3, -0.7), (-2., -3.)] {
+            eval.evaluate(&[y, x], &mut value, &mut grad);
+
+            let n: f64 = x * x + y * y;
+            assert!((value[0] - f64::atan2(y, x)).abs() < 1e-12);
+            assert!((grad[0] - x / n).abs() < 1e-12);
+            assert!((grad[1] + y / n).abs() < 1e-12);
+        }
+    }
+
+    #[test]
+    fn max_min() {
+        let e = parse!("max(v1, v2, 1) + 2*min(v1, v2^2)").unwrap();
+        let params = vec![parse!("v1").unwrap(), parse!("v2").unwrap()];
+
+        let evaluator = e
+            .evaluator(
+                &FunctionMap::new(),
+                &params,
+                OptimizationSettings::default(),
+            )
+            .unwrap();
+
+        let code = evaluator.export_cpp_str("max_min", true);
+        assert!(code.contains("T symbolica_max(T a, T b)"));
+        assert!(code.contains("T symbolica_min(T a, T b)"));
+
+        // the SIMD lanes disagree on the active arguments
+        let points = vec![vec![0.5, 2.], vec![3., -1.], vec![-2., 0.5], vec![1., 1.]];
+        let mut e_f64 = evaluator.clone().map_coeff(&|x| x.into());
+        let r = e_f64.evaluate_batch(&points);
+        assert_eq!(r, vec![3., 5., -3., 3.]);
+        assert_eq!(e_f64.to_simd().evaluate_batch_simd(&points), r);
+
+        let mut eval = evaluator.map_coeff(&|x| x.into()).with_gradient(&[0, 1]);
+        let mut value = [0.];
+        let mut grad = [0.; 2];
+        for (p, r, d) in [
+            ([0.5, 2.], 3., [2., 1.]),
+            ([3., -1.], 5., [1., -4.]),
+            ([-2., 0.5], -3., [2., 0.]),
+        ] {
+            eval.evaluate(&p, &mut value, &mut grad);
+            assert_eq!(value, [r]);
+            assert_eq!(grad, d);
+        }
+
+        // complex numbers are compared by their real parts, like in the exported code
+        let e = parse!("max(v1, v2) - min(v1, v2)").unwrap();
+        let mut eval = e
+            .evaluator(
+                &FunctionMap::new(),
+                &params,
+                OptimizationSettings::default(),
+            )
+            .unwrap()
+            .map_coeff(&|x| Complex::new(x.into(), 0.));
+        let r = eval.evaluate_single(&[Complex::new(1., 5.), Complex::new(2., 0.)]);
+        assert_eq!(r, Complex::new(1., -5.));
+    }
+
+    #[test]
+    fn tan_hyperbolic() {
+        let e = parse!("tan(v1) + 2*sinh(v1) + 3*cosh(v1) + 5*tanh(v1)").unwrap();
+        let params = vec![parse!("v1").unwrap()];
+
+        let evaluator = e
+            .evaluator(
+                &FunctionMap::new(),
+                &params,
+                OptimizationSettings::default(),
+            )
+            .unwrap();
+
+        let code = evaluator.export_cpp_str("tan_hyperbolic", true);
+        for f in ["tan", "sinh", "cosh", "tanh"] {
+            assert!(code.contains(&format!("= {}(Z0)", f)));
+        }
+
+        let mut eval = evaluator.map_coeff(&|x| x.into()).with_gradient(&[0]);
+        let mut value = [0.];
+        let mut grad = [0.];
+        for x in [0.3f64, -1.2, 2.] {
+            eval.evaluate(&[x], &mut value, &mut grad);
+
+            let r = x.tan() + 2. * x.sinh() + 3. * x.cosh() + 5. * x.tanh();
+            let d = 1. / (x.cos() * x.cos())
+                + 2. * x.cosh()
+                + 3. * x.sinh()
+                + 5. / (x.cosh() * x.cosh());
+            assert!((value[0] - r).abs() < 1e-10 * r.abs().max(1.));
+            assert!((grad[0] - d).abs() < 1e-10 * d.abs().max(1.));
+        }
+    }
+
+    #[test]
+    fn inverse_trig() {
+        let e = parse!("asin(v1) + 2*acos(v1) + 3*atan(v1)").unwrap();
+        let params = vec![parse!("v1").unwrap()];
+
+        let evaluator = e
+            .evaluator(
+                &FunctionMap::new(),
+                &params,
+                OptimizationSettings::default(),
+            )
+            .unwrap();
+
+        let code = evaluator.export_cpp_str("inverse_trig", true);
+        for f in ["asin", "acos", "atan"] {
+            assert!(code.contains(&format!("= {}(Z0)", f)));
+        }
+
+        let mut eval = evaluator.map_coeff(&|x| x.into()).with_gradient(&[0]);
+        let mut value = [0.];
+        let mut grad = [0.];
+        for x in [0.3f64, -0.8, 0.5] {
+            eval.evaluate(&[x], &mut value, &mut grad);
+
+            let r = x.asin() + 2. * x.acos() + 3. * x.atan();
+            let d = -1. / (1. - x * x).sqrt() + 3. / (1. + x * x);
+            assert!((value[0] - r).abs() < 1e-10 * r.abs().max(1.));
+            assert!((grad[0] - d).abs() < 1e-10 * d.abs().max(1.));
+        }
+    }
+
     #[test]
     fn zero_test() {
         let e = parse!("(sin(v1)^2-sin(v1))(sin(v1)^2+sin(v1))^2 - (1/4 sin(2v1)^2-1/2 sin(2v1)cos(v1)-2 cos(v1)^2+1/2 sin(2v1)cos(v1)^3+3 cos(v1)^4-cos(v1)^6)").unwrap();
@@ -3,7 +3,7 @@ use std::{ops::DerefMut, sync::Arc};
 use smallvec::SmallVec;
 
 use crate::{
-    atom::{Atom, AtomView},
+    atom::{Atom, AtomView, FunctionBuilder, Symbol},
     coefficient::CoefficientView,
     combinatorics::CombinationWithReplacementIterator,
     domains::{integer::Integer, rational::Q},
@@ -53,6 +53,66 @@ impl<'a> AtomView<'a> {
         changed
     }
 
+    /// Estimate the number of terms of the expanded expression, without expanding.
+    /// The estimate ignores cancellations and saturates at `u128::MAX`.
+    pub(crate) fn estimate_expanded_terms(&self) -> u128 {
+        match self {
+            AtomView::Num(_) | AtomView::Var(_) | AtomView::Fun(_) => 1,
+            AtomView::Add(a) => a.iter().fold(0u128, |acc, t| {
+                acc.saturating_add(t.estimate_expanded_terms())
+            }),
+            AtomView::Mul(m) => m.iter().fold(1u128, |acc, f| {
+                acc.saturating_mul(f.estimate_expanded_terms())
+            }),
+            AtomView::Pow(p) => {
+                let (base, exp) = p.get_base_exp();
+
+                let AtomView::Num(n) = exp else {
+                    return 1;
+                };
+                let CoefficientView::Natural(n, 1) = n.get_coeff_view() else {
+                    return 1;
+                };
+
+                let k = base.estimate_expanded_terms();
+                if n <= 0 || k == 1 {
+                    return 1;
+                }
+
+                // the number of monomials of degree n in k terms is binomial(n + k - 1, k - 1)
+                let n = n as u128;
+                let m = n.min(k - 1);
+                let mut r = 1u128;
+                for i in 1..=m {
+                    let Some(x) = (n.max(k - 1) - m)
+                        .checked_add(i)
+                        .and_then(|f| r.checked_mul(f))
+                    else {
+                        return u128::MAX;
+                    };
+                    r = x / i;
+                }
+                r
+            }
+        }
+    }
+
+    /// Create an iterator over the terms of the expanded expression, without
+    /// constructing the full expansion.
+    pub(crate) fn expand_iter(&self) -> ExpandIterator {
+        let terms = if let AtomView::Add(a) = self {
+            a.iter().map(|t| t.to_owned()).collect()
+        } else {
+            vec![self.to_owned()]
+        };
+
+        ExpandIterator {
+            terms: terms.into_iter(),
+            factors: vec![],
+            indices: vec![],
+        }
+    }
+
     /// Check if the expression is expanded, optionally in only the variable or function `var`.
     pub(crate) fn is_expanded(&self, var: Option<AtomView>) -> bool {
         match self {
@@ -393,6 +453,252 @@ impl<'a> AtomView<'a> {
         }
     }
 
+    /// Rewrite exponentials with an imaginary exponent using Euler's formula:
+    /// `exp(𝑖*x)` -> `cos(x)+𝑖*sin(x)`.
+    pub(crate) fn expand_complex_exp(&self) -> Atom {
+        let mut out = Atom::new();
+        Workspace::get_local().with(|ws| {
+            self.map_functions_impl(
+                ws,
+                &|a: &AtomView, ws: &Workspace, out: &mut Atom| a.expand_complex_exp_fn(ws, out),
+                &mut out,
+            );
+        });
+        out
+    }
+
+    /// Rewrite `exp(𝑖*x)` to `cos(x)+𝑖*sin(x)`, returning `true` iff the function was rewritten.
+    fn expand_complex_exp_fn(&self, ws: &Workspace, out: &mut Atom) -> bool {
+        let AtomView::Fun(f) = self else {
+            return false;
+        };
+
+        if f.get_symbol() != Atom::EXP || f.get_nargs() != 1 {
+            return false;
+        }
+
+        // find the imaginary unit in the exponent and remove it
+        let mut x = ws.new_atom();
+        match f.iter().next().unwrap() {
+            AtomView::Var(v) if v.get_symbol() == Atom::I => {
+                x.to_num(1.into());
+            }
+            AtomView::Mul(m)
+                if m.iter()
+                    .any(|a| matches!(a, AtomView::Var(v) if v.get_symbol() == Atom::I)) =>
+            {
+                let mut nm = ws.new_atom();
+                let nmm = nm.to_mul();
+                for a in m {
+                    if !matches!(a, AtomView::Var(v) if v.get_symbol() == Atom::I) {
+                        nmm.extend(a);
+                    }
+                }
+                nm.as_view().normalize(ws, &mut x);
+            }
+            _ => return false,
+        }
+
+        *out = x.cos() + Atom::new_var(Atom::I) * x.sin();
+        true
+    }
+
+    /// Apply the angle-addition identities to sines and cosines of sums:
+    /// `sin(x+y)` -> `sin(x)*cos(y)+cos(x)*sin(y)` and
+    /// `cos(x+y)` -> `cos(x)*cos(y)-sin(x)*sin(y)`.
+    pub(crate) fn expand_trig(&self) -> Atom {
+        let mut out = Atom::new();
+        Workspace::get_local().with(|ws| {
+            self.map_functions_impl(
+                ws,
+                &|a: &AtomView, ws: &Workspace, out: &mut Atom| a.expand_trig_fn(ws, out),
+                &mut out,
+            );
+        });
+        out
+    }
+
+    /// Apply the angle-addition identity to `sin` or `cos` of a sum by splitting off the first term,
+    /// returning `true` iff the function was rewritten.
+    fn expand_trig_fn(&self, ws: &Workspace, out: &mut Atom) -> bool {
+        let AtomView::Fun(f) = self else {
+            return false;
+        };
+
+        let s = f.get_symbol();
+        if (s != Atom::SIN && s != Atom::COS) || f.get_nargs() != 1 {
+            return false;
+        }
+
+        let AtomView::Add(a) = f.iter().next().unwrap() else {
+            return false;
+        };
+
+        let mut it = a.iter();
+        let x = it.next().unwrap().to_owned();
+
+        let mut rest = ws.new_atom();
+        let ra = rest.to_add();
+        for t in it {
+            ra.extend(t);
+        }
+        let mut y = ws.new_atom();
+        rest.as_view().normalize(ws, &mut y);
+
+        let r = if s == Atom::SIN {
+            x.sin() * y.cos() + x.cos() * y.sin()
+        } else {
+            x.cos() * y.cos() - x.sin() * y.sin()
+        };
+
+        // the remainder may still be a sum
+        r.as_view().map_functions_impl(
+            ws,
+            &|a: &AtomView, ws: &Workspace, out: &mut Atom| a.expand_trig_fn(ws, out),
+            out,
+        );
+        true
+    }
+
+    /// Simplify squares of sines and cosines using `sin(x)^2+cos(x)^2 = 1`, for example
+    /// `sin(x)^2+cos(x)^2+y` -> `1+y` and `1-cos(x)^2` -> `sin(x)^2`.
+    ///
+    /// Only exact squares of `sin` and `cos` are considered. The squares of sines are
+    /// rewritten in terms of cosines and vice versa, and the expanded form with the fewest
+    /// terms is returned. If neither has fewer terms than the input, the input is returned
+    /// unchanged.
+    pub(crate) fn simplify_trig(&self) -> Atom {
+        let mut best = self.to_owned();
+        for (f, g) in [(Atom::SIN, Atom::COS), (Atom::COS, Atom::SIN)] {
+            let r = self.rewrite_trig_square(f, g).expand();
+            if r.nterms() < best.nterms() {
+                best = r;
+            }
+        }
+
+        best
+    }
+
+    /// Rewrite every `f(x)^2` to `1-g(x)^2`.
+    fn rewrite_trig_square(&self, f: Symbol, g: Symbol) -> Atom {
+        self.replace_map(|a, _, out| {
+            let AtomView::Pow(p) = a else {
+                return false;
+            };
+
+            let (b, e) = p.get_base_exp();
+            let (AtomView::Fun(ff), AtomView::Num(n)) = (b, e) else {
+                return false;
+            };
+
+            if ff.get_symbol() != f
+                || ff.get_nargs() != 1
+                || n.get_coeff_view() != CoefficientView::Natural(2, 1)
+            {
+                return false;
+            }
+
+            let x = ff.iter().next().unwrap();
+            *out = Atom::new_num(1) - FunctionBuilder::new(g).add_arg(x).finish().npow(2);
+            true
+        })
+    }
+
+    /// Rebuild the expression bottom-up, calling `f` on every function after its arguments
+    /// have been processed. The function `f` writes its result in `out` and returns `true` iff
+    /// it rewrote the function. Returns `true` iff the expression changed.
+    fn map_functions_impl(
+        &self,
+        ws: &Workspace,
+        f: &impl Fn(&AtomView, &Workspace, &mut Atom) -> bool,
+        out: &mut Atom,
+    ) -> bool {
+        match self {
+            AtomView::Num(_) | AtomView::Var(_) => {
+                out.set_from_view(self);
+                false
+            }
+            AtomView::Fun(fv) => {
+                let mut changed = false;
+                let mut nf = ws.new_atom();
+                let nff = nf.to_fun(fv.get_symbol());
+                let mut na = ws.new_atom();
+                for arg in fv {
+                    changed |= arg.map_functions_impl(ws, f, &mut na);
+                    nff.add_arg(na.as_view());
+                }
+
+                if changed {
+                    nf.as_view().normalize(ws, out);
+                } else {
+                    out.set_from_view(self);
+                }
+
+                let mut r = ws.new_atom();
+                if f(&out.as_view(), ws, &mut r) {
+                    out.set_from_view(&r.as_view());
+                    changed = true;
+                }
+
+                changed
+            }
+            AtomView::Pow(p) => {
+                let (b, e) = p.get_base_exp();
+
+                let mut nb = ws.new_atom();
+                let mut changed = b.map_functions_impl(ws, f, &mut nb);
+                let mut ne = ws.new_atom();
+                changed |= e.map_functions_impl(ws, f, &mut ne);
+
+                if changed {
+                    let mut np = ws.new_atom();
+                    np.to_pow(nb.as_view(), ne.as_view());
+                    np.as_view().normalize(ws, out);
+                } else {
+                    out.set_from_view(self);
+                }
+
+                changed
+            }
+            AtomView::Mul(m) => {
+                let mut changed = false;
+                let mut nm = ws.new_atom();
+                let nmm = nm.to_mul();
+                let mut na = ws.new_atom();
+                for arg in m {
+                    changed |= arg.map_functions_impl(ws, f, &mut na);
+                    nmm.extend(na.as_view());
+                }
+
+                if changed {
+                    nm.as_view().normalize(ws, out);
+                } else {
+                    out.set_from_view(self);
+                }
+
+                changed
+            }
+            AtomView::Add(a) => {
+                let mut changed = false;
+                let mut nadd = ws.new_atom();
+                let nadda = nadd.to_add();
+                let mut na = ws.new_atom();
+                for arg in a {
+                    changed |= arg.map_functions_impl(ws, f, &mut na);
+                    nadda.extend(na.as_view());
+                }
+
+                if changed {
+                    nadd.as_view().normalize(ws, out);
+                } else {
+                    out.set_from_view(self);
+                }
+
+                changed
+            }
+        }
+    }
+
     /// Distribute numbers in the expression, for example:
     /// `2*(x+y)` -> `2*x+2*y`.
     pub(crate) fn expand_num(&self) -> Atom {
@@ -510,11 +816,133 @@ impl<'a> AtomView<'a> {
     }
 }
 
+/// An iterator over the terms of an expanded expression, created by
+/// [AtomCore::expand_iter](crate::atom::AtomCore::expand_iter).
+///
+/// The factors of every product are expanded separately and the terms of the
+/// product are generated one at a time. Like terms are not merged, so the same
+/// term may be yielded more than once.
+pub struct ExpandIterator {
+    terms: std::vec::IntoIter<Atom>,
+    factors: Vec<Vec<Atom>>,
+    indices: Vec<usize>,
+}
+
+impl ExpandIterator {
+    /// Expand the factors of the next term of the expression.
+    fn next_term(&mut self) -> bool {
+        let Some(t) = self.terms.next() else {
+            return false;
+        };
+
+        let expand_terms = |f: AtomView| -> Vec<Atom> {
+            let e = f.expand();
+            if let AtomView::Add(a) = e.as_view() {
+                return a.iter().map(|t| t.to_owned()).collect();
+            }
+            vec![e]
+        };
+
+        self.factors = if let AtomView::Mul(m) = t.as_view() {
+            m.iter().map(expand_terms).collect()
+        } else {
+            vec![expand_terms(t.as_view())]
+        };
+        self.indices = vec![0; self.factors.len()];
+        true
+    }
+
+    /// Advance the indices to the next combination of terms of the factors.
+    fn next_indices(&mut self) -> bool {
+        for (i, f) in self.indices.iter_mut().zip(&self.factors).rev() {
+            *i += 1;
+            if *i < f.len() {
+                return true;
+            }
+            *i = 0;
+        }
+
+        false
+    }
+}
+
+impl Iterator for ExpandIterator {
+    type Item = Atom;
+
+    fn next(&mut self) -> Option<Atom> {
+        loop {
+            if self.factors.is_empty() && !self.next_term() {
+                return None;
+            }
+
+            let mut out = Atom::new();
+            Workspace::get_local().with(|ws| {
+                let mut m = ws.new_atom();
+                let mm = m.to_mul();
+                for (f, i) in self.factors.iter().zip(&self.indices) {
+                    mm.extend(f[*i].as_view());
+                }
+                m.as_view().normalize(ws, &mut out);
+            });
+
+            if !self.next_indices() {
+                self.factors.clear();
+            }
+
+            if !out.is_zero() {
+                return Some(out);
+            }
+        }
+    }
+}
+
 #[cfg(test)]
 mod test {
-    use crate::atom::AtomCore;
+    use crate::atom::{Atom, AtomCore};
     use crate::{parse, symbol};
 
+    #[test]
+    fn expand_square() {
+        let input = parse!("(v1+v2)^2").unwrap();
+        let res = parse!("v1^2+2*v1*v2+v2^2").unwrap();
+        assert_eq!(input.expand(), res);
+        assert_eq!(input.as_view().expand(), res);
+    }
+
+    #[test]
+    fn expand_unchanged() {
+        let input = parse!("(v1+v2)^2").unwrap();
+        let mut out = Atom::new();
+        assert!(input.expand_into::<Atom>(None, &mut out));
+
+        let mut out2 = Atom::new();
+        assert!(!out.expand_into::<Atom>(None, &mut out2));
+        assert_eq!(out, out2);
+    }
+
+    #[test]
+    fn estimate_expanded_terms() {
+        let expr = parse!("(a+b)^3*(c+d)").unwrap();
+        let estimate = expr.estimate_expanded_terms();
+        assert_eq!(estimate, 8);
+        assert!(estimate >= expr.expand().nterms() as u128);
+
+        assert_eq!(parse!("(a+b+c)^2").unwrap().estimate_expanded_terms(), 6);
+        assert_eq!(parse!("(a+b)^-2").unwrap().estimate_expanded_terms(), 1);
+        assert_eq!(
+            parse!("(a+b)^1000*(c+d+e)^1000")
+                .unwrap()
+                .estimate_expanded_terms(),
+            1001 * 501501
+        );
+        assert_eq!(
+            parse!("(a+b+c+d+e+f+g+h)^10000000")
+                .unwrap()
+                .estimate_expanded_terms(),
+            u128::MAX
+        );
+    }
+
     #[test]
     fn expand_num() {
         let exp = parse!("5+2*v3*(v1-v2)*(v4+v5)").unwrap().expand_num();
@@ -536,6 +964,62 @@ mod test {
         assert_eq!(exp, res);
     }
 
+    #[test]
+    fn expand_iter() {
+        let e = parse!("(v1+v2)*(v3+v4)").unwrap();
+        let terms: Vec<_> = e.expand_iter().collect();
+        assert_eq!(terms.len(), e.expand().nterms());
+        assert_eq!(terms.len(), 4);
+
+        let sum = terms.into_iter().fold(Atom::new(), |acc, t| acc + t);
+        assert_eq!(sum, e.expand());
+    }
+
+    #[test]
+    fn expand_complex_exp() {
+        let i = Atom::new_var(Atom::I);
+        let x = parse!("v1").unwrap();
+
+        let r = (&i * &x).exp().expand_complex_exp();
+        assert_eq!(r, x.cos() + &i * x.sin());
+
+        let r = (&i * 2).exp().expand_complex_exp();
+        assert_eq!(r, Atom::new_num(2).cos() + &i * Atom::new_num(2).sin());
+
+        let e = parse!("exp(v1)").unwrap();
+        assert_eq!(e.expand_complex_exp(), e);
+    }
+
+    #[test]
+    fn expand_trig() {
+        let r = parse!("sin(v1+v2)").unwrap().expand_trig();
+        assert_eq!(r, parse!("sin(v1)*cos(v2)+cos(v1)*sin(v2)").unwrap());
+
+        let r = parse!("cos(v1+v2)").unwrap().expand_trig();
+        assert_eq!(r, parse!("cos(v1)*cos(v2)-sin(v1)*sin(v2)").unwrap());
+
+        let r = parse!("sin(v1+v2+v3)").unwrap().expand_trig().expand();
+        let res = parse!(
+            "sin(v1)*cos(v2)*cos(v3)+cos(v1)*sin(v2)*cos(v3)+cos(v1)*cos(v2)*sin(v3)-sin(v1)*sin(v2)*sin(v3)"
+        )
+        .unwrap();
+        assert_eq!(r, res);
+    }
+
+    #[test]
+    fn simplify_trig() {
+        let r = parse!("sin(v1)^2+cos(v1)^2+v2").unwrap().simplify_trig();
+        assert_eq!(r, parse!("1+v2").unwrap());
+
+        let r = parse!("1-cos(v1)^2").unwrap().simplify_trig();
+        assert_eq!(r, parse!("sin(v1)^2").unwrap());
+
+        let r = parse!("v2*sin(v1+1)^2+v2*cos(v1+1)^2")
+            .unwrap()
+            .simplify_trig();
+        assert_eq!(r, parse!("v2").unwrap());
+    }
+
     #[test]
     fn mul_pow() {
         let exp = parse!("(v1*v2*2)^3*2").unwrap().expand();
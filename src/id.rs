@@ -24,9 +24,10 @@ use dyn_clone::DynClone;
 use crate::{
     atom::{
         representation::{InlineVar, ListSlice},
-        Atom, AtomCore, AtomType, AtomView, Num, SliceType, Symbol,
+        Atom, AtomCore, AtomType, AtomView, NamespacedSymbol, Num, SliceType, Symbol,
     },
-    state::{RecycledAtom, Workspace},
+    parser::TEMPLATE_NAMESPACE,
+    state::{RecycledAtom, State, StateMap, Workspace},
     transformer::{Transformer, TransformerError},
     utils::BorrowedOrOwned,
 };
@@ -270,6 +271,19 @@ impl<'a, 'b> ReplaceBuilder<'a, 'b> {
         self
     }
 
+    /// Specifies the `[min,max]` number of arguments a function in the pattern
+    /// must have to match.
+    pub fn function_arity(mut self, function_arity: Vec<(Symbol, (usize, Option<usize>))>) -> Self {
+        self.settings.function_arity = function_arity;
+        self
+    }
+
+    /// Only allow the pattern to match the entire expression and not its subexpressions.
+    pub fn anchored(mut self, anchored: bool) -> Self {
+        self.settings.anchored = anchored;
+        self
+    }
+
     /// Add a condition to the replacement.
     pub fn when<R: Into<BorrowedOrOwned<'b, Condition<PatternRestriction>>>>(
         mut self,
@@ -344,6 +358,44 @@ impl<'a, 'b> ReplaceBuilder<'a, 'b> {
         replaced
     }
 
+    /// Execute the replacement by specifying the right-hand side and also return the
+    /// positions of all replaced subexpressions. A position is the path of child indices from
+    /// the root, where the base and exponent of a power have index 0 and 1 respectively.
+    ///
+    /// The replacement is performed once, even if [ReplaceBuilder::repeat] is set.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use symbolica::{atom::AtomCore, parse};
+    /// let expr = parse!("f(x)+g(f(x))").unwrap();
+    /// let (r, sites) = expr.replace(parse!("f(x)").unwrap()).with_positions(parse!("y").unwrap());
+    /// assert_eq!(r, parse!("y+g(y)").unwrap());
+    /// assert_eq!(sites.len(), 2);
+    /// ```
+    pub fn with_positions<'c, R: Into<BorrowedOrOwned<'c, Pattern>>>(
+        &self,
+        rhs: R,
+    ) -> (Atom, Vec<Vec<usize>>) {
+        let rhs = ReplaceWith::Pattern(rhs.into());
+        let mut out = Atom::new();
+        let mut sites = Some((vec![], vec![]));
+
+        Workspace::get_local().with(|ws| {
+            self.target.replace_with_sites_into(
+                self.pattern.borrow(),
+                &rhs,
+                ws,
+                self.conditions.as_ref().map(|x| x.borrow()),
+                Some(&self.settings),
+                &mut sites,
+                &mut out,
+            )
+        });
+
+        (out, sites.unwrap().1)
+    }
+
     /// Execute the replacement by specifying the right-hand side as a map on the matched wildcards.
     ///
     /// # Example
@@ -462,6 +514,20 @@ impl<'a> AtomView<'a> {
         Pattern::from_view(self, true)
     }
 
+    /// Fill the template placeholder `{name}` with `value`.
+    pub(crate) fn fill_template(&self, name: &str, value: AtomView) -> Atom {
+        let placeholder = State::get_or_insert_symbol(NamespacedSymbol::parse(&format!(
+            "{}::{}",
+            TEMPLATE_NAMESPACE, name
+        )));
+        self.replace(placeholder).with(value.to_owned())
+    }
+
+    /// Substitute the variable `x` by `value` and normalize the result.
+    pub(crate) fn eval_at(&self, x: Symbol, value: AtomView) -> Atom {
+        self.replace(x).with(value.to_owned())
+    }
+
     /// Get all symbols in the expression, optionally including function symbols.
     pub(crate) fn get_all_symbols(&self, include_function_symbols: bool) -> HashSet<Symbol> {
         let mut out = HashSet::default();
@@ -546,7 +612,7 @@ impl<'a> AtomView<'a> {
     }
 
     /// Returns true iff `self` contains `a` literally.
-    pub(crate) fn contains<T: AtomCore>(&self, a: T) -> bool {
+    pub fn contains<T: AtomCore>(&self, a: T) -> bool {
         let mut stack = Vec::with_capacity(20);
         stack.push(*self);
 
@@ -936,7 +1002,8 @@ impl<'a> AtomView<'a> {
     ) -> bool {
         Workspace::get_local().with(|ws| {
             let mut rhs_cache = HashMap::default();
-            let matched = self.replace_no_norm(replacements, ws, 0, 0, &mut rhs_cache, out);
+            let matched =
+                self.replace_no_norm(replacements, ws, 0, 0, &mut rhs_cache, &mut None, out);
 
             if matched {
                 let mut norm = ws.new_atom();
@@ -949,6 +1016,8 @@ impl<'a> AtomView<'a> {
     }
 
     /// Replace all occurrences of the patterns in the target, without normalizing the output.
+    /// If `sites` is set, it holds the path to the current atom and the paths of all
+    /// replaced atoms are recorded.
     fn replace_no_norm<T: BorrowReplacement>(
         &self,
         replacements: &[T],
@@ -956,6 +1025,7 @@ impl<'a> AtomView<'a> {
         tree_level: usize,
         fn_level: usize,
         rhs_cache: &mut HashMap<(usize, Vec<(Symbol, Match<'a>)>), Atom>,
+        sites: &mut Option<(Vec<usize>, Vec<Vec<usize>>)>,
         out: &mut Atom,
     ) -> bool {
         let mut beyond_max_level = true;
@@ -967,6 +1037,10 @@ impl<'a> AtomView<'a> {
             let conditions = r.conditions.unwrap_or(&def_c);
             let settings = r.settings.unwrap_or(&def_s);
 
+            if settings.anchored && tree_level > 0 {
+                continue;
+            }
+
             if let Some(max_level) = settings.level_range.1 {
                 if settings.level_is_tree_depth && tree_level > max_level
                     || !settings.level_is_tree_depth && fn_level > max_level
@@ -1034,6 +1108,10 @@ impl<'a> AtomView<'a> {
                         }
                     }
 
+                    if let Some((path, found)) = sites {
+                        found.push(path.clone());
+                    }
+
                     if used_flags.iter().all(|x| *x) {
                         // all used, return rhs
                         out.set_from_view(&rhs_subs.as_view());
@@ -1086,15 +1164,18 @@ impl<'a> AtomView<'a> {
                 let mut submatch = false;
 
                 let mut child_buf = workspace.new_atom();
-                for child in f {
+                for (i, child) in f.iter().enumerate() {
+                    enter_site(sites, i);
                     submatch |= child.replace_no_norm(
                         replacements,
                         workspace,
                         tree_level + 1,
                         fn_level + 1,
                         rhs_cache,
+                        sites,
                         &mut child_buf,
                     );
+                    leave_site(sites);
 
                     out.add_arg(child_buf.as_view());
                 }
@@ -1106,24 +1187,30 @@ impl<'a> AtomView<'a> {
                 let (base, exp) = p.get_base_exp();
 
                 let mut base_out = workspace.new_atom();
+                enter_site(sites, 0);
                 let mut submatch = base.replace_no_norm(
                     replacements,
                     workspace,
                     tree_level + 1,
                     fn_level,
                     rhs_cache,
+                    sites,
                     &mut base_out,
                 );
+                leave_site(sites);
 
                 let mut exp_out = workspace.new_atom();
+                enter_site(sites, 1);
                 submatch |= exp.replace_no_norm(
                     replacements,
                     workspace,
                     tree_level + 1,
                     fn_level,
                     rhs_cache,
+                    sites,
                     &mut exp_out,
                 );
+                leave_site(sites);
 
                 let out = out.to_pow(base_out.as_view(), exp_out.as_view());
                 out.set_normalized(!submatch && p.is_normalized());
@@ -1134,15 +1221,18 @@ impl<'a> AtomView<'a> {
 
                 let mut submatch = false;
                 let mut child_buf = workspace.new_atom();
-                for child in m {
+                for (i, child) in m.iter().enumerate() {
+                    enter_site(sites, i);
                     submatch |= child.replace_no_norm(
                         replacements,
                         workspace,
                         tree_level + 1,
                         fn_level,
                         rhs_cache,
+                        sites,
                         &mut child_buf,
                     );
+                    leave_site(sites);
 
                     mul.extend(child_buf.as_view());
                 }
@@ -1155,15 +1245,18 @@ impl<'a> AtomView<'a> {
                 let out = out.to_add();
                 let mut submatch = false;
                 let mut child_buf = workspace.new_atom();
-                for child in a {
+                for (i, child) in a.iter().enumerate() {
+                    enter_site(sites, i);
                     submatch |= child.replace_no_norm(
                         replacements,
                         workspace,
                         tree_level + 1,
                         fn_level,
                         rhs_cache,
+                        sites,
                         &mut child_buf,
                     );
+                    leave_site(sites);
 
                     out.extend(child_buf.as_view());
                 }
@@ -1189,6 +1282,23 @@ impl<'a> AtomView<'a> {
         conditions: Option<&Condition<PatternRestriction>>,
         settings: Option<&MatchSettings>,
         out: &mut Atom,
+    ) -> bool {
+        self.replace_with_sites_into(
+            pattern, rhs, workspace, conditions, settings, &mut None, out,
+        )
+    }
+
+    /// Replace all occurrences of the pattern in the target, returning `true` iff a match was found.
+    /// If `sites` is set, the paths of the replaced atoms are recorded in it.
+    fn replace_with_sites_into(
+        &self,
+        pattern: &Pattern,
+        rhs: &ReplaceWith,
+        workspace: &Workspace,
+        conditions: Option<&Condition<PatternRestriction>>,
+        settings: Option<&MatchSettings>,
+        sites: &mut Option<(Vec<usize>, Vec<Vec<usize>>)>,
+        out: &mut Atom,
     ) -> bool {
         let rep = BorrowedReplacement {
             pattern,
@@ -1204,6 +1314,7 @@ impl<'a> AtomView<'a> {
             0,
             0,
             &mut rhs_cache,
+            sites,
             out,
         );
 
@@ -1217,12 +1328,64 @@ impl<'a> AtomView<'a> {
     }
 }
 
+/// Descend into child `index` when recording replacement sites.
+fn enter_site(sites: &mut Option<(Vec<usize>, Vec<Vec<usize>>)>, index: usize) {
+    if let Some((path, _)) = sites {
+        path.push(index);
+    }
+}
+
+/// Return to the parent when recording replacement sites.
+fn leave_site(sites: &mut Option<(Vec<usize>, Vec<Vec<usize>>)>) {
+    if let Some((path, _)) = sites {
+        path.pop();
+    }
+}
+
 impl Pattern {
     /// Create a pattern from an expression.
     pub fn new(atom: Atom) -> Pattern {
         atom.to_pattern()
     }
 
+    /// Returns `true` iff the pattern matches `atom` or any of its subexpressions.
+    /// The search stops at the first match.
+    ///
+    /// # Example
+    /// ```
+    /// use symbolica::{atom::AtomCore, parse};
+    /// let p = parse!("f(x__)").unwrap().to_pattern();
+    /// assert!(p.matches(parse!("g(f(1))").unwrap().as_view()));
+    /// ```
+    pub fn matches(&self, atom: AtomView) -> bool {
+        PatternAtomTreeIterator::new(self, atom, None, None)
+            .next_detailed()
+            .is_some()
+    }
+
+    /// Rename the symbols in the pattern using the state map.
+    /// Transformers are kept as they are.
+    pub(crate) fn rename(&self, state_map: &StateMap) -> Pattern {
+        let map = |s: &Symbol| state_map.symbols.get(&s.get_id()).copied().unwrap_or(*s);
+
+        match self {
+            Pattern::Literal(a) => Pattern::Literal(a.as_view().rename(state_map)),
+            Pattern::Wildcard(s) => Pattern::Wildcard(map(s)),
+            Pattern::Fn(s, args) => {
+                Pattern::Fn(map(s), args.iter().map(|a| a.rename(state_map)).collect())
+            }
+            Pattern::Pow(p) => {
+                Pattern::Pow(Box::new([p[0].rename(state_map), p[1].rename(state_map)]))
+            }
+            Pattern::Mul(m) => Pattern::Mul(m.iter().map(|a| a.rename(state_map)).collect()),
+            Pattern::Add(a) => Pattern::Add(a.iter().map(|a| a.rename(state_map)).collect()),
+            Pattern::Transformer(t) => Pattern::Transformer(Box::new((
+                t.0.as_ref().map(|p| p.rename(state_map)),
+                t.1.clone(),
+            ))),
+        }
+    }
+
     /// Convert the pattern to an atom, if there are not transformers present.
     pub fn to_atom(&self) -> Result<Atom, &'static str> {
         Workspace::get_local().with(|ws| {
@@ -2771,6 +2934,11 @@ pub struct MatchSettings {
     /// The maximum size of the cache for the right-hand side of a replacement.
     /// This can be used to prevent expensive recomputations.
     pub rhs_cache_size: usize,
+    /// Specifies the `[min,max]` number of arguments a function in the pattern
+    /// must have to match, for example to restrict `f(x___)` to functions `f` with three arguments.
+    pub function_arity: Vec<(Symbol, (usize, Option<usize>))>,
+    /// Only allow the pattern to match the entire expression and not its subexpressions.
+    pub anchored: bool,
 }
 
 static DEFAULT_MATCH_SETTINGS: MatchSettings = MatchSettings::new();
@@ -2783,6 +2951,8 @@ impl MatchSettings {
             level_is_tree_depth: false,
             allow_new_wildcards_on_rhs: false,
             rhs_cache_size: 0,
+            function_arity: Vec::new(),
+            anchored: false,
         }
     }
 
@@ -2794,8 +2964,19 @@ impl MatchSettings {
             level_is_tree_depth: false,
             allow_new_wildcards_on_rhs: false,
             rhs_cache_size: 100,
+            function_arity: Vec::new(),
+            anchored: false,
         }
     }
+
+    /// Check if a function with symbol `name` in the pattern may match a function
+    /// with `nargs` arguments.
+    fn allows_arity(&self, name: Symbol, nargs: usize) -> bool {
+        self.function_arity
+            .iter()
+            .filter(|(s, _)| *s == name)
+            .all(|(_, (min, max))| nargs >= *min && max.map(|m| nargs <= m).unwrap_or(true))
+    }
 }
 
 impl Default for MatchSettings {
@@ -3193,7 +3374,8 @@ impl<'a, 'b> SubSliceIterator<'a, 'b> {
             target: target_list,
 
             initialized: shortcut_done,
-            complete: false,
+            // an anchored pattern may not match a part of an addition or multiplication
+            complete: match_stack.settings.anchored,
             ordered_gapless: false,
             cyclic: false,
             do_not_match_to_single_atom_in_list,
@@ -3603,7 +3785,8 @@ impl<'a, 'b> SubSliceIterator<'a, 'b> {
                                 f.get_symbol() == *name
                             };
 
-                            if name_match {
+                            if name_match && match_stack.settings.allows_arity(*name, f.get_nargs())
+                            {
                                 let mut it = SubSliceIterator::from_list(
                                     args,
                                     f.to_slice(),
@@ -3840,7 +4023,9 @@ impl<'a> Iterator for AtomTreeIterator<'a> {
                     level
                 };
 
-                self.stack.push((Some(0), new_level, atom));
+                if !self.settings.anchored {
+                    self.stack.push((Some(0), new_level, atom));
+                }
 
                 if level >= self.settings.level_range.0 {
                     return Some((location, atom));
@@ -4162,12 +4347,19 @@ impl<'a: 'b, 'b> Iterator for ReplaceIterator<'a, 'b> {
 mod test {
     use crate::{
         atom::{Atom, AtomCore},
-        id::{Condition, ConditionResult, Match, Replacement},
+        id::{Condition, ConditionResult, Match, MatchSettings, Replacement},
         parse,
         printer::PrintOptions,
         symbol,
     };
 
+    #[test]
+    fn matches() {
+        let p = parse!("f(x__)").unwrap().to_pattern();
+        assert!(p.matches(parse!("g(f(1))").unwrap().as_view()));
+        assert!(!p.matches(parse!("g(h(1))").unwrap().as_view()));
+    }
+
     #[test]
     fn replace_wildcards_with_map() {
         let a = parse!("f1(v1__, 5) + v1*v2_ + v3^v3_")
@@ -4255,6 +4447,59 @@ mod test {
         assert_eq!(r, res);
     }
 
+    #[test]
+    fn function_arity() {
+        let a = parse!("f(1,2,3) + f(1,2)").unwrap();
+
+        let r = a
+            .replace(parse!("f(x_,y_,z_)").unwrap())
+            .with(parse!("1").unwrap());
+        assert_eq!(r, parse!("1 + f(1,2)").unwrap());
+
+        let r = a
+            .replace(parse!("f(x___)").unwrap())
+            .function_arity(vec![(symbol!("f"), (3, Some(3)))])
+            .with(parse!("1").unwrap());
+        assert_eq!(r, parse!("1 + f(1,2)").unwrap());
+    }
+
+    #[test]
+    fn anchored() {
+        let p = parse!("a+b").unwrap().to_pattern();
+        let settings = MatchSettings {
+            anchored: true,
+            ..MatchSettings::default()
+        };
+
+        let a = parse!("a+b").unwrap();
+        assert!(a.pattern_match(&p, None, &settings).next().is_some());
+
+        let a = parse!("(a+b)*c").unwrap();
+        assert!(a.pattern_match(&p, None, None).next().is_some());
+        assert!(a.pattern_match(&p, None, &settings).next().is_none());
+
+        let r = a
+            .replace(p.clone())
+            .anchored(true)
+            .with(parse!("d").unwrap());
+        assert_eq!(r, a);
+
+        let a = parse!("a+b+c").unwrap();
+        assert!(a.pattern_match(&p, None, None).next().is_some());
+        assert!(a.pattern_match(&p, None, &settings).next().is_none());
+
+        let r = a.replace(p).anchored(true).with(parse!("d").unwrap());
+        assert_eq!(r, a);
+    }
+
+    #[test]
+    fn contains() {
+        let a = parse!("sin(x)+1").unwrap();
+        assert!(a.as_view().contains(parse!("x").unwrap()));
+        assert!(a.as_view().contains(parse!("sin(x)").unwrap()));
+        assert!(!a.as_view().contains(parse!("y").unwrap()));
+    }
+
     #[test]
     fn multiple() {
         let a = parse!("f(v1,v2)").unwrap();
@@ -4411,4 +4656,32 @@ mod test {
         let vars = e.as_view().is_polynomial(false, false).unwrap();
         assert_eq!(vars.len(), 5);
     }
+
+    #[test]
+    fn eval_at() {
+        let v1 = symbol!("v1");
+        let expr = parse!("v1^2 + v1 + f1(v1)").unwrap();
+
+        let r = expr.eval_at(v1, parse!("v2+1").unwrap());
+        assert_eq!(r, parse!("(v2+1)^2 + v2 + 1 + f1(v2+1)").unwrap());
+
+        let r = expr.eval_at(v1, Atom::new_num(2));
+        assert_eq!(r, parse!("6 + f1(2)").unwrap());
+    }
+
+    #[test]
+    fn replace_positions() {
+        let expr = parse!("g(h(x, f(x)))^f(x)").unwrap();
+        let (r, sites) = expr
+            .replace(parse!("f(x)").unwrap())
+            .with_positions(parse!("y").unwrap());
+        assert_eq!(r, parse!("g(h(x, y))^y").unwrap());
+        assert_eq!(sites, vec![vec![0, 0, 1], vec![1]]);
+
+        let (r, sites) = expr
+            .replace(parse!("z").unwrap())
+            .with_positions(parse!("y").unwrap());
+        assert_eq!(r, expr);
+        assert!(sites.is_empty());
+    }
 }
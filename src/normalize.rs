@@ -348,6 +348,18 @@ impl<'a> AtomView<'a> {
 }
 
 impl Atom {
+    /// Normalize the atom in place, using a buffer from the workspace
+    /// instead of a caller-managed output atom.
+    pub fn normalize_in_place(&mut self, workspace: &Workspace) {
+        if !self.as_view().needs_normalization() {
+            return;
+        }
+
+        let mut out = workspace.new_atom();
+        self.as_view().normalize(workspace, &mut out);
+        std::mem::swap(self, out.deref_mut());
+    }
+
     /// Merge two factors if possible. If this function returns `true`, `self`
     /// will have been updated by the merge from `other` and `other` should be discarded.
     /// If the function return `false`, no merge was possible and no modifications were made.
@@ -682,8 +694,10 @@ impl<'a> AtomView<'a> {
             AtomView::Num(_) | AtomView::Var(_) => false,
             AtomView::Fun(f) => !f.is_normalized(),
             AtomView::Pow(p) => !p.is_normalized(),
-            AtomView::Mul(m) => !m.is_normalized(),
-            AtomView::Add(a) => !a.is_normalized(),
+            // a sum or product with a single argument is never canonical,
+            // even when it is marked as normalized
+            AtomView::Mul(m) => !m.is_normalized() || m.get_nargs() == 1,
+            AtomView::Add(a) => !a.is_normalized() || a.get_nargs() == 1,
         }
     }
 
@@ -1550,6 +1564,7 @@ mod test {
         atom::{Atom, AtomCore},
         parse,
         printer::PrintOptions,
+        state::Workspace,
     };
 
     #[test]
@@ -1559,6 +1574,82 @@ mod test {
         assert_eq!(res, refr);
     }
 
+    #[test]
+    fn numeric_folding() {
+        assert_eq!(parse!("2+3*4").unwrap(), Atom::new_num(14));
+        assert_eq!(parse!("2^3").unwrap(), Atom::new_num(8));
+        assert_eq!(parse!("2^10*(1/2+3^2)").unwrap(), Atom::new_num(9728));
+
+        let a = Atom::new_num(2) + Atom::new_num(3) * Atom::new_num(4).npow(2);
+        assert_eq!(a, Atom::new_num(50));
+    }
+
+    #[test]
+    fn exact_roots() {
+        assert_eq!(parse!("8^(1/3)").unwrap(), Atom::new_num(2));
+        assert_eq!(parse!("8^(2/3)").unwrap(), Atom::new_num(4));
+        assert_eq!(parse!("(4/9)^(-1/2)").unwrap(), parse!("3/2").unwrap());
+
+        let r = parse!("2^(1/2)").unwrap();
+        assert!(matches!(r, Atom::Pow(_)));
+        let r = parse!("(-8)^(1/3)").unwrap();
+        assert!(matches!(r, Atom::Pow(_)));
+    }
+
+    #[test]
+    fn trivial_powers() {
+        assert_eq!(parse!("v1^0").unwrap(), Atom::new_num(1));
+        assert_eq!(parse!("v1^1").unwrap(), parse!("v1").unwrap());
+        assert_eq!(parse!("(v1+v2)^0").unwrap(), Atom::new_num(1));
+        assert_eq!(parse!("(v1+v2)^1").unwrap(), parse!("v1+v2").unwrap());
+    }
+
+    #[test]
+    fn single_term_collapse() {
+        let x = parse!("v1*v2").unwrap();
+
+        Workspace::get_local().with(|ws| {
+            let mut a = Atom::new();
+            a.to_add().extend(x.as_view());
+            let mut out = Atom::new();
+            a.as_view().normalize(ws, &mut out);
+            assert_eq!(out, x);
+
+            let add = a.to_add();
+            add.extend(x.as_view());
+            add.set_normalized(true);
+            a.as_view().normalize(ws, &mut out);
+            assert_eq!(out, x);
+
+            let mul = a.to_mul();
+            mul.extend(parse!("v1+v2").unwrap().as_view());
+            mul.set_normalized(true);
+            a.as_view().normalize(ws, &mut out);
+            assert_eq!(out, parse!("v1+v2").unwrap());
+        });
+    }
+
+    #[test]
+    fn normalize_in_place() {
+        Workspace::get_local().with(|ws| {
+            let mut a = Atom::new();
+            let m = a.to_mul();
+            m.extend(parse!("v1+v2").unwrap().as_view());
+            m.extend(Atom::new_num(3).as_view());
+            m.extend(parse!("v1+v2").unwrap().as_view());
+
+            let mut out = Atom::new();
+            a.as_view().normalize(ws, &mut out);
+
+            a.normalize_in_place(ws);
+            assert_eq!(a, out);
+            assert_eq!(a, parse!("3*(v1+v2)^2").unwrap());
+
+            a.normalize_in_place(ws);
+            assert_eq!(a, out);
+        });
+    }
+
     #[test]
     fn pow_simplify() {
         assert_eq!(parse!("1^(1/2)"), parse!("1"));
@@ -73,12 +73,16 @@ const HEX_TO_DIGIT: [u8; 24] = [
     0, 1, 2, 3, 4, 5, 6, 7, 8, 9, 0, 0, 0, 0, 0, 0, 0, 10, 11, 12, 13, 14, 15, 0,
 ];
 
+/// The namespace of the symbols created for template placeholders `{name}`.
+pub(crate) const TEMPLATE_NAMESPACE: &str = "template";
+
 /// The current parsing state.
 #[derive(Debug, Copy, Clone, PartialEq, Eq)]
 enum ParseState {
     Identifier,
     Number,
     RationalPolynomial,
+    Placeholder,
     Any,
 }
 
@@ -383,11 +387,7 @@ impl Token {
     ) -> Result<Atom, String> {
         let mut atom = workspace.new_atom();
 
-        {
-            let mut state = State::get_global_state().write().unwrap();
-            // do not normalize to prevent potential deadlocks
-            self.to_atom_with_output_no_norm(namespace, &mut state, workspace, &mut atom)?;
-        }
+        self.to_atom_with_output_no_norm(namespace, workspace, &mut atom)?;
 
         let mut out = Atom::new();
         atom.as_view().normalize(workspace, &mut out);
@@ -399,7 +399,6 @@ impl Token {
     fn to_atom_with_output_no_norm(
         &self,
         namespace: &DefaultNamespace,
-        state: &mut State,
         workspace: &Workspace,
         out: &mut Atom,
     ) -> Result<(), String> {
@@ -417,7 +416,7 @@ impl Token {
                 },
             },
             Token::ID(x) => {
-                out.to_var(state.get_symbol(namespace.attach_namespace(x)));
+                out.to_var(State::get_or_insert_symbol(namespace.attach_namespace(x)));
             }
             Token::Op(_, _, op, args) => match op {
                 Operator::Mul => {
@@ -425,7 +424,7 @@ impl Token {
 
                     let mut atom = workspace.new_atom();
                     for a in args {
-                        a.to_atom_with_output_no_norm(namespace, state, workspace, &mut atom)?;
+                        a.to_atom_with_output_no_norm(namespace, workspace, &mut atom)?;
                         mul.extend(atom.as_view());
                     }
                 }
@@ -434,7 +433,7 @@ impl Token {
 
                     let mut atom = workspace.new_atom();
                     for a in args {
-                        a.to_atom_with_output_no_norm(namespace, state, workspace, &mut atom)?;
+                        a.to_atom_with_output_no_norm(namespace, workspace, &mut atom)?;
                         add.extend(atom.as_view());
                     }
                 }
@@ -442,10 +441,10 @@ impl Token {
                     // pow is right associative
                     args.last()
                         .unwrap()
-                        .to_atom_with_output_no_norm(namespace, state, workspace, out)?;
+                        .to_atom_with_output_no_norm(namespace, workspace, out)?;
                     for a in args.iter().rev().skip(1) {
                         let mut cur_base = workspace.new_atom();
-                        a.to_atom_with_output_no_norm(namespace, state, workspace, &mut cur_base)?;
+                        a.to_atom_with_output_no_norm(namespace, workspace, &mut cur_base)?;
 
                         let mut pow_h = workspace.new_atom();
                         pow_h.to_pow(cur_base.as_view(), out.as_view());
@@ -457,7 +456,7 @@ impl Token {
                     debug_assert!(args.len() == 1);
 
                     let mut base = workspace.new_atom();
-                    args[0].to_atom_with_output_no_norm(namespace, state, workspace, &mut base)?;
+                    args[0].to_atom_with_output_no_norm(namespace, workspace, &mut base)?;
 
                     let num = workspace.new_num(-1);
 
@@ -469,7 +468,7 @@ impl Token {
                     debug_assert!(args.len() == 1);
 
                     let mut base = workspace.new_atom();
-                    args[0].to_atom_with_output_no_norm(namespace, state, workspace, &mut base)?;
+                    args[0].to_atom_with_output_no_norm(namespace, workspace, &mut base)?;
 
                     let num = workspace.new_num(-1);
 
@@ -482,10 +481,12 @@ impl Token {
                     _ => unreachable!(),
                 };
 
-                let fun = out.to_fun(state.get_symbol(namespace.attach_namespace(name)));
+                let fun = out.to_fun(State::get_or_insert_symbol(
+                    namespace.attach_namespace(name),
+                ));
                 let mut atom = workspace.new_atom();
                 for a in args.iter().skip(1) {
-                    a.to_atom_with_output_no_norm(namespace, state, workspace, &mut atom)?;
+                    a.to_atom_with_output_no_norm(namespace, workspace, &mut atom)?;
                     fun.add_arg(atom.as_view());
                 }
             }
@@ -777,6 +778,40 @@ impl Token {
                     column_counter += pos + 1;
                     c = char_iter.next().unwrap_or('\0');
                 }
+                ParseState::Placeholder => {
+                    let (start_line, start_column) = (line_counter, column_counter);
+
+                    let mut name = String::new();
+                    while c != '}' && c != '\0' {
+                        name.push(c);
+                        column_counter += 1;
+                        c = char_iter.next().unwrap_or('\0');
+                    }
+
+                    if c == '\0' {
+                        Err(format!(
+                            "Missing }} of placeholder started at line {} and column {}",
+                            start_line, start_column
+                        ))?;
+                    }
+
+                    let name = name.trim();
+                    if name.is_empty() || name.contains(|x: char| forbidden.contains(&x)) {
+                        Err(format!(
+                            "Invalid placeholder name '{}' at line {} and column {}",
+                            name, start_line, start_column
+                        ))?;
+                    }
+
+                    stack.push(Token::ID(
+                        format!("{}::{}", TEMPLATE_NAMESPACE, name).into(),
+                    ));
+
+                    state = ParseState::Any;
+
+                    column_counter += 1;
+                    c = char_iter.next().unwrap_or('\0');
+                }
                 ParseState::Any => {}
             }
 
@@ -874,6 +909,16 @@ impl Token {
                         }
                     }
                     ']' => stack.push(Token::CloseBracket),
+                    // a placeholder can only start a token, as `{` may be part of an identifier
+                    '{' => {
+                        if unsafe { stack.last().unwrap_unchecked() }.is_normal() {
+                            // insert multiplication: x{a} -> x*{a}
+                            stack.push(Token::Op(true, true, Operator::Mul, vec![]));
+                            extra_ops.push(c);
+                        } else {
+                            state = ParseState::Placeholder;
+                        }
+                    }
                     _ => {
                         if unsafe { stack.last().unwrap_unchecked() }.is_normal()
                             && (!c.is_ascii_digit()
@@ -1175,13 +1220,12 @@ impl Token {
 
                         if len <= 20 {
                             if let Ok(n) = n.parse::<i64>() {
-                                break 'read_coeff field.element_from_coefficient(n.into());
+                                break 'read_coeff field.element_from_integer(n.into());
                             }
                         }
 
                         if let Ok(n) = n.parse::<i128>() {
-                            break 'read_coeff field
-                                .element_from_coefficient(Integer::Double(n).into());
+                            break 'read_coeff field.element_from_integer(Integer::Double(n));
                         }
                     }
 
@@ -1212,7 +1256,8 @@ impl Token {
                         )
                     };
 
-                    field.element_from_coefficient(p.into())
+                    // convert directly to avoid a copy through a rational coefficient
+                    field.element_from_integer(p.into())
                 }
             }
 
@@ -1318,7 +1363,12 @@ mod test {
     use std::sync::Arc;
 
     use crate::{
-        atom::AtomCore, domains::integer::Z, parse, parser::Token, printer::PrintOptions, symbol,
+        atom::{Atom, AtomCore},
+        domains::integer::{Integer, Z},
+        parse,
+        parser::Token,
+        printer::PrintOptions,
+        symbol,
     };
 
     #[test]
@@ -1377,6 +1427,39 @@ mod test {
         assert_eq!(input, res);
     }
 
+    #[test]
+    fn template() {
+        let template = parse!("v1*{slot}+v2 + f({ other })v1 {slot}").unwrap();
+        assert_eq!(
+            template,
+            parse!("v1*template::slot+v2+f(template::other)*v1*template::slot").unwrap()
+        );
+
+        let r = template.fill_template("slot", parse!("x^2").unwrap());
+        assert_eq!(r, parse!("v1*x^2+v2+f(template::other)*v1*x^2").unwrap());
+
+        // braces inside an identifier are part of its name
+        assert_eq!(parse!("x_{1}").unwrap(), Atom::new_var(symbol!("x_{1}")));
+
+        assert!(parse!("v1*{slot").is_err());
+        assert!(parse!("v1*{}").is_err());
+    }
+
+    #[test]
+    fn poly_large_coefficient() {
+        let var_names = ["v1".into()];
+        let var_map = Arc::new(vec![symbol!("v1").into()]);
+
+        let digits = "1234567890".repeat(20);
+        let input = format!("-{}*v1+{}", digits, digits);
+        let (rest, poly) =
+            Token::parse_polynomial::<_, u8>(input.as_bytes(), &var_map, &var_names, &Z);
+
+        assert!(rest.is_empty());
+        let c: Integer = digits.parse().unwrap();
+        assert_eq!(poly.coefficients, vec![c.clone(), -c]);
+    }
+
     #[test]
     fn poly() {
         let var_names = ["v1".into(), "v2".into()];
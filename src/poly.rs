@@ -10,6 +10,7 @@ pub mod series;
 pub mod univariate;
 
 use std::borrow::Cow;
+use std::cell::Cell;
 use std::cmp::Ordering::{self, Equal};
 use std::fmt::{Debug, Display};
 use std::hash::Hash;
@@ -27,7 +28,8 @@ use crate::domains::atom::AtomField;
 use crate::domains::factorized_rational_polynomial::{
     FactorizedRationalPolynomial, FromNumeratorAndFactorizedDenominator,
 };
-use crate::domains::integer::{gcd_signed, gcd_unsigned, Integer};
+use crate::domains::integer::{gcd_signed, gcd_unsigned, Integer, Z};
+use crate::domains::rational::Q;
 use crate::domains::rational_polynomial::{FromNumeratorAndDenominator, RationalPolynomial};
 use crate::domains::{EuclideanDomain, Ring, SelfRing};
 use crate::parser::{Operator, Token};
@@ -746,6 +748,67 @@ impl Variable {
     }
 }
 
+/// A milestone reached during a long-running polynomial algorithm,
+/// such as a GCD computation or a factorization.
+///
+/// Milestones are reported to the callback installed with [with_progress].
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Progress {
+    /// A new prime was selected to compute a modular image.
+    Prime(u64),
+    /// A new evaluation round of the heuristic GCD algorithm was started.
+    HeuristicRound(usize),
+    /// The factorization of the `index`-th out of `total` square-free factors was started.
+    SquareFreeFactor { index: usize, total: usize },
+}
+
+type ProgressCallback = Box<dyn FnMut(Progress)>;
+
+thread_local!(
+    static PROGRESS_CALLBACK: Cell<Option<ProgressCallback>> = const { Cell::new(None) };
+);
+
+/// Restores the previous progress callback when dropped.
+struct ProgressGuard(Option<ProgressCallback>);
+
+impl Drop for ProgressGuard {
+    fn drop(&mut self) {
+        PROGRESS_CALLBACK.with(|c| c.set(self.0.take()));
+    }
+}
+
+/// Execute `f` while reporting the milestones of polynomial algorithms
+/// that run on the current thread to `callback`.
+///
+/// # Example
+///
+/// ```
+/// use std::{cell::Cell, rc::Rc};
+/// use symbolica::{atom::AtomCore, domains::integer::Z, parse, poly::with_progress};
+///
+/// let a = parse!("(x+y)*(x-y)^2").unwrap().expand().to_polynomial::<_, u8>(&Z, None);
+/// let b = parse!("(x+y)*(x+2)").unwrap().expand().to_polynomial(&Z, a.variables.clone());
+///
+/// let steps = Rc::new(Cell::new(0));
+/// let s = steps.clone();
+/// let g = with_progress(move |_| s.set(s.get() + 1), || a.gcd(&b));
+/// println!("gcd {} computed in {} steps", g, steps.get());
+/// ```
+pub fn with_progress<T>(callback: impl FnMut(Progress) + 'static, f: impl FnOnce() -> T) -> T {
+    let _guard = ProgressGuard(PROGRESS_CALLBACK.with(|c| c.replace(Some(Box::new(callback)))));
+    f()
+}
+
+/// Report a milestone to the progress callback of the current thread, if there is one.
+pub(crate) fn report_progress(progress: Progress) {
+    if let Some(callback) = PROGRESS_CALLBACK.with(|c| c.take()) {
+        // the callback is taken out during the call, so that it is not aliased
+        // when the callback itself performs polynomial operations
+        let mut guard = ProgressGuard(Some(callback));
+        (guard.0.as_mut().unwrap())(progress);
+    }
+}
+
 impl<'a> AtomView<'a> {
     /// Convert an expanded expression to a polynomial.
     fn to_polynomial_expanded<R: Ring + ConvertToRing, E: Exponent>(
@@ -754,10 +817,11 @@ impl<'a> AtomView<'a> {
         var_map: Option<&Arc<Vec<Variable>>>,
         allow_new_vars: bool,
     ) -> Result<MultivariatePolynomial<R, E>, &'static str> {
-        fn check_factor(
+        fn check_factor<R: Ring + ConvertToRing, E: Exponent>(
             factor: &AtomView<'_>,
             vars: &mut Vec<Variable>,
             allow_new_vars: bool,
+            field: &R,
         ) -> Result<(), &'static str> {
             match factor {
                 AtomView::Num(n) => match n.get_coeff_view() {
@@ -768,6 +832,10 @@ impl<'a> AtomView<'a> {
                 },
                 AtomView::Var(v) => {
                     let name = v.get_symbol();
+                    if field.element_from_symbol(name).is_some() {
+                        return Ok(());
+                    }
+
                     if !vars.contains(&name.into()) {
                         if !allow_new_vars {
                             return Err("Expression contains variable that is not in variable map");
@@ -780,9 +848,34 @@ impl<'a> AtomView<'a> {
                 AtomView::Fun(_) => Err("function not supported in polynomial"),
                 AtomView::Pow(p) => {
                     let (base, exp) = p.get_base_exp();
+
+                    // x^0 does not introduce a variable
+                    if let AtomView::Num(n) = exp {
+                        if n.is_zero() {
+                            return Ok(());
+                        }
+                    }
+
                     match base {
                         AtomView::Var(v) => {
                             let name = v.get_symbol();
+                            if field.element_from_symbol(name).is_some() {
+                                // the symbol is part of the coefficient ring
+                                return match exp {
+                                    AtomView::Num(n) => match n.get_coeff_view() {
+                                        CoefficientView::Natural(n, 1)
+                                            if n >= 0 && n <= u32::MAX as i64 =>
+                                        {
+                                            Ok(())
+                                        }
+                                        _ => Err(
+                                            "Exponent of coefficient is not a small natural number",
+                                        ),
+                                    },
+                                    _ => Err("Exponent must be a number"),
+                                };
+                            }
+
                             if !vars.contains(&name.into()) {
                                 if !allow_new_vars {
                                     return Err(
@@ -799,10 +892,19 @@ impl<'a> AtomView<'a> {
                     match exp {
                         AtomView::Num(n) => match n.get_coeff_view() {
                             CoefficientView::Natural(n, d) => {
-                                if d == 1 && n >= 0 && n <= u32::MAX as i64 {
+                                if d != 1 {
+                                    Err("Exponent is a fraction")
+                                } else if n >= 0 && n <= u32::MAX as i64 {
+                                    Ok(())
+                                } else if i32::try_from(n)
+                                    .ok()
+                                    .and_then(|e| E::try_from(e).ok())
+                                    .is_some()
+                                {
+                                    // negative exponents are allowed when E supports them
                                     Ok(())
                                 } else {
-                                    Err("Exponent negative or a fraction")
+                                    Err("Exponent negative")
                                 }
                             }
                             CoefficientView::Large(r) => {
@@ -834,19 +936,20 @@ impl<'a> AtomView<'a> {
             }
         }
 
-        fn check_term(
+        fn check_term<R: Ring + ConvertToRing, E: Exponent>(
             term: &AtomView<'_>,
             vars: &mut Vec<Variable>,
             allow_new_vars: bool,
+            field: &R,
         ) -> Result<(), &'static str> {
             match term {
                 AtomView::Mul(m) => {
                     for factor in m {
-                        check_factor(&factor, vars, allow_new_vars)?;
+                        check_factor::<R, E>(&factor, vars, allow_new_vars, field)?;
                     }
                     Ok(())
                 }
-                _ => check_factor(term, vars, allow_new_vars),
+                _ => check_factor::<R, E>(term, vars, allow_new_vars, field),
             }
         }
 
@@ -856,12 +959,12 @@ impl<'a> AtomView<'a> {
         match self {
             AtomView::Add(a) => {
                 for term in a {
-                    check_term(&term, &mut vars, allow_new_vars)?;
+                    check_term::<R, E>(&term, &mut vars, allow_new_vars, field)?;
                     n_terms += 1;
                 }
             }
             _ => {
-                check_term(self, &mut vars, allow_new_vars)?;
+                check_term::<R, E>(self, &mut vars, allow_new_vars, field)?;
                 n_terms += 1;
             }
         }
@@ -872,7 +975,27 @@ impl<'a> AtomView<'a> {
             coefficient: &mut R::Element,
             exponents: &mut SmallVec<[E; INLINED_EXPONENTS]>,
             field: &R,
-        ) {
+        ) -> Result<(), &'static str> {
+            fn add_exponent<E: Exponent>(
+                exponents: &mut SmallVec<[E; INLINED_EXPONENTS]>,
+                vars: &[Variable],
+                var: Symbol,
+                exp: i64,
+            ) -> Result<(), &'static str> {
+                let index = vars
+                    .iter()
+                    .position(|v| *v == var.into())
+                    .ok_or("Variable missing from variable map")?;
+                let exp = i32::try_from(exp)
+                    .ok()
+                    .and_then(|e| E::try_from(e).ok())
+                    .ok_or("Exponent does not fit in exponent type")?;
+                exponents[index] = exponents[index]
+                    .checked_add(&exp)
+                    .ok_or("Exponent does not fit in exponent type")?;
+                Ok(())
+            }
+
             match factor {
                 AtomView::Num(n) => {
                     field.mul_assign(
@@ -881,79 +1004,113 @@ impl<'a> AtomView<'a> {
                     );
                 }
                 AtomView::Var(v) => {
-                    let id = v.get_symbol();
-                    exponents[vars.iter().position(|v| *v == id.into()).unwrap()] += E::one();
+                    if let Some(c) = field.element_from_symbol(v.get_symbol()) {
+                        field.mul_assign(coefficient, &c);
+                    } else {
+                        add_exponent(exponents, vars, v.get_symbol(), 1)?;
+                    }
                 }
                 AtomView::Pow(p) => {
                     let (base, exp) = p.get_base_exp();
 
-                    let var_index = match base {
-                        AtomView::Var(v) => {
-                            let id = v.get_symbol();
-                            vars.iter().position(|v| *v == id.into()).unwrap()
+                    if let AtomView::Num(n) = exp {
+                        if n.is_zero() {
+                            return Ok(());
                         }
-                        _ => unreachable!(),
+                    }
+
+                    let AtomView::Var(v) = base else {
+                        return Err("base must be a variable");
                     };
 
-                    match exp {
+                    let exp = match exp {
                         AtomView::Num(n) => match n.get_coeff_view() {
-                            CoefficientView::Natural(r, _) => {
-                                exponents[var_index] += E::from_i32(r as i32)
-                            }
-                            CoefficientView::Large(r) => {
-                                exponents[var_index] +=
-                                    E::from_i32(r.to_rat().numerator_ref().to_i64().unwrap() as i32)
-                            }
-                            _ => unreachable!(),
+                            CoefficientView::Natural(r, 1) => r,
+                            CoefficientView::Large(r) => r
+                                .to_rat()
+                                .numerator_ref()
+                                .to_i64()
+                                .ok_or("Exponent does not fit in exponent type")?,
+                            _ => return Err("Exponent must be an integer"),
                         },
-                        _ => unreachable!(),
+                        _ => return Err("Exponent must be a number"),
+                    };
+
+                    if let Some(c) = field.element_from_symbol(v.get_symbol()) {
+                        let exp = u64::try_from(exp).map_err(|_| "Exponent negative")?;
+                        field.mul_assign(coefficient, &field.pow(&c, exp));
+                    } else {
+                        add_exponent(exponents, vars, v.get_symbol(), exp)?;
                     }
                 }
-                _ => unreachable!("Unsupported expression"),
+                _ => return Err("Unsupported expression"),
             }
+
+            Ok(())
         }
 
         fn parse_term<R: Ring + ConvertToRing, E: Exponent>(
             term: &AtomView<'_>,
             vars: &[Variable],
-            poly: &mut MultivariatePolynomial<R, E>,
             field: &R,
-        ) {
-            let mut coefficient = poly.ring.one();
+        ) -> Result<(R::Element, SmallVec<[E; INLINED_EXPONENTS]>), &'static str> {
+            let mut coefficient = field.one();
             let mut exponents = smallvec![E::zero(); vars.len()];
 
             match term {
                 AtomView::Mul(m) => {
                     for factor in m {
-                        parse_factor(&factor, vars, &mut coefficient, &mut exponents, field);
+                        parse_factor(&factor, vars, &mut coefficient, &mut exponents, field)?;
                     }
                 }
-                _ => parse_factor(term, vars, &mut coefficient, &mut exponents, field),
+                _ => parse_factor(term, vars, &mut coefficient, &mut exponents, field)?,
             }
 
-            poly.append_monomial(coefficient, &exponents);
+            Ok((coefficient, exponents))
         }
 
-        let mut poly =
-            MultivariatePolynomial::<R, E>::new(field, Some(n_terms), Arc::new(vars.clone()));
+        // reuse the variable map if no new variables were added
+        let variables = match var_map {
+            Some(v) if v.len() == vars.len() => v.clone(),
+            _ => Arc::new(vars.clone()),
+        };
+        let mut poly = MultivariatePolynomial::<R, E>::new(field, Some(n_terms), variables);
 
         match self {
             AtomView::Add(a) => {
-                for term in a {
-                    parse_term(&term, &vars, &mut poly, field);
-                }
+                let terms = a
+                    .iter()
+                    .map(|term| parse_term(&term, &vars, field))
+                    .collect::<Result<Vec<_>, _>>()?;
+                poly.append_monomials(terms);
+            }
+            _ => {
+                let (coefficient, exponents) = parse_term(self, &vars, field)?;
+                poly.append_monomial(coefficient, &exponents);
             }
-            _ => parse_term(self, &vars, &mut poly, field),
         }
 
         Ok(poly)
     }
 
+    /// Convert an expanded expression to a polynomial, optionally in the variable ordering
+    /// specified by `var_map`. New variables are added to the variable map.
+    /// Unlike [AtomView::to_polynomial], an error is returned instead of a panic
+    /// when the expression is not an expanded polynomial or when an exponent
+    /// does not fit in `E`.
+    pub(crate) fn try_to_polynomial<R: Ring + ConvertToRing, E: Exponent>(
+        &self,
+        field: &R,
+        var_map: Option<Arc<Vec<Variable>>>,
+    ) -> Result<MultivariatePolynomial<R, E>, &'static str> {
+        self.to_polynomial_expanded(field, var_map.as_ref(), true)
+    }
+
     /// Convert the atom to a polynomial, optionally in the variable ordering
     /// specified by `var_map`. If new variables are encountered, they are
     /// added to the variable map. Similarly, non-polynomial parts are automatically
     /// defined as a new independent variable in the polynomial.
-    pub(crate) fn to_polynomial<R: EuclideanDomain + ConvertToRing, E: Exponent>(
+    pub(crate) fn to_polynomial<R: Ring + ConvertToRing, E: Exponent>(
         &self,
         field: &R,
         var_map: Option<Arc<Vec<Variable>>>,
@@ -961,7 +1118,36 @@ impl<'a> AtomView<'a> {
         self.to_polynomial_impl(field, var_map.as_ref().unwrap_or(&Arc::new(Vec::new())))
     }
 
-    pub(crate) fn to_polynomial_impl<R: EuclideanDomain + ConvertToRing, E: Exponent>(
+    /// Convert the atom to a polynomial in the variables `vars`. If the atom
+    /// contains any other indeterminates, all of them are returned as an error.
+    pub(crate) fn to_polynomial_in<R: EuclideanDomain + ConvertToRing, E: Exponent>(
+        &self,
+        field: &R,
+        vars: &Arc<Vec<Variable>>,
+    ) -> Result<MultivariatePolynomial<R, E>, Vec<Variable>> {
+        let poly = self.to_polynomial_impl(field, vars);
+        if poly.nvars() == vars.len() {
+            return Ok(poly);
+        }
+
+        let unknown: Vec<_> = (vars.len()..poly.nvars())
+            .filter(|i| poly.into_iter().any(|t| !t.exponents[*i].is_zero()))
+            .map(|i| poly.variables[i].clone())
+            .collect();
+
+        if !unknown.is_empty() {
+            return Err(unknown);
+        }
+
+        // the new variables do not appear in the result
+        let mut res = MultivariatePolynomial::new(field, Some(poly.nterms()), vars.clone());
+        for t in &poly {
+            res.append_monomial(t.coefficient.clone(), &t.exponents[..vars.len()]);
+        }
+        Ok(res)
+    }
+
+    pub(crate) fn to_polynomial_impl<R: Ring + ConvertToRing, E: Exponent>(
         &self,
         field: &R,
         var_map: &Arc<Vec<Variable>>,
@@ -988,7 +1174,12 @@ impl<'a> AtomView<'a> {
                     let num_n = n.get_coeff_view();
                     if let CoefficientView::Natural(nn, nd) = num_n {
                         if nd == 1 {
-                            if nn > 0 && nn < i32::MAX as i64 {
+                            if nn == 0 {
+                                return MultivariatePolynomial::new(field, None, var_map.clone())
+                                    .constant(field.one());
+                            } else if nn == 1 {
+                                return base.to_polynomial_impl(field, var_map);
+                            } else if nn > 0 && nn < i32::MAX as i64 {
                                 return base.to_polynomial_impl(field, var_map).pow(nn as usize);
                             } else if nn < 0 && nn > i32::MIN as i64 {
                                 // allow x^-2 as a term if supported by the exponent
@@ -1119,6 +1310,18 @@ impl<'a> AtomView<'a> {
             return num;
         }
 
+        // a sum or product may itself be an indeterminate
+        if let AtomView::Add(_) | AtomView::Mul(_) = self {
+            if let Some(id) = var_map.iter().position(|v| match v {
+                Variable::Other(vv) => vv.as_view() == *self,
+                _ => false,
+            }) {
+                let mut exp = vec![E::zero(); var_map.len()];
+                exp[id] = E::one();
+                return poly.monomial(field.one(), exp);
+            }
+        }
+
         match self {
             AtomView::Num(_) | AtomView::Var(_) => poly.constant(self.to_owned()),
             AtomView::Pow(p) => {
@@ -1216,6 +1419,34 @@ impl<'a> AtomView<'a> {
         )
     }
 
+    /// Check if two expressions are equal as rational functions, by converting both
+    /// to rational polynomials over the rationals. Non-polynomial parts, such as `f(x)` or `x^(1/2)`,
+    /// are treated as independent variables. Returns `false` if an expression contains
+    /// a number that is not rational, such as a float.
+    pub(crate) fn is_equal_rational(&self, other: AtomView) -> bool {
+        let is_rational = |a: &AtomView| {
+            a.fold(true, |acc, x| {
+                acc && match x {
+                    AtomView::Num(n) => matches!(
+                        n.get_coeff_view(),
+                        CoefficientView::Natural(_, _) | CoefficientView::Large(_)
+                    ),
+                    _ => true,
+                }
+            })
+        };
+
+        if !is_rational(self) || !is_rational(&other) {
+            return false;
+        }
+
+        let mut a: RationalPolynomial<_, u16> = self.to_rational_polynomial(&Q, &Z, None);
+        let mut b: RationalPolynomial<_, u16> =
+            other.to_rational_polynomial(&Q, &Z, Some(a.get_variables().clone()));
+        a.unify_variables(&mut b);
+        a == b
+    }
+
     fn to_rational_polynomial_impl<
         R: EuclideanDomain + ConvertToRing,
         RO: EuclideanDomain + PolynomialGCD<E>,
@@ -1311,29 +1542,105 @@ impl<'a> AtomView<'a> {
                 }
             }
             AtomView::Mul(m) => {
+                // determine the variable map once, so that all arguments
+                // are constructed in the same map and never need to be unified
+                let var_map = self.extend_rational_polynomial_var_map(var_map);
                 let mut r = RationalPolynomial::new(out_field, var_map.clone());
                 r.numerator = r.numerator.add_constant(out_field.one());
                 for arg in m {
-                    let mut arg_r =
-                        arg.to_rational_polynomial_impl(field, out_field, &r.numerator.variables);
-                    r.unify_variables(&mut arg_r);
+                    let arg_r = arg.to_rational_polynomial_impl(field, out_field, &var_map);
                     r = &r * &arg_r;
                 }
                 r
             }
             AtomView::Add(a) => {
+                let var_map = self.extend_rational_polynomial_var_map(var_map);
+
+                // sum all polynomial terms separately to avoid fraction arithmetic
+                let mut poly = MultivariatePolynomial::new(field, None, var_map.clone());
                 let mut r = RationalPolynomial::new(out_field, var_map.clone());
                 for arg in a {
-                    let mut arg_r =
-                        arg.to_rational_polynomial_impl(field, out_field, &r.numerator.variables);
-                    r.unify_variables(&mut arg_r);
-                    r = &r + &arg_r;
+                    if let Ok(p) = arg.to_polynomial_expanded(field, Some(&var_map), false) {
+                        poly = &poly + &p;
+                    } else {
+                        let arg_r = arg.to_rational_polynomial_impl(field, out_field, &var_map);
+                        r = &r + &arg_r;
+                    }
+                }
+
+                if !poly.is_zero() {
+                    let den = poly.one();
+                    let p = RationalPolynomial::from_num_den(poly, den, out_field, false);
+                    r = &r + &p;
                 }
                 r
             }
         }
     }
 
+    /// Extend `var_map` with all variables that are introduced when converting
+    /// the atom to a rational polynomial, in the order in which the conversion would add them.
+    /// The original map is returned if no new variables are needed.
+    fn extend_rational_polynomial_var_map(
+        &self,
+        var_map: &Arc<Vec<Variable>>,
+    ) -> Arc<Vec<Variable>> {
+        fn collect(a: AtomView, vars: &mut Vec<Variable>) {
+            match a {
+                AtomView::Num(_) => {}
+                AtomView::Var(v) => {
+                    let v = v.get_symbol().into();
+                    if !vars.contains(&v) {
+                        vars.push(v);
+                    }
+                }
+                AtomView::Fun(f) => {
+                    if !vars.iter().any(|v| match v {
+                        Variable::Function(_, vv) => vv.as_view() == a,
+                        _ => false,
+                    }) {
+                        vars.push(Variable::Function(f.get_symbol(), Arc::new(a.to_owned())));
+                    }
+                }
+                AtomView::Pow(p) => {
+                    let (base, exp) = p.get_base_exp();
+                    if let AtomView::Num(n) = exp {
+                        if let CoefficientView::Natural(_, 1) = n.get_coeff_view() {
+                            collect(base, vars);
+                            return;
+                        }
+                    }
+
+                    if !vars.iter().any(|v| match v {
+                        Variable::Other(vv) => vv.as_view() == a,
+                        _ => false,
+                    }) {
+                        vars.push(Variable::Other(Arc::new(a.to_owned())));
+                    }
+                }
+                AtomView::Mul(m) => {
+                    for arg in m {
+                        collect(arg, vars);
+                    }
+                }
+                AtomView::Add(aa) => {
+                    for arg in aa {
+                        collect(arg, vars);
+                    }
+                }
+            }
+        }
+
+        let mut vars = var_map.as_ref().clone();
+        collect(*self, &mut vars);
+
+        if vars.len() == var_map.len() {
+            var_map.clone()
+        } else {
+            Arc::new(vars)
+        }
+    }
+
     /// Convert the atom to a rational polynomial with factorized denominators, optionally in the variable ordering
     /// specified by `var_map`. If new variables are encountered, they are
     /// added to the variable map. Similarly, non-rational polynomial parts are automatically
@@ -1578,21 +1885,23 @@ impl<R: Ring, E: Exponent, O: MonomialOrder> MultivariatePolynomial<R, E, O> {
             return;
         }
 
-        let add = out.to_add();
+        let mut add_h = workspace.new_atom();
+        let add = add_h.to_add();
 
         let mut mul_h = workspace.new_atom();
         let mut num_h = workspace.new_atom();
         let mut pow_h = workspace.new_atom();
 
+        // temporary variables that are not in the map are only an error
+        // when they appear in a monomial
         let vars: Vec<_> = self
             .variables
             .iter()
             .map(|v| {
                 if let Variable::Temporary(_) = v {
-                    let a = map.get(v).expect("Variable missing from map");
-                    a.to_owned()
+                    map.get(v).map(|a| a.to_owned())
                 } else {
-                    v.to_atom()
+                    Some(v.to_atom())
                 }
             })
             .collect();
@@ -1604,9 +1913,9 @@ impl<R: Ring, E: Exponent, O: MonomialOrder> MultivariatePolynomial<R, E, O> {
             let mul = mul_h.to_mul();
 
             for i in &sorted_vars {
-                let var = &vars[*i];
                 let pow = monomial.exponents[*i];
                 if pow != E::zero() {
+                    let var = vars[*i].as_ref().expect("Variable missing from map");
                     if pow != E::one() {
                         num_h.to_num((pow.to_i32() as i64).into());
                         pow_h.to_pow(var.as_view(), num_h.as_view());
@@ -1620,12 +1929,17 @@ impl<R: Ring, E: Exponent, O: MonomialOrder> MultivariatePolynomial<R, E, O> {
             let number = monomial.coefficient.clone().into();
             num_h.to_num(number);
             mul.extend(num_h.as_view());
+
+            // do not wrap a single monomial in a sum
+            if self.nterms() == 1 {
+                mul_h.as_view().normalize(workspace, out);
+                return;
+            }
+
             add.extend(mul_h.as_view());
         }
 
-        let mut norm = workspace.new_atom();
-        out.as_view().normalize(workspace, &mut norm);
-        std::mem::swap(norm.deref_mut(), out);
+        add_h.as_view().normalize(workspace, out);
     }
 
     pub fn to_expression_with_coeff_map<F: Fn(&R, &R::Element, &mut Atom)>(&self, f: F) -> Atom {
@@ -1653,7 +1967,8 @@ impl<R: Ring, E: Exponent, O: MonomialOrder> MultivariatePolynomial<R, E, O> {
             return;
         }
 
-        let add = out.to_add();
+        let mut add_h = workspace.new_atom();
+        let add = add_h.to_add();
 
         let mut mul_h = workspace.new_atom();
         let mut var_h = workspace.new_atom();
@@ -1690,12 +2005,16 @@ impl<R: Ring, E: Exponent, O: MonomialOrder> MultivariatePolynomial<R, E, O> {
 
             f(&self.ring, monomial.coefficient, &mut coeff);
             mul.extend(coeff.as_view());
+
+            if self.nterms() == 1 {
+                mul_h.as_view().normalize(workspace, out);
+                return;
+            }
+
             add.extend(mul_h.as_view());
         }
 
-        let mut norm = workspace.new_atom();
-        out.as_view().normalize(workspace, &mut norm);
-        std::mem::swap(norm.deref_mut(), out);
+        add_h.as_view().normalize(workspace, out);
     }
 }
 
@@ -19,6 +19,7 @@ use crate::{
     coefficient::CoefficientView,
     domains::{
         float::NumericalFloatLike,
+        integer::{Integer, IntegerRing},
         rational::{Rational, RationalField, Q},
         EuclideanDomain,
     },
@@ -317,6 +318,96 @@ where
     }
 }
 
+impl<E: PositiveExponent> MultivariatePolynomial<IntegerRing, E> {
+    /// Write the polynomial as a C expression in Horner form, using `var_names`
+    /// as the names of the variables. For example, `3*x^3+2*x` is written as
+    /// `x*(2+3*pow(x, 2))`.
+    ///
+    /// The coefficients are written as integer literals and an error is returned
+    /// if one of them does not fit in a `long long`. The variables are expected
+    /// to be of floating point type.
+    ///
+    /// To export polynomials with rational coefficients, or to share common
+    /// subexpressions between several polynomials, convert them to polynomials over
+    /// [Q], use [MultivariatePolynomial::optimize_horner_scheme] and print the resulting
+    /// instructions with an [InstructionSetPrinter] instead.
+    pub fn to_c_code(&self, var_names: &[&str]) -> Result<String, String> {
+        assert_eq!(var_names.len(), self.nvars(), "Every variable needs a name");
+
+        if let Some(c) = self
+            .coefficients
+            .iter()
+            .find(|c| !matches!(c, Integer::Natural(_)))
+        {
+            return Err(format!("Coefficient {} does not fit in a long long", c));
+        }
+
+        if self.is_zero() {
+            return Ok("0".to_string());
+        }
+
+        Ok(self.to_c_code_impl(var_names, 0, 0, self.nterms()))
+    }
+
+    /// Write the terms `start..end`, that share the exponents of all variables
+    /// before `var`, in Horner form in `var`.
+    fn to_c_code_impl(&self, var_names: &[&str], var: usize, start: usize, end: usize) -> String {
+        if var == self.nvars() {
+            return self.coefficients[start].to_string();
+        }
+
+        fn mul(x: String, c: String) -> String {
+            if c == "1" {
+                x
+            } else if c == "-1" {
+                format!("-{}", x)
+            } else if c.contains(['+', '-']) {
+                format!("{}*({})", x, c)
+            } else {
+                format!("{}*{}", c, x)
+            }
+        }
+
+        let pow = |e: E| {
+            if e == E::one() {
+                var_names[var].to_string()
+            } else {
+                format!("pow({}, {})", var_names[var], e)
+            }
+        };
+
+        // collect the terms with the same power of var, in ascending order
+        let mut groups = vec![];
+        let mut i = start;
+        while i < end {
+            let e = self.exponents(i)[var];
+            let mut j = i + 1;
+            while j < end && self.exponents(j)[var] == e {
+                j += 1;
+            }
+            groups.push((e, self.to_c_code_impl(var_names, var + 1, i, j)));
+            i = j;
+        }
+
+        let (mut last_exp, mut acc) = groups.pop().unwrap();
+        while let Some((e, c)) = groups.pop() {
+            let t = mul(pow(last_exp - e), acc);
+            acc = if t.starts_with('-') {
+                format!("{}{}", c, t)
+            } else {
+                format!("{}+{}", c, t)
+            };
+            last_exp = e;
+        }
+
+        if last_exp.is_zero() {
+            acc
+        } else {
+            mul(pow(last_exp), acc)
+        }
+    }
+}
+
 impl<E: PositiveExponent> MultivariatePolynomial<RationalField, E> {
     /// Write the polynomial in a Horner scheme with the variable ordering
     /// defined in `order`.
@@ -1960,14 +2051,17 @@ auto 𝑖 = 1i;\n",
 
 #[cfg(test)]
 mod test {
+    use std::sync::Arc;
+
     use crate::{
         atom::AtomCore,
-        domains::{float::Complex, rational::Q},
+        domains::{float::Complex, integer::Z, rational::Q},
         parse,
         poly::{
             evaluate::{BorrowedHornerScheme, InstructionSetPrinter},
             polynomial::MultivariatePolynomial,
         },
+        symbol,
     };
 
     use wide::f64x4;
@@ -2091,4 +2185,21 @@ a0^2*a2*b2^2*b3^3-2*a0^2*a2*b1*b3^4-a0^2*a1*b2*b3^4+a0^3*b3^5";
             (res.re - 3230756.634848104).abs() < 1e-6 && (res.im - 2522437.0904901037).abs() < 1e-6
         );
     }
+
+    #[test]
+    fn to_c_code() {
+        let vars = Arc::new(vec![symbol!("x").into(), symbol!("y").into()]);
+        let p = parse!("3*x^3*y + 2*x - x*y^2 + 5")
+            .unwrap()
+            .to_polynomial::<_, u8>(&Z, Some(vars));
+        assert_eq!(
+            p.to_c_code(&["x", "y"]).unwrap(),
+            "5+x*(2-pow(y, 2)+3*y*pow(x, 2))"
+        );
+
+        let p = parse!("2^70*x + 1")
+            .unwrap()
+            .to_polynomial::<_, u8>(&Z, None);
+        assert!(p.to_c_code(&["x"]).is_err());
+    }
 }
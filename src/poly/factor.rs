@@ -22,7 +22,10 @@ use crate::{
     poly::Variable,
 };
 
-use super::{gcd::PolynomialGCD, polynomial::MultivariatePolynomial, LexOrder, PositiveExponent};
+use super::{
+    gcd::PolynomialGCD, polynomial::MultivariatePolynomial, report_progress, with_progress,
+    LexOrder, PositiveExponent, Progress,
+};
 
 /// A polynomial that can be factorized.
 pub trait Factorize: Sized {
@@ -33,6 +36,12 @@ pub trait Factorize: Sized {
     /// Factor a polynomial over its coefficient ring.
     fn factor(&self) -> Vec<(Self, usize)>;
     fn is_irreducible(&self) -> bool;
+
+    /// Factor a polynomial over its coefficient ring, while reporting
+    /// the milestones of the algorithm to `callback`.
+    fn factor_with_progress(&self, callback: impl FnMut(Progress) + 'static) -> Vec<(Self, usize)> {
+        with_progress(callback, || self.factor())
+    }
 }
 
 impl<F: EuclideanDomain + PolynomialGCD<E>, E: PositiveExponent>
@@ -532,8 +541,10 @@ impl<E: PositiveExponent> Factorize for MultivariatePolynomial<IntegerRing, E, L
 
         let mut factors = vec![];
         let mut degrees = vec![0; self.nvars()];
-        for (f, p) in sf {
+        let total = sf.len();
+        for (index, (f, p)) in sf.into_iter().enumerate() {
             debug!("SFF {} {}", f, p);
+            report_progress(Progress::SquareFreeFactor { index, total });
 
             let mut var_count = 0;
             for (v, d) in degrees.iter_mut().enumerate() {
@@ -758,7 +769,12 @@ impl<E: PositiveExponent> Factorize
         let sf = self.square_free_factorization();
 
         let mut full_factors = vec![];
-        for (f, p) in &sf {
+        for (index, (f, p)) in sf.iter().enumerate() {
+            report_progress(Progress::SquareFreeFactor {
+                index,
+                total: sf.len(),
+            });
+
             if f.is_constant() {
                 full_factors.push((f.clone(), *p));
                 continue;
@@ -835,8 +851,10 @@ where
 
         let mut factors = vec![];
         let mut degrees = vec![0; self.nvars()];
-        for (f, p) in sf {
+        let total = sf.len();
+        for (index, (f, p)) in sf.into_iter().enumerate() {
             debug!("SFF {} {}", f, p);
+            report_progress(Progress::SquareFreeFactor { index, total });
 
             let mut var_count = 0;
             for v in 0..self.nvars() {
@@ -3744,7 +3762,7 @@ impl<E: PositiveExponent> MultivariatePolynomial<FiniteField<Integer>, E, LexOrd
 
 #[cfg(test)]
 mod test {
-    use std::sync::Arc;
+    use std::{cell::RefCell, rc::Rc, sync::Arc};
 
     use crate::{
         atom::AtomCore,
@@ -3756,7 +3774,7 @@ mod test {
             InternalOrdering,
         },
         parse,
-        poly::factor::Factorize,
+        poly::{factor::Factorize, Progress},
         symbol,
     };
 
@@ -3849,6 +3867,38 @@ mod test {
         assert_eq!(r, res);
     }
 
+    #[test]
+    fn square_free_univariate() {
+        let poly = parse!("(v1+1)^2*(v1-2)")
+            .unwrap()
+            .expand()
+            .to_polynomial::<_, u8>(&Z, None);
+
+        let mut res = [("v1+1", 2), ("v1-2", 1)]
+            .iter()
+            .map(|(f, p)| {
+                (
+                    parse!(f).unwrap().to_polynomial(&Z, poly.variables.clone()),
+                    *p,
+                )
+            })
+            .collect::<Vec<_>>();
+
+        res.sort_by(|a, b| a.0.internal_cmp(&b.0).then(a.1.cmp(&b.1)));
+        let mut r = poly.square_free_factorization();
+        r.sort_by(|a, b| a.0.internal_cmp(&b.0).then(a.1.cmp(&b.1)));
+        assert_eq!(r, res);
+
+        let poly = parse!("6").unwrap().to_polynomial::<_, u8>(&Z, None);
+        let r = poly.square_free_factorization();
+        assert!(r.iter().all(|(f, _)| f.is_constant()));
+        let mut prod = poly.one();
+        for (f, p) in &r {
+            prod = &prod * &f.pow(*p);
+        }
+        assert_eq!(prod, poly);
+    }
+
     #[test]
     fn factor_univariate_1() {
         let poly = parse!("2*(4 + 3*v1)*(3 + 2*v1 + 3*v1^2)*(3 + 8*v1^2)*(4 + v1 + v1^16)")
@@ -3952,6 +4002,32 @@ mod test {
         assert_eq!(r, res);
     }
 
+    #[test]
+    fn factor_rational() {
+        let poly = parse!("(v1/2+1)*(v1*v2-3)^2*(v2+2/3)")
+            .unwrap()
+            .expand()
+            .to_polynomial::<_, u8>(&Q, None);
+
+        let factors = poly.factor();
+        assert_eq!(factors.iter().filter(|(f, _)| !f.is_constant()).count(), 3);
+
+        let mut r = poly.one();
+        for (f, p) in &factors {
+            r = &r * &f.pow(*p);
+        }
+        assert_eq!(r, poly);
+
+        let poly = parse!("3/2").unwrap().to_polynomial::<_, u8>(&Q, None);
+        let factors = poly.factor();
+        assert!(factors.iter().all(|(f, _)| f.is_constant()));
+        let mut r = poly.one();
+        for (f, p) in &factors {
+            r = &r * &f.pow(*p);
+        }
+        assert_eq!(r, poly);
+    }
+
     #[test]
     fn factor_multivariate() {
         let input = "(v1*(2+2*v2+2*v3)+1)*(v1*(4+v3^2)+v2+3)*(v1*(v4+v4^2+4+v2)+v4+5)";
@@ -4050,6 +4126,23 @@ mod test {
         assert_eq!(r, res);
     }
 
+    #[test]
+    fn factor_progress() {
+        let poly = parse!("(v1^2+v2*v3+1)*(v1*v2-v3^2+3)^2*(v1+v2+v3)")
+            .unwrap()
+            .expand()
+            .to_polynomial::<_, u8>(&Z, None);
+
+        let steps = Rc::new(RefCell::new(vec![]));
+        let s = steps.clone();
+        let factors = poly.factor_with_progress(move |p| s.borrow_mut().push(p));
+        let steps = steps.borrow();
+
+        assert_eq!(factors.len(), 3);
+        assert!(!steps.is_empty());
+        assert!(steps.contains(&Progress::SquareFreeFactor { index: 0, total: 2 }));
+    }
+
     #[test]
     fn galois_upgrade() {
         let a =
@@ -21,7 +21,7 @@ use crate::poly::INLINED_EXPONENTS;
 use crate::tensors::matrix::{Matrix, MatrixError};
 
 use super::polynomial::MultivariatePolynomial;
-use super::PositiveExponent;
+use super::{report_progress, with_progress, PositiveExponent, Progress, Variable};
 
 /// The maximum power of a variable that is cached
 pub(crate) const POW_CACHE_SIZE: usize = 1000;
@@ -1456,9 +1456,71 @@ impl<R: EuclideanDomain + PolynomialGCD<E>, E: PositiveExponent> MultivariatePol
         None
     }
 
+    /// Compute the gcd of two multivariate polynomials, while reporting
+    /// the milestones of the algorithm to `callback`.
+    pub fn gcd_with_progress(
+        &self,
+        b: &MultivariatePolynomial<R, E>,
+        callback: impl FnMut(Progress) + 'static,
+    ) -> MultivariatePolynomial<R, E> {
+        with_progress(callback, || self.gcd(b))
+    }
+
+    /// Compute the gcd `g` of two multivariate polynomials `a` and `b`, together
+    /// with the cofactors `a/g` and `b/g`. No division is performed
+    /// when the gcd is one or when it is equal to an input.
+    pub fn gcd_with_cofactors(
+        &self,
+        b: &MultivariatePolynomial<R, E>,
+    ) -> (
+        MultivariatePolynomial<R, E>,
+        MultivariatePolynomial<R, E>,
+        MultivariatePolynomial<R, E>,
+    ) {
+        let g = self.gcd(b);
+
+        if g.is_one() {
+            return (g, self.clone(), b.clone());
+        }
+
+        let cofactor = |p: &MultivariatePolynomial<R, E>| {
+            if *p == g {
+                p.one()
+            } else {
+                p / &g
+            }
+        };
+
+        let (ca, cb) = (cofactor(self), cofactor(b));
+        (g, ca, cb)
+    }
+
     /// Compute the gcd of two multivariate polynomials.
     #[instrument(skip_all)]
     pub fn gcd(&self, b: &MultivariatePolynomial<R, E>) -> MultivariatePolynomial<R, E> {
+        self.gcd_impl(b, None)
+    }
+
+    /// Compute the gcd of two multivariate polynomials, using `var` as the main
+    /// variable of the recursion if it is still present after the simplification steps.
+    /// By default, the main variable is the one with the highest estimated degree in the gcd.
+    ///
+    /// Picking the sparsest variable may speed up the computation, but the result
+    /// is the same for any choice.
+    #[instrument(skip_all)]
+    pub fn gcd_with_main_variable(
+        &self,
+        b: &MultivariatePolynomial<R, E>,
+        var: &Variable,
+    ) -> MultivariatePolynomial<R, E> {
+        self.gcd_impl(b, Some(var))
+    }
+
+    fn gcd_impl(
+        &self,
+        b: &MultivariatePolynomial<R, E>,
+        main_var: Option<&Variable>,
+    ) -> MultivariatePolynomial<R, E> {
         debug!("gcd of {} and {}", self, b);
 
         if let Some(g) = self.simple_gcd(b) {
@@ -1753,6 +1815,15 @@ impl<R: EuclideanDomain + PolynomialGCD<E>, E: PositiveExponent> MultivariatePol
         // polynomials do not have to be sorted after filling in variables.
         vars.sort_by(|&i, &j| tight_bounds[j].cmp(&tight_bounds[i]));
 
+        if let Some(main_var) = main_var {
+            if let Some(p) = a.get_vars_ref().iter().position(|v| v == main_var) {
+                if let Some(pos) = vars.iter().position(|v| *v == p) {
+                    let v = vars.remove(pos);
+                    vars.insert(0, v);
+                }
+            }
+        }
+
         // strip the gcd of the univariate contents wrt the new first variable
         let content = if vars.len() > 1 {
             debug!("Starting univariate content computation in {}", vars[0]);
@@ -1924,6 +1995,7 @@ impl<E: PositiveExponent> MultivariatePolynomial<IntegerRing, E> {
 
             for retry in 0..6 {
                 debug!("round {}, xi={}", retry, xi);
+                report_progress(Progress::HeuristicRound(retry));
                 match &xi * &Integer::Natural(a.degree(var).max(b.degree(var)).to_u32() as i64) {
                     Integer::Natural(_) => {}
                     Integer::Double(_) => {}
@@ -2141,6 +2213,7 @@ impl<E: PositiveExponent> MultivariatePolynomial<IntegerRing, E> {
                     self, b
                 );
             };
+            report_progress(Progress::Prime(p));
             let Some(p) = UField::try_from_integer(p.into()) else {
                 panic!(
                     "Ran out of primes for gcd reconstruction.\ngcd({},{})",
@@ -2254,6 +2327,7 @@ impl<E: PositiveExponent> MultivariatePolynomial<IntegerRing, E> {
                             self, b, gm, vars, bounds, tight_bounds
                         );
                     };
+                    report_progress(Progress::Prime(p));
                     let Some(p) = UField::try_from_integer(p.into()) else {
                         panic!(
                             "Ran out of primes for gcd images.\ngcd({},{})\nAttempt: {}\n vars: {:?}, bounds: {:?}; {:?}",
@@ -1,6 +1,7 @@
 //! Multivariate polynomial structures and methods.
 
 use ahash::{HashMap, HashMapExt};
+use rand::Rng;
 use std::cell::{Cell, UnsafeCell};
 use std::cmp::{Ordering, Reverse};
 use std::collections::{BTreeMap, BinaryHeap};
@@ -10,10 +11,16 @@ use std::mem;
 use std::ops::{Add, Div, Mul, Neg, Sub};
 use std::sync::Arc;
 
+use crate::coefficient::ConvertToRing;
 use crate::domains::algebraic_number::AlgebraicExtension;
-use crate::domains::integer::{Integer, IntegerRing};
-use crate::domains::rational::{RationalField, Q};
-use crate::domains::{Derivable, EuclideanDomain, Field, InternalOrdering, Ring, SelfRing};
+use crate::domains::finite_field::{FiniteField, FiniteFieldCore, FiniteFieldWorkspace};
+use crate::domains::integer::{Integer, IntegerRing, Z};
+use crate::domains::modular::ZMod;
+use crate::domains::rational::{Rational, RationalField, Q};
+use crate::domains::{
+    Derivable, EuclideanDomain, Field, InternalOrdering, Ring, RingHomomorphism, RingPrinter,
+    SelfRing,
+};
 use crate::printer::{PrintOptions, PrintState};
 
 use super::gcd::PolynomialGCD;
@@ -241,6 +248,45 @@ impl<F: Ring, E: Exponent, O: MonomialOrder> MultivariatePolynomial<F, E, O> {
         }
     }
 
+    /// Constructs a random polynomial with at most `n_terms` terms, where every variable
+    /// has an exponent of at most `max_degree` in each term. The coefficients are
+    /// sampled from the ring in the range `[-100, 100)`.
+    ///
+    /// Use a seeded `rng` to get a reproducible polynomial.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use std::sync::Arc;
+    /// use rand::SeedableRng;
+    /// use rand_xoshiro::Xoshiro256StarStar;
+    /// use symbolica::{domains::integer::Z, poly::polynomial::MultivariatePolynomial, symbol};
+    ///
+    /// let vars = Arc::new(vec![symbol!("x").into(), symbol!("y").into()]);
+    /// let mut rng = Xoshiro256StarStar::seed_from_u64(1);
+    /// let p = MultivariatePolynomial::<_, u8>::random(&Z, vars, 5, 3, &mut rng);
+    /// assert!(p.nterms() <= 5);
+    /// ```
+    pub fn random(
+        ring: &F,
+        variables: Arc<Vec<Variable>>,
+        n_terms: usize,
+        max_degree: E,
+        rng: &mut impl rand::RngCore,
+    ) -> Self {
+        let mut res = Self::new(ring, Some(n_terms), variables);
+        let mut exp = vec![E::zero(); res.nvars()];
+        for _ in 0..n_terms {
+            for e in &mut exp {
+                *e = E::from_i32(rng.gen_range(0..=max_degree.to_i32()));
+            }
+
+            res.append_monomial(ring.sample(rng, (-100, 100)), &exp);
+        }
+
+        res
+    }
+
     /// Constructs a zero polynomial, inheriting the field and variable map from `self`.
     #[inline]
     pub fn zero(&self) -> Self {
@@ -456,6 +502,11 @@ impl<F: Ring, E: Exponent, O: MonomialOrder> MultivariatePolynomial<F, E, O> {
         self.variables.as_ref()
     }
 
+    /// Get the names of the variables, in the order of the variables list.
+    pub fn variable_names(&self) -> Vec<String> {
+        self.variables.iter().map(|v| v.to_string()).collect()
+    }
+
     /// Rename a variable.
     pub fn rename_variable(&mut self, old: &Variable, new: &Variable) {
         if let Some(pos) = self.variables.iter().position(|v| v == old) {
@@ -718,6 +769,174 @@ impl<F: Ring, E: Exponent, O: MonomialOrder> MultivariatePolynomial<F, E, O> {
         let i = l * self.nvars();
         self.exponents.splice(i..i, exponents.iter().cloned());
     }
+
+    /// Append a list of monomials to the polynomial. All monomials are added
+    /// first and the terms are sorted and merged once at the end, which is much faster
+    /// than repeated calls to [Self::append_monomial] for many unsorted terms.
+    pub fn append_monomials<V: AsRef<[E]>, I: IntoIterator<Item = (F::Element, V)>>(
+        &mut self,
+        terms: I,
+    ) {
+        let nvars = self.nvars();
+        let old_len = self.nterms();
+
+        for (coefficient, exponents) in terms {
+            let exponents = exponents.as_ref();
+            if nvars != exponents.len() {
+                panic!(
+                    "nvars mismatched: got {}, expected {}",
+                    exponents.len(),
+                    nvars
+                );
+            }
+
+            if !self.ring.is_zero(&coefficient) {
+                self.coefficients.push(coefficient);
+                self.exponents.extend_from_slice(exponents);
+            }
+        }
+
+        if self.nterms() == old_len {
+            return;
+        }
+
+        let mut order: Vec<usize> = (0..self.nterms()).collect();
+        order.sort_by(|a, b| O::cmp(self.exponents(*a), self.exponents(*b)));
+
+        let mut old_coefficients: Vec<_> = std::mem::take(&mut self.coefficients)
+            .into_iter()
+            .map(Some)
+            .collect();
+        let mut coefficients: Vec<F::Element> = Vec::with_capacity(order.len());
+        let mut exponents = Vec::with_capacity(self.exponents.len());
+
+        for i in order {
+            let c = old_coefficients[i].take().unwrap();
+            let e = &self.exponents[i * nvars..(i + 1) * nvars];
+
+            if !coefficients.is_empty() && &exponents[exponents.len() - nvars..] == e {
+                self.ring.add_assign(coefficients.last_mut().unwrap(), &c);
+                continue;
+            }
+
+            // remove the previous term if it cancelled out
+            if coefficients.last().is_some_and(|c| self.ring.is_zero(c)) {
+                coefficients.pop();
+                exponents.truncate(exponents.len() - nvars);
+            }
+
+            coefficients.push(c);
+            exponents.extend_from_slice(e);
+        }
+
+        if coefficients.last().is_some_and(|c| self.ring.is_zero(c)) {
+            coefficients.pop();
+            exponents.truncate(exponents.len() - nvars);
+        }
+
+        self.coefficients = coefficients;
+        self.exponents = exponents;
+    }
+}
+
+impl<F: ConvertToRing, E: Exponent, O: MonomialOrder> MultivariatePolynomial<F, E, O> {
+    /// Read a polynomial in a sparse line-based format, where every line
+    /// contains a rational coefficient followed by the exponent of each of the variables
+    /// in `var_map`, separated by whitespace. Empty lines are skipped. An error is
+    /// returned when a coefficient is not an element of `field`, such as `1/2` in the integers.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use std::sync::Arc;
+    /// use symbolica::{domains::integer::Z, poly::polynomial::MultivariatePolynomial, symbol};
+    /// let vars = Arc::new(vec![symbol!("x").into(), symbol!("y").into()]);
+    /// let p = MultivariatePolynomial::<_, u8>::from_reader("3 2 0\n-1 0 1\n".as_bytes(), &Z, vars)
+    ///     .unwrap();
+    /// assert_eq!(p.nterms(), 2);
+    /// ```
+    pub fn from_reader<R: std::io::BufRead>(
+        reader: R,
+        field: &F,
+        var_map: Arc<Vec<Variable>>,
+    ) -> Result<Self, String> {
+        let mut poly = Self::new(field, None, var_map);
+        let mut exponents = vec![E::zero(); poly.nvars()];
+
+        for (line_number, line) in reader.lines().enumerate() {
+            let line = line.map_err(|e| e.to_string())?;
+            let mut parts = line.split_whitespace();
+
+            let Some(coeff) = parts.next() else {
+                continue;
+            };
+
+            let (num, den) = coeff.split_once('/').unwrap_or((coeff, "1"));
+            let (Ok(num), Ok(den)) = (num.parse::<Integer>(), den.parse::<Integer>()) else {
+                return Err(format!(
+                    "Invalid coefficient {} on line {}",
+                    coeff,
+                    line_number + 1
+                ));
+            };
+
+            // the division fails when the coefficient is not in the ring, for example
+            // 1/2 in the integers
+            let Some(c) = field.try_div(
+                &field.element_from_integer(num),
+                &field.element_from_integer(den),
+            ) else {
+                return Err(format!(
+                    "Coefficient {} on line {} is not an element of {}",
+                    coeff,
+                    line_number + 1,
+                    field
+                ));
+            };
+
+            for e in &mut exponents {
+                let exp = parts
+                    .next()
+                    .and_then(|x| x.parse::<i32>().ok())
+                    .and_then(|x| E::try_from(x).ok())
+                    .ok_or_else(|| {
+                        format!("Missing or invalid exponent on line {}", line_number + 1)
+                    })?;
+                *e = exp;
+            }
+
+            if parts.next().is_some() {
+                return Err(format!("Too many exponents on line {}", line_number + 1));
+            }
+
+            poly.append_monomial(c, &exponents);
+        }
+
+        Ok(poly)
+    }
+}
+
+impl<F: Ring, E: Exponent, O: MonomialOrder> MultivariatePolynomial<F, E, O> {
+    /// Write the polynomial in the sparse line-based format that can be read
+    /// with [MultivariatePolynomial::from_reader]: one line per monomial, containing
+    /// the coefficient followed by the exponents. The coefficients are printed
+    /// without any decorations, such as the modulus of finite field elements.
+    pub fn to_writer<W: std::io::Write>(&self, writer: &mut W) -> Result<(), std::io::Error> {
+        for monomial in self {
+            let coeff = RingPrinter {
+                ring: &self.ring,
+                element: monomial.coefficient,
+                opts: PrintOptions::file(),
+                state: PrintState::new(),
+            };
+            write!(writer, "{}", coeff)?;
+            for e in monomial.exponents {
+                write!(writer, " {}", e)?;
+            }
+            writeln!(writer)?;
+        }
+        Ok(())
+    }
 }
 
 impl<F: Ring, E: Exponent, O: MonomialOrder> SelfRing for MultivariatePolynomial<F, E, O> {
@@ -1172,6 +1391,55 @@ impl<'a, F: Ring, E: Exponent> Mul<&'a MultivariatePolynomial<F, E, LexOrder>>
     }
 }
 
+#[cfg(feature = "parallel_mul")]
+impl<F: Ring + Send + Sync, E: Exponent + Send + Sync> MultivariatePolynomial<F, E, LexOrder>
+where
+    F::Element: Send + Sync,
+{
+    /// Multiply two polynomials in parallel. The terms of the largest polynomial
+    /// are partitioned over the threads of the current rayon thread pool, and the
+    /// partial products are merged afterwards.
+    pub fn mul_parallel(&self, rhs: &Self) -> Self {
+        use rayon::prelude::*;
+
+        if self.nterms() < rhs.nterms() {
+            return rhs.mul_parallel(self);
+        }
+
+        if self.variables != rhs.variables {
+            let mut c1 = self.clone();
+            let mut c2 = rhs.clone();
+            c1.unify_variables(&mut c2);
+            return c1.mul_parallel(&c2);
+        }
+
+        let n_threads = rayon::current_num_threads();
+        if n_threads == 1 || self.nterms() < 2 * n_threads || rhs.nterms() == 1 {
+            return self * rhs;
+        }
+
+        let chunk_size = self.nterms().div_ceil(n_threads);
+        let nvars = self.nvars();
+
+        self.coefficients
+            .par_chunks(chunk_size)
+            .zip(self.exponents.par_chunks(chunk_size * nvars))
+            .map(|(coefficients, exponents)| {
+                let part = MultivariatePolynomial {
+                    coefficients: coefficients.to_vec(),
+                    exponents: exponents.to_vec(),
+                    ring: self.ring.clone(),
+                    variables: self.variables.clone(),
+                    _phantom: PhantomData,
+                };
+
+                &part * rhs
+            })
+            .reduce_with(|a, b| a + b)
+            .unwrap()
+    }
+}
+
 impl<'a, 'b, F: EuclideanDomain, E: PositiveExponent>
     Div<&'a MultivariatePolynomial<F, E, LexOrder>> for &'b MultivariatePolynomial<F, E, LexOrder>
 {
@@ -1242,6 +1510,26 @@ impl<F: Ring, E: Exponent, O: MonomialOrder> MultivariatePolynomial<F, E, O> {
         self
     }
 
+    /// Convert the polynomial to one with exponent type `E2`.
+    /// Returns an error if an exponent does not fit in `E2`.
+    pub fn convert_exponents<E2: Exponent>(
+        &self,
+    ) -> Result<MultivariatePolynomial<F, E2, O>, &'static str> {
+        let exponents = self
+            .exponents
+            .iter()
+            .map(|e| E2::try_from(e.to_i32()).map_err(|_| "Exponent does not fit in target type"))
+            .collect::<Result<Vec<_>, _>>()?;
+
+        Ok(MultivariatePolynomial {
+            coefficients: self.coefficients.clone(),
+            exponents,
+            ring: self.ring.clone(),
+            variables: self.variables.clone(),
+            _phantom: PhantomData,
+        })
+    }
+
     /// Map a coefficient using the function `f`.
     pub fn map_coeff<U: Ring, T: Fn(&F::Element) -> U::Element>(
         &self,
@@ -1268,6 +1556,39 @@ impl<F: Ring, E: Exponent, O: MonomialOrder> MultivariatePolynomial<F, E, O> {
         }
     }
 
+    /// Map every coefficient to the ring `target` using the ring homomorphism `hom`.
+    ///
+    /// In debug mode, it is verified that `hom` maps one to one and
+    /// preserves the sum and product of consecutive coefficients.
+    pub fn apply_ring_hom<R2: Ring, H: RingHomomorphism<F, R2>>(
+        &self,
+        hom: H,
+        target: R2,
+    ) -> MultivariatePolynomial<R2, E, O> {
+        if cfg!(debug_assertions) {
+            assert!(
+                target.is_one(&hom.apply(&self.ring.one())),
+                "Ring homomorphism does not map one to one"
+            );
+
+            for c in self.coefficients.windows(2) {
+                let (a, b) = (hom.apply(&c[0]), hom.apply(&c[1]));
+                assert_eq!(
+                    hom.apply(&self.ring.add(&c[0], &c[1])),
+                    target.add(&a, &b),
+                    "Ring homomorphism does not preserve addition"
+                );
+                assert_eq!(
+                    hom.apply(&self.ring.mul(&c[0], &c[1])),
+                    target.mul(&a, &b),
+                    "Ring homomorphism does not preserve multiplication"
+                );
+            }
+        }
+
+        self.map_coeff(|c| hom.apply(c), target)
+    }
+
     /// Add `exponents` to every exponent.
     pub fn mul_exp(mut self, exponents: &[E]) -> Self {
         debug_assert_eq!(self.nvars(), exponents.len());
@@ -1325,6 +1646,43 @@ impl<F: Ring, E: Exponent, O: MonomialOrder> MultivariatePolynomial<F, E, O> {
         false
     }
 
+    /// Get the variables that appear with a non-zero exponent in at least one term.
+    pub fn used_variables(&self) -> Vec<Variable> {
+        self.variables
+            .iter()
+            .enumerate()
+            .filter(|(i, _)| self.contains(*i))
+            .map(|(_, v)| v.clone())
+            .collect()
+    }
+
+    /// Remove all variables that do not appear in the polynomial from the variable list.
+    pub fn drop_unused_variables(&mut self) {
+        let used: Vec<_> = (0..self.nvars()).map(|i| self.contains(i)).collect();
+        if used.iter().all(|u| *u) {
+            return;
+        }
+
+        // removing zero exponents does not change the monomial order
+        let nvars = self.nvars();
+        let mut exponents = Vec::with_capacity(self.nterms() * used.len());
+        for (i, e) in self.exponents.iter().enumerate() {
+            if used[i % nvars] {
+                exponents.push(*e);
+            }
+        }
+        self.exponents = exponents;
+
+        self.variables = Arc::new(
+            self.variables
+                .iter()
+                .zip(&used)
+                .filter(|(_, u)| **u)
+                .map(|(v, _)| v.clone())
+                .collect(),
+        );
+    }
+
     /// Get the degree of the variable `x`.
     /// This operation is O(n).
     pub fn degree(&self, x: usize) -> E {
@@ -1361,6 +1719,21 @@ impl<F: Ring, E: Exponent, O: MonomialOrder> MultivariatePolynomial<F, E, O> {
         (min.unwrap_or(E::zero()), max.unwrap_or(E::zero()))
     }
 
+    /// Get the degree of every variable, computed in a single pass over the exponents.
+    /// This operation is O(n).
+    pub fn degree_profile(&self) -> Vec<(Variable, E)> {
+        let mut max = vec![E::zero(); self.nvars()];
+        for e in self.exponents_iter() {
+            for (m, e) in max.iter_mut().zip(e) {
+                if *m < *e {
+                    *m = *e;
+                }
+            }
+        }
+
+        self.variables.iter().cloned().zip(max).collect()
+    }
+
     // Get the highest degree of a variable in the leading monomial.
     pub fn ldegree(&self, v: usize) -> E {
         if self.is_zero() {
@@ -1550,6 +1923,82 @@ impl<F: Ring, E: PositiveExponent> MultivariatePolynomial<F, E, LexOrder> {
         res
     }
 
+    /// Evaluate the polynomial at the point `values`, using a multivariate Horner scheme.
+    ///
+    /// # Panics
+    ///
+    /// Panics if a variable that appears in the polynomial has no value in `values`.
+    pub fn evaluate(&self, values: &HashMap<Variable, F::Element>) -> F::Element {
+        let r: Vec<_> = self
+            .variables
+            .iter()
+            .enumerate()
+            .map(|(i, v)| {
+                if let Some(x) = values.get(v) {
+                    x.clone()
+                } else if self.contains(i) {
+                    panic!("No value provided for variable {}", v)
+                } else {
+                    self.ring.zero()
+                }
+            })
+            .collect();
+
+        if self.is_zero() {
+            return self.ring.zero();
+        }
+
+        self.horner(&r, 0, 0, self.nterms())
+    }
+
+    /// Evaluate the terms `start..end`, that share the exponents of
+    /// all variables before `var`, using Horner's scheme in `var`.
+    fn horner(&self, r: &[F::Element], var: usize, start: usize, end: usize) -> F::Element {
+        if var == self.nvars() {
+            return self.coefficients[start].clone();
+        }
+
+        let mut acc = self.ring.zero();
+        let mut last_exp: Option<E> = None;
+        let mut i = end;
+        while i > start {
+            let e = self.exponents(i - 1)[var];
+            let mut j = i - 1;
+            while j > start && self.exponents(j - 1)[var] == e {
+                j -= 1;
+            }
+
+            if let Some(l) = last_exp {
+                self.ring
+                    .mul_assign(&mut acc, &self.ring.pow(&r[var], (l - e).to_i32() as u64));
+            }
+            self.ring
+                .add_assign(&mut acc, &self.horner(r, var + 1, j, i));
+
+            last_exp = Some(e);
+            i = j;
+        }
+
+        if let Some(l) = last_exp {
+            if l > E::zero() {
+                self.ring
+                    .mul_assign(&mut acc, &self.ring.pow(&r[var], l.to_i32() as u64));
+            }
+        }
+
+        acc
+    }
+
+    /// Evaluate the variable `var` at `value`, yielding a polynomial in the
+    /// remaining variables. The variable stays in the variable map.
+    /// If `var` is not in the variable map, the polynomial is returned unchanged.
+    pub fn evaluate_one(&self, var: &Variable, value: &F::Element) -> Self {
+        match self.variables.iter().position(|v| v == var) {
+            Some(n) => self.replace(n, value),
+            None => self.clone(),
+        }
+    }
+
     /// Replace a variable `n` in the polynomial by a polynomial `v`.
     pub fn replace_with_poly(&self, n: usize, v: &Self) -> Self {
         assert_eq!(self.variables, v.variables);
@@ -1850,6 +2299,19 @@ impl<F: Ring, E: Exponent> MultivariatePolynomial<F, E, LexOrder> {
         self.rearrange_impl(order, inverse, true)
     }
 
+    /// Format the polynomial with the variables displayed in the order `order`,
+    /// where `order[i]` is the index of the variable that is printed at position `i`.
+    /// The polynomial itself is not modified.
+    pub fn format_with_order<W: std::fmt::Write>(
+        &self,
+        order: &[usize],
+        opts: &PrintOptions,
+        state: PrintState,
+        f: &mut W,
+    ) -> Result<bool, std::fmt::Error> {
+        self.rearrange(order, false).format(opts, state, f)
+    }
+
     /// Change the order of the variables in the polynomial, using `order`.
     /// The order may contain `None`, to signal unmapped indices. This operation
     /// allows the polynomial to grow in size.
@@ -3764,6 +4226,73 @@ impl<F: Field, E: PositiveExponent, O: MonomialOrder> MultivariatePolynomial<F,
 }
 
 impl<F: Field, E: PositiveExponent> MultivariatePolynomial<F, E, LexOrder> {
+    /// Construct the univariate polynomial in `var` of degree smaller than `points.len()`
+    /// that passes through all `(x, y)` in `points`, using Newton's divided differences.
+    /// The `x` values must be distinct.
+    ///
+    /// If a `degree` bound is given, only the first `degree + 1` points are used for the
+    /// interpolation. In debug builds, the result is verified to pass through the
+    /// remaining points.
+    pub fn interpolate_univariate(
+        points: &[(F::Element, F::Element)],
+        degree: Option<usize>,
+        field: &F,
+        var: Variable,
+    ) -> Self {
+        let (points, extra_points) = points.split_at(
+            degree
+                .map(|d| (d + 1).min(points.len()))
+                .unwrap_or(points.len()),
+        );
+
+        let mut res = Self::new(field, Some(points.len()), Arc::new(vec![var]));
+        if points.is_empty() {
+            return res;
+        }
+
+        let mut diffs: Vec<_> = points.iter().map(|(_, y)| y.clone()).collect();
+        for k in 1..points.len() {
+            for i in (k..points.len()).rev() {
+                let num = field.sub(&diffs[i], &diffs[i - 1]);
+                let den = field.sub(&points[i].0, &points[i - k].0);
+                debug_assert!(
+                    !field.is_zero(&den),
+                    "Interpolation points must be distinct"
+                );
+                diffs[i] = field.div(&num, &den);
+            }
+        }
+
+        // convert from the Newton basis to the monomial basis using Horner's scheme
+        let mut coeffs = vec![diffs.pop().unwrap()];
+        for ((x, _), d) in points[..diffs.len()].iter().zip(diffs.iter()).rev() {
+            // multiply by (var - x) and add d
+            coeffs.push(field.zero());
+            for j in (1..coeffs.len()).rev() {
+                let t = field.mul(x, &coeffs[j]);
+                coeffs[j] = field.sub(&coeffs[j - 1], &t);
+            }
+            let t = field.mul(x, &coeffs[0]);
+            coeffs[0] = field.sub(d, &t);
+        }
+
+        for (i, c) in coeffs.into_iter().enumerate() {
+            if !field.is_zero(&c) {
+                res.append_monomial(c, &[E::from_i32(i as i32)]);
+            }
+        }
+
+        for (x, y) in extra_points {
+            debug_assert_eq!(
+                &res.replace_all(std::slice::from_ref(x)),
+                y,
+                "Inconsistent interpolation points"
+            );
+        }
+
+        res
+    }
+
     /// Optimized division routine for univariate polynomials over a field, which
     /// makes the divisor monic first.
     pub fn quot_rem_univariate(
@@ -3881,6 +4410,47 @@ impl<F: Field, E: PositiveExponent> MultivariatePolynomial<F, E, LexOrder> {
         (r0, s0, t0)
     }
 
+    /// Compute `(g, s, t)` where `g` is the monic gcd of `self` and `other`
+    /// and `self * s + other * t = g`. Both polynomials must be univariate in the
+    /// variable with index `var`.
+    pub fn extended_gcd(&self, other: &Self, var: usize) -> (Self, Self, Self) {
+        for p in [self, other] {
+            if p.exponents_iter()
+                .any(|e| e.iter().enumerate().any(|(i, x)| i != var && !x.is_zero()))
+            {
+                panic!("Polynomial is not univariate in {}", self.variables[var]);
+            }
+        }
+
+        if other.is_zero() {
+            if self.is_zero() {
+                return (self.zero(), self.zero(), self.zero());
+            }
+
+            let inv = self.ring.inv(&self.lcoeff());
+            return (self.clone().make_monic(), self.constant(inv), self.zero());
+        }
+
+        if self.is_zero() {
+            let inv = self.ring.inv(&other.lcoeff());
+            return (other.clone().make_monic(), self.zero(), self.constant(inv));
+        }
+
+        self.eea_univariate(other)
+    }
+
+    /// Compute the inverse of `self` modulo `modulus`, where both polynomials are
+    /// univariate in the variable with index `var`. Returns `None` if the
+    /// polynomials are not coprime.
+    pub fn inverse_mod(&self, modulus: &Self, var: usize) -> Option<Self> {
+        let (g, s, _) = self.extended_gcd(modulus, var);
+        if !g.is_one() {
+            return None;
+        }
+
+        Some(s.quot_rem_univariate(&mut modulus.clone()).1)
+    }
+
     /// Compute `(s1,...,n2)` where `A0 * s0 + ... + An * sn = g`
     /// where `Ai = prod(polys[j], j != i)`
     /// by means of the extended Euclidean algorithm.
@@ -4037,6 +4607,157 @@ impl<R: EuclideanDomain, E: Exponent> MultivariatePolynomial<AlgebraicExtension<
     }
 }
 
+impl<E: Exponent, O: MonomialOrder> MultivariatePolynomial<IntegerRing, E, O> {
+    /// Get the bit length of the largest coefficient in absolute value.
+    /// This can be used to decide which algorithm to use, such as a modular GCD.
+    pub fn max_coefficient_bits(&self) -> u64 {
+        self.coefficients
+            .iter()
+            .map(|c| c.bit_length())
+            .max()
+            .unwrap_or(0)
+    }
+}
+
+impl<E: Exponent, O: MonomialOrder> MultivariatePolynomial<RationalField, E, O> {
+    /// Get the bit length of the largest numerator or denominator of the coefficients.
+    pub fn max_coefficient_bits(&self) -> u64 {
+        self.coefficients
+            .iter()
+            .map(|c| {
+                c.numerator_ref()
+                    .bit_length()
+                    .max(c.denominator_ref().bit_length())
+            })
+            .max()
+            .unwrap_or(0)
+    }
+}
+
+impl<E: Exponent> MultivariatePolynomial<ZMod, E> {
+    /// Combine images of a polynomial over distinct prime fields into a polynomial
+    /// modulo the product of the primes, using the Chinese remainder theorem.
+    /// Monomials are matched by their exponent vector; a monomial that is absent
+    /// in an image has coefficient zero there.
+    ///
+    /// All images must have the same variable map and the product of the primes
+    /// must fit in a `u64`.
+    pub fn crt_combine<UField: FiniteFieldWorkspace + Into<u64>>(
+        polys: &[MultivariatePolynomial<FiniteField<UField>, E>],
+    ) -> Self
+    where
+        FiniteField<UField>: FiniteFieldCore<UField>,
+    {
+        assert!(!polys.is_empty(), "At least one image is required");
+
+        let primes: Vec<u64> = polys.iter().map(|p| p.ring.get_prime().into()).collect();
+        let modulus = primes
+            .iter()
+            .try_fold(1u64, |acc, p| acc.checked_mul(*p))
+            .expect("The product of the primes does not fit in a u64");
+
+        let mut coeffs: BTreeMap<&[E], Vec<u64>> = BTreeMap::new();
+        for (i, p) in polys.iter().enumerate() {
+            assert_eq!(
+                p.variables, polys[0].variables,
+                "All images must have the same variable map"
+            );
+
+            for t in p {
+                coeffs
+                    .entry(t.exponents)
+                    .or_insert_with(|| vec![0; polys.len()])[i] =
+                    p.ring.from_element(t.coefficient).into();
+            }
+        }
+
+        let mut res = MultivariatePolynomial::new(
+            &ZMod::new(modulus),
+            Some(coeffs.len()),
+            polys[0].variables.clone(),
+        );
+        for (exp, c) in coeffs {
+            // Garner's algorithm: x stays below m, so x + m * d < m * p
+            let (mut x, mut m) = (c[0], primes[0]);
+            for (y, p) in c[1..].iter().zip(&primes[1..]) {
+                let field = ZMod::new(*p);
+                let inv = field
+                    .try_inv(&field.to_element(m))
+                    .expect("The primes must be distinct");
+                let d = field.mul(&field.sub(y, &field.to_element(x)), &inv);
+                x += m * d;
+                m *= p;
+            }
+
+            if x != 0 {
+                res.append_monomial(x, exp);
+            }
+        }
+
+        res
+    }
+
+    /// Reconstruct a polynomial over the rationals from its image modulo `n`.
+    /// Every coefficient `c` is mapped to the unique fraction `a/b` with
+    /// `a = b*c mod n` and `|a|, b <= sqrt(n/2)`, if it exists, which is found
+    /// with a bounded continued fraction expansion of `c/n`.
+    ///
+    /// If the modulus is too small compared to the numerators and denominators of
+    /// the true coefficients, `None` is returned or, worse, a different fraction that
+    /// happens to satisfy the bound. The latter cannot be detected from a single image,
+    /// so the result should be verified, for example by checking that it is unchanged
+    /// after combining with an image over an additional prime using [Self::crt_combine].
+    pub fn rational_reconstruct(&self) -> Option<MultivariatePolynomial<RationalField, E>> {
+        let n = self.ring.get_modulus();
+
+        let mut bound = ((n / 2) as f64).sqrt() as u64;
+        while bound as u128 * bound as u128 > (n / 2) as u128 {
+            bound -= 1;
+        }
+        while (bound + 1) as u128 * (bound + 1) as u128 <= (n / 2) as u128 {
+            bound += 1;
+        }
+
+        let coefficients = self
+            .coefficients
+            .iter()
+            .map(|c| {
+                let (mut r0, mut r1) = (n as i128, *c as i128);
+                let (mut t0, mut t1) = (0i128, 1i128);
+                while r1 > bound as i128 {
+                    let q = r0 / r1;
+                    (r0, r1) = (r1, r0 - q * r1);
+                    (t0, t1) = (t1, t0 - q * t1);
+                }
+
+                if t1.unsigned_abs() > bound as u128 {
+                    return None;
+                }
+
+                let (num, den) = if t1 < 0 {
+                    (Integer::from(-r1 as i64), Integer::from(-t1 as i64))
+                } else {
+                    (Integer::from(r1 as i64), Integer::from(t1 as i64))
+                };
+
+                if !Z.gcd(&num, &den).is_one() {
+                    return None;
+                }
+
+                Some(Rational::from((num, den)))
+            })
+            .collect::<Option<Vec<_>>>()?;
+
+        Some(MultivariatePolynomial {
+            coefficients,
+            exponents: self.exponents.clone(),
+            ring: Q,
+            variables: self.variables.clone(),
+            _phantom: PhantomData,
+        })
+    }
+}
+
 impl<E: Exponent> From<&MultivariatePolynomial<IntegerRing, E>>
     for MultivariatePolynomial<RationalField, E>
 {
@@ -4099,7 +4820,24 @@ impl<'a, F: Ring, E: Exponent, O: MonomialOrder> IntoIterator
 
 #[cfg(test)]
 mod test {
-    use crate::{atom::AtomCore, domains::integer::Z, parse, symbol};
+    use std::sync::Arc;
+
+    use ahash::HashMap;
+    use rand::SeedableRng;
+    use rand_xoshiro::Xoshiro256StarStar;
+
+    use crate::{
+        atom::{Atom, AtomCore, AtomView},
+        domains::{
+            finite_field::{FiniteFieldCore, Zp},
+            integer::{Integer, Z},
+            modular::ZMod,
+            rational::{Rational, Q},
+        },
+        parse,
+        poly::{polynomial::MultivariatePolynomial, GrevLexOrder, Variable},
+        symbol,
+    };
 
     #[test]
     fn mul_packed() {
@@ -4114,6 +4852,531 @@ mod test {
         assert_eq!(b.to_expression(), r)
     }
 
+    #[test]
+    fn variable_names() {
+        let vars: Arc<Vec<Variable>> = Arc::new(vec![symbol!("x").into(), symbol!("y").into()]);
+        let p = parse!("x^2+x*y")
+            .unwrap()
+            .to_polynomial::<_, u8>(&Z, Some(vars));
+        assert_eq!(p.variable_names(), ["x", "y"]);
+    }
+
+    #[test]
+    fn to_expression_minimal() {
+        let p = parse!("v1^2+v2").unwrap().to_polynomial::<_, u8>(&Z, None);
+        let zero = &p - &p;
+        assert_eq!(zero.to_expression(), Atom::new_num(0));
+
+        let p = parse!("3*v1^2*v2")
+            .unwrap()
+            .to_polynomial::<_, u8>(&Z, None);
+        let e = p.to_expression();
+        assert!(matches!(e.as_view(), AtomView::Mul(_)));
+        assert_eq!(e, parse!("3*v1^2*v2").unwrap());
+
+        let p = parse!("v1").unwrap().to_polynomial::<_, u8>(&Z, None);
+        assert!(matches!(p.to_expression().as_view(), AtomView::Var(_)));
+    }
+
+    #[test]
+    fn constant_to_expression() {
+        let p =
+            MultivariatePolynomial::<_, u8>::new(&Z, None, Arc::new(vec![Variable::Temporary(0)]))
+                .constant(5.into());
+        assert_eq!(p.to_expression(), Atom::new_num(5));
+    }
+
+    #[test]
+    fn convert_exponents() {
+        let p = parse!("v1^200*v2+3*v2^5")
+            .unwrap()
+            .to_polynomial::<_, u8>(&Z, None);
+        let p32 = p.convert_exponents::<u32>().unwrap();
+        assert_eq!(p32.to_expression(), p.to_expression());
+        assert_eq!(p32.convert_exponents::<u8>().unwrap(), p);
+
+        let p = parse!("v1^300").unwrap().to_polynomial::<_, u16>(&Z, None);
+        assert!(p.convert_exponents::<u8>().is_err());
+    }
+
+    #[test]
+    fn append_monomials() {
+        let vars: Arc<Vec<Variable>> = Arc::new(vec![symbol!("v1").into(), symbol!("v2").into()]);
+        let n: u32 = 20000;
+
+        // unsorted terms with duplicates, some of which cancel
+        let terms: Vec<_> = (0..n)
+            .map(|i| {
+                let j = (i * 7919) % n;
+                let c = if j % 3 == 0 { -1 } else { 1 };
+                (Integer::from(c), [j % 100, j / 100])
+            })
+            .chain(
+                (0..n)
+                    .filter(|j| j % 3 == 0)
+                    .map(|j| (Integer::from(1), [j % 100, j / 100])),
+            )
+            .collect();
+
+        let mut p1 = MultivariatePolynomial::<_, u32>::new(&Z, None, vars.clone());
+        for (c, e) in &terms {
+            p1.append_monomial(c.clone(), e);
+        }
+
+        let mut p2 = MultivariatePolynomial::<_, u32>::new(&Z, None, vars);
+        p2.append_monomials(terms);
+
+        assert_eq!(p1, p2);
+        assert_eq!(p2.nterms(), (n - n.div_ceil(3)) as usize);
+    }
+
+    #[test]
+    fn monomial_order() {
+        let vars: Arc<Vec<Variable>> = Arc::new(vec![symbol!("v1").into(), symbol!("v2").into()]);
+        let lex = parse!("v1*v2^3+v1^2+v2")
+            .unwrap()
+            .to_polynomial::<_, u8>(&Z, Some(vars));
+        let grevlex = lex.reorder::<GrevLexOrder>();
+
+        let lex_terms: Vec<_> = lex.into_iter().map(|t| t.exponents.to_vec()).collect();
+        let grevlex_terms: Vec<_> = grevlex.into_iter().map(|t| t.exponents.to_vec()).collect();
+        assert_eq!(lex_terms, [vec![0, 1], vec![1, 3], vec![2, 0]]);
+        assert_eq!(grevlex_terms, [vec![0, 1], vec![2, 0], vec![1, 3]]);
+
+        assert_eq!(lex.last_exponents(), [2, 0]);
+        assert_eq!(grevlex.last_exponents(), [1, 3]);
+    }
+
+    #[test]
+    fn max_coefficient_bits() {
+        let p = parse!("3*v1 - 2^100*v2 + 1")
+            .unwrap()
+            .to_polynomial::<_, u8>(&Z, None);
+        assert_eq!(p.max_coefficient_bits(), 101);
+
+        let p = parse!("v1/2^70 - 5")
+            .unwrap()
+            .to_polynomial::<_, u8>(&Q, None);
+        assert_eq!(p.max_coefficient_bits(), 71);
+
+        let p = parse!("-255*v1").unwrap().to_polynomial::<_, u8>(&Z, None);
+        assert_eq!(p.max_coefficient_bits(), 8);
+    }
+
+    #[test]
+    fn to_polynomial_in() {
+        let vars: Arc<Vec<Variable>> = Arc::new(vec![symbol!("v1").into()]);
+
+        let p = parse!("v1^2 + 3*v1")
+            .unwrap()
+            .to_polynomial_in::<_, u8>(&Z, &vars)
+            .unwrap();
+        assert_eq!(p.get_vars_ref(), vars.as_slice());
+
+        let err = parse!("v1^2 + v2*v1 + f1(v1) + 1")
+            .unwrap()
+            .to_polynomial_in::<_, u8>(&Z, &vars)
+            .unwrap_err();
+        assert_eq!(err.len(), 2);
+        assert!(err.contains(&symbol!("v2").into()));
+        assert!(err
+            .iter()
+            .any(|v| matches!(v, Variable::Function(f, _) if *f == symbol!("f1"))));
+    }
+
+    #[test]
+    fn apply_ring_hom() {
+        let p = parse!("3*v1^2 - 5*v1*v2 + 7")
+            .unwrap()
+            .to_polynomial::<_, u8>(&Z, None);
+
+        let r = p.apply_ring_hom(|c: &Integer| Rational::from(c), Q);
+        assert_eq!(
+            r,
+            parse!("3*v1^2 - 5*v1*v2 + 7")
+                .unwrap()
+                .to_polynomial::<_, u8>(&Q, p.variables.clone())
+        );
+    }
+
+    #[test]
+    fn trivial_powers() {
+        let x = Atom::new_var(symbol!("v1"));
+        let x_plus_one = parse!("v1+1").unwrap();
+
+        let mut a = Atom::new();
+        a.to_pow(x.as_view(), Atom::new_num(0).as_view());
+        let p = a.to_polynomial::<_, u8>(&Z, None);
+        assert_eq!(p.nvars(), 0);
+        assert!(p.is_one());
+
+        a.to_pow(x_plus_one.as_view(), Atom::new_num(0).as_view());
+        let p = a.to_polynomial::<_, u8>(&Z, None);
+        assert_eq!(p.nvars(), 0);
+        assert!(p.is_one());
+
+        a.to_pow(x.as_view(), Atom::new_num(1).as_view());
+        let p = a.to_polynomial::<_, u8>(&Z, None);
+        assert_eq!(p, x.to_polynomial(&Z, None));
+
+        a.to_pow(x_plus_one.as_view(), Atom::new_num(1).as_view());
+        let p = a.to_polynomial::<_, u8>(&Z, None);
+        assert_eq!(p, x_plus_one.to_polynomial(&Z, None));
+    }
+
+    #[test]
+    fn try_to_polynomial() {
+        let p = parse!("v1^2*v2 + 3*v1").unwrap();
+        let r = p.try_to_polynomial::<_, u8>(&Z, None).unwrap();
+        assert_eq!(r, p.to_polynomial::<_, u8>(&Z, None));
+
+        // the exponent used to overflow the exponent type with a panic
+        let p = parse!("v1^300").unwrap();
+        assert!(p.try_to_polynomial::<_, u8>(&Z, None).is_err());
+        assert!(p.try_to_polynomial::<_, u16>(&Z, None).is_ok());
+
+        let p = parse!("(v1+1)^2").unwrap();
+        assert!(p.try_to_polynomial::<_, u8>(&Z, None).is_err());
+    }
+
+    #[test]
+    fn to_expression_generic() {
+        let vars: Arc<Vec<Variable>> = Arc::new(vec![symbol!("v1").into(), symbol!("v2").into()]);
+        let e = parse!("3/4*v1^200*v2 - 5*v2^3 + 1/2").unwrap();
+
+        let p = e.to_polynomial::<_, u8>(&Q, Some(vars.clone()));
+        assert_eq!(p.to_expression(), e);
+        assert_eq!(
+            p.to_expression()
+                .to_polynomial::<_, u8>(&Q, Some(vars.clone())),
+            p
+        );
+
+        // finite field elements are mapped to their representative
+        let field = Zp::new(7);
+        let p = parse!("3*v1^2 + 9*v2")
+            .unwrap()
+            .to_polynomial::<_, u8>(&field, Some(vars));
+        let r = p.to_expression_with_coeff_map(|f, c, out| {
+            *out = Atom::new_num(f.from_element(c) as i64);
+        });
+        assert_eq!(r, parse!("3*v1^2 + 2*v2").unwrap());
+    }
+
+    #[test]
+    fn drop_unused_variables() {
+        let vars: Arc<Vec<Variable>> = Arc::new(vec![
+            symbol!("v1").into(),
+            symbol!("v2").into(),
+            symbol!("v3").into(),
+        ]);
+        let p = parse!("v1^2*v2 + v2*v3^3 + v3")
+            .unwrap()
+            .to_polynomial::<_, u8>(&Z, Some(vars.clone()));
+
+        let mut r = p.replace(1, &Integer::zero());
+        assert_eq!(r.nvars(), 3);
+        assert_eq!(r.used_variables(), vec![Variable::from(symbol!("v3"))]);
+
+        r.drop_unused_variables();
+        assert_eq!(r.nvars(), 1);
+        assert_eq!(r.to_expression(), parse!("v3").unwrap());
+
+        let mut p2 = p.clone();
+        p2.drop_unused_variables();
+        assert_eq!(p2, p);
+    }
+
+    #[test]
+    fn degree_profile() {
+        let vars: Arc<Vec<Variable>> = Arc::new(vec![symbol!("v1").into(), symbol!("v2").into()]);
+        let p = parse!("v1^3*v2 + v2^2")
+            .unwrap()
+            .to_polynomial::<_, u8>(&Z, Some(vars));
+
+        assert_eq!(
+            p.degree_profile(),
+            vec![
+                (Variable::from(symbol!("v1")), 3),
+                (Variable::from(symbol!("v2")), 2)
+            ]
+        );
+    }
+
+    #[test]
+    fn crt_rational_reconstruct() {
+        let vars: Arc<Vec<Variable>> = Arc::new(vec![symbol!("v1").into(), symbol!("v2").into()]);
+        let a = parse!("1/3*v1^2 - 5/7*v2 + 2").unwrap();
+        let p = a.to_polynomial::<_, u8>(&Q, Some(vars.clone()));
+
+        let images: Vec<_> = [999983, 1000003]
+            .into_iter()
+            .map(|prime| a.to_polynomial::<_, u8>(&Zp::new(prime), Some(vars.clone())))
+            .collect();
+
+        let r = MultivariatePolynomial::crt_combine(&images);
+        assert_eq!(r.ring, ZMod::new(999983 * 1000003));
+        assert_eq!(r.rational_reconstruct(), Some(p.clone()));
+
+        // the modulus is too small to reconstruct 5/7
+        let small = MultivariatePolynomial::crt_combine(&[
+            a.to_polynomial::<_, u8>(&Zp::new(31), Some(vars))
+        ]);
+        assert_ne!(small.rational_reconstruct(), Some(p));
+    }
+
+    #[test]
+    fn sparse_format() {
+        let vars: Arc<Vec<Variable>> = Arc::new(vec![symbol!("v1").into(), symbol!("v2").into()]);
+        let p = parse!("3/4*v1^2*v2 - 5*v2^3 + 1/2")
+            .unwrap()
+            .to_polynomial::<_, u8>(&Q, Some(vars.clone()));
+
+        let mut buf = vec![];
+        p.to_writer(&mut buf).unwrap();
+        let r =
+            MultivariatePolynomial::<_, u8>::from_reader(buf.as_slice(), &Q, vars.clone()).unwrap();
+        assert_eq!(r, p);
+
+        let r = MultivariatePolynomial::<_, u8>::from_reader(
+            "2 1 0\n\n-3 0 2\n".as_bytes(),
+            &Z,
+            vars.clone(),
+        )
+        .unwrap();
+        assert_eq!(r.to_expression(), parse!("2*v1-3*v2^2").unwrap());
+
+        assert!(
+            MultivariatePolynomial::<_, u8>::from_reader("2 1\n".as_bytes(), &Z, vars.clone())
+                .is_err()
+        );
+        assert!(MultivariatePolynomial::<_, u8>::from_reader(
+            "x 1 2\n".as_bytes(),
+            &Z,
+            vars.clone()
+        )
+        .is_err());
+        assert!(MultivariatePolynomial::<_, u8>::from_reader(
+            "1/2 1 0\n".as_bytes(),
+            &Z,
+            vars.clone()
+        )
+        .is_err());
+
+        let field = Zp::new(7);
+        assert!(MultivariatePolynomial::<_, u8>::from_reader(
+            "1/7 1 0\n".as_bytes(),
+            &field,
+            vars.clone()
+        )
+        .is_err());
+
+        // 1/2 = 4 mod 7, and the modulus is not written
+        let r = MultivariatePolynomial::<_, u8>::from_reader("1/2 1 0\n".as_bytes(), &field, vars)
+            .unwrap();
+        buf.clear();
+        r.to_writer(&mut buf).unwrap();
+        assert_eq!(buf, b"4 1 0\n");
+    }
+
+    #[test]
+    fn laurent_exponents() {
+        let vars: Arc<Vec<Variable>> = Arc::new(vec![symbol!("v1").into(), symbol!("v2").into()]);
+
+        let p = parse!("v1^-2*v2^3 + v1^3 + 1").unwrap();
+        let r = p
+            .try_to_polynomial::<_, i32>(&Z, Some(vars.clone()))
+            .unwrap();
+        assert_eq!(r.nterms(), 3);
+        assert!(!r.is_polynomial());
+        assert_eq!(r.to_expression(), p);
+
+        // unsigned exponents cannot represent x^-2
+        assert!(p
+            .try_to_polynomial::<_, u32>(&Z, Some(vars.clone()))
+            .is_err());
+
+        // x^-2*x^2 must merge with the constant term
+        let a = parse!("v1^-2 + 1")
+            .unwrap()
+            .to_polynomial::<_, i32>(&Z, Some(vars.clone()));
+        let b = parse!("v1^2")
+            .unwrap()
+            .to_polynomial::<_, i32>(&Z, Some(vars.clone()));
+        let r = parse!("v1^2 + 1")
+            .unwrap()
+            .to_polynomial::<_, i32>(&Z, Some(vars));
+        assert_eq!(&a * &b, r);
+    }
+
+    #[test]
+    fn gcd_with_cofactors() {
+        let vars: Arc<Vec<Variable>> = Arc::new(vec![symbol!("v1").into(), symbol!("v2").into()]);
+        let a = parse!("(v1+v2)^2*(v1-3)")
+            .unwrap()
+            .expand()
+            .to_polynomial::<_, u8>(&Z, Some(vars.clone()));
+        let b = parse!("(v1+v2)*(2*v2+1)")
+            .unwrap()
+            .expand()
+            .to_polynomial::<_, u8>(&Z, Some(vars.clone()));
+
+        let (g, ca, cb) = a.gcd_with_cofactors(&b);
+        assert_eq!(g, a.gcd(&b));
+        assert_eq!(&g * &ca, a);
+        assert_eq!(&g * &cb, b);
+
+        let (g, ca, cb) = g.gcd_with_cofactors(&a);
+        assert!(ca.is_one());
+        assert_eq!(&g * &cb, a);
+    }
+
+    #[test]
+    fn random() {
+        let vars: Arc<Vec<Variable>> = Arc::new(vec![symbol!("v1").into(), symbol!("v2").into()]);
+
+        let mut rng = Xoshiro256StarStar::seed_from_u64(42);
+        let p1 = MultivariatePolynomial::<_, u8>::random(&Z, vars.clone(), 10, 4, &mut rng);
+        let mut rng = Xoshiro256StarStar::seed_from_u64(42);
+        let p2 = MultivariatePolynomial::<_, u8>::random(&Z, vars.clone(), 10, 4, &mut rng);
+
+        assert_eq!(p1, p2);
+        assert!(p1.nterms() <= 10);
+        assert!(p1.degree(0) <= 4 && p1.degree(1) <= 4);
+    }
+
+    #[test]
+    fn interpolate_univariate() {
+        let x: Variable = symbol!("v1").into();
+        let points: Vec<(Rational, Rational)> = [(1, 1), (2, 4), (-3, 9), (4, 16)]
+            .into_iter()
+            .map(|(x, y)| (x.into(), y.into()))
+            .collect();
+
+        let p = MultivariatePolynomial::<_, u8>::interpolate_univariate(
+            &points[..3],
+            None,
+            &Q,
+            x.clone(),
+        );
+        assert_eq!(p.to_expression(), parse!("v1^2").unwrap());
+
+        let p2 = MultivariatePolynomial::<_, u8>::interpolate_univariate(
+            &points,
+            Some(2),
+            &Q,
+            x.clone(),
+        );
+        assert_eq!(p2, p);
+
+        let p3 = MultivariatePolynomial::<_, u8>::interpolate_univariate(&points[..2], None, &Q, x);
+        assert_eq!(p3.to_expression(), parse!("3*v1 - 2").unwrap());
+    }
+
+    #[test]
+    #[cfg(debug_assertions)]
+    #[should_panic(expected = "Inconsistent interpolation points")]
+    fn interpolate_univariate_inconsistent() {
+        let x: Variable = symbol!("v1").into();
+        let points: Vec<(Rational, Rational)> = [(1, 1), (2, 4), (-3, 9), (4, 17)]
+            .into_iter()
+            .map(|(x, y)| (x.into(), y.into()))
+            .collect();
+
+        MultivariatePolynomial::<_, u8>::interpolate_univariate(&points, Some(2), &Q, x);
+    }
+
+    #[test]
+    fn evaluate() {
+        let vars: Arc<Vec<Variable>> = Arc::new(vec![
+            symbol!("v1").into(),
+            symbol!("v2").into(),
+            symbol!("v3").into(),
+        ]);
+        let p = parse!("3*v1^3*v2 + v1^3 - 2*v1*v2^2*v3 + v2 + 7")
+            .unwrap()
+            .to_polynomial::<_, u8>(&Z, Some(vars.clone()));
+
+        let values: HashMap<_, _> = vars
+            .iter()
+            .cloned()
+            .zip([2.into(), (-1).into(), 5.into()])
+            .collect();
+        assert_eq!(p.evaluate(&values), Integer::from(-30));
+        assert_eq!(
+            p.evaluate(&values),
+            p.replace_all(&[2.into(), (-1).into(), 5.into()])
+        );
+
+        let r = p.evaluate_one(&vars[0], &2.into());
+        assert_eq!(r.to_expression(), parse!("25*v2 - 4*v2^2*v3 + 15").unwrap());
+    }
+
+    #[test]
+    fn gcd_with_main_variable() {
+        let vars: Arc<Vec<Variable>> = Arc::new(vec![
+            symbol!("v1").into(),
+            symbol!("v2").into(),
+            symbol!("v3").into(),
+        ]);
+        let a = parse!("(v1^2*v3+v2^2+v3^2+1)*(v1^2+v2^2*v3^2+2)")
+            .unwrap()
+            .expand()
+            .to_polynomial::<_, u8>(&Z, Some(vars.clone()));
+        let b = parse!("(v1^2*v3+v2^2+v3^2+1)*(v1*v2^2+v3^2-1)")
+            .unwrap()
+            .expand()
+            .to_polynomial::<_, u8>(&Z, Some(vars.clone()));
+
+        let g = a.gcd(&b);
+        assert_eq!(g.nterms(), 4);
+        for v in vars.iter() {
+            assert_eq!(a.gcd_with_main_variable(&b, v), g);
+        }
+    }
+
+    #[test]
+    fn extended_gcd() {
+        let vars: Arc<Vec<Variable>> = Arc::new(vec![symbol!("v1").into(), symbol!("v2").into()]);
+        let a = parse!("v2^2-1")
+            .unwrap()
+            .to_polynomial::<_, u8>(&Q, Some(vars.clone()));
+        let b = parse!("v2-1")
+            .unwrap()
+            .to_polynomial::<_, u8>(&Q, Some(vars.clone()));
+
+        let (g, s, t) = a.extended_gcd(&b, 1);
+        assert_eq!(g, b);
+        assert_eq!(&(&s * &a) + &(&t * &b), g);
+
+        let c = parse!("2*v2^3+v2+5")
+            .unwrap()
+            .to_polynomial::<_, u8>(&Q, Some(vars));
+        let (g, s, t) = a.extended_gcd(&c, 1);
+        assert!(g.is_one());
+        assert_eq!(&(&s * &a) + &(&t * &c), g);
+    }
+
+    #[test]
+    fn inverse_mod() {
+        let vars: Arc<Vec<Variable>> = Arc::new(vec![symbol!("v1").into()]);
+        let x = parse!("v1")
+            .unwrap()
+            .to_polynomial::<_, u8>(&Q, Some(vars.clone()));
+        let m = parse!("v1^2+1")
+            .unwrap()
+            .to_polynomial::<_, u8>(&Q, Some(vars.clone()));
+
+        let inv = x.inverse_mod(&m, 0).unwrap();
+        assert_eq!(inv, -x.clone());
+        assert!((&x * &inv).quot_rem_univariate(&mut m.clone()).1.is_one());
+
+        let m = parse!("v1^2+v1")
+            .unwrap()
+            .to_polynomial::<_, u8>(&Q, Some(vars));
+        assert!(x.inverse_mod(&m, 0).is_none());
+    }
+
     #[test]
     fn mul_full() {
         let p1 = parse!("v1^2+v2^3*v3*+3*v1^4+4*v2*v3+v4+v5+v6*v1*v2+v7*v5+v8+v9*v8")
@@ -4128,6 +5391,27 @@ mod test {
         assert_eq!(b.to_expression(), r)
     }
 
+    #[cfg(feature = "parallel_mul")]
+    #[test]
+    fn mul_parallel() {
+        let p1 = parse!("(v1+v2^2*v3+3*v4+v5*v6+7)^4")
+            .unwrap()
+            .expand()
+            .to_polynomial::<_, u8>(&Z, None);
+        let p2 = parse!("(v1*v4+v2-2*v3+v6^2*v5+1)^3")
+            .unwrap()
+            .expand()
+            .to_polynomial::<_, u8>(&Z, p1.variables.clone());
+
+        let pool = rayon::ThreadPoolBuilder::new()
+            .num_threads(4)
+            .build()
+            .unwrap();
+        let r = pool.install(|| p1.mul_parallel(&p2));
+
+        assert_eq!(r, &p1 * &p2);
+    }
+
     #[test]
     fn div_packed() {
         let p1 = parse!("(v1+v2*5+v3*v2+v1*v2*v3)(v1+v2+v3)")
@@ -1,6 +1,11 @@
+use std::sync::Arc;
+
 use crate::domains::{EuclideanDomain, Field};
 
-use super::univariate::UnivariatePolynomial;
+use super::{
+    gcd::PolynomialGCD, polynomial::MultivariatePolynomial, univariate::UnivariatePolynomial,
+    PositiveExponent,
+};
 
 impl<F: EuclideanDomain> UnivariatePolynomial<F> {
     /// Compute the resultant using Brown's polynomial remainder sequence algorithm.
@@ -202,6 +207,65 @@ impl<F: Field> UnivariatePolynomial<F> {
     }
 }
 
+impl<F: EuclideanDomain + PolynomialGCD<E>, E: PositiveExponent> MultivariatePolynomial<F, E> {
+    /// Compute the resultant of two polynomials with respect to the variable `var`.
+    /// The variable is removed from the variable map of the result.
+    pub fn resultant(&self, other: &Self, var: usize) -> Self {
+        if self.variables != other.variables {
+            let mut c1 = self.clone();
+            let mut c2 = other.clone();
+            c1.unify_variables(&mut c2);
+            return c1.resultant(&c2, var);
+        }
+
+        self.resultant_in_var(other, var).remove_variable(var)
+    }
+
+    /// Compute the discriminant of the polynomial with respect to the variable `var`.
+    /// The variable is removed from the variable map of the result.
+    pub fn discriminant(&self, var: usize) -> Self {
+        let d = self.degree(var).to_i32() as usize;
+        if d == 0 {
+            return self.one().remove_variable(var);
+        }
+
+        let r = self.resultant_in_var(&self.derivative(var), var);
+        let mut disc = r
+            .try_div(&self.univariate_lcoeff(var))
+            .expect("Leading coefficient does not divide the resultant");
+
+        if (d * (d - 1) / 2) % 2 == 1 {
+            disc = -disc;
+        }
+
+        disc.remove_variable(var)
+    }
+
+    /// Compute the resultant in `var`, keeping the variable map.
+    fn resultant_in_var(&self, other: &Self, var: usize) -> Self {
+        let a = self.to_univariate(var);
+        let b = other.to_univariate(var);
+        a.resultant_prs(&b)
+    }
+
+    /// Remove the variable `var` that does not appear in the polynomial from the variable map.
+    fn remove_variable(&self, var: usize) -> Self {
+        debug_assert!(self.degree(var).is_zero());
+
+        let mut vars = self.variables.as_ref().clone();
+        vars.remove(var);
+
+        let mut res = MultivariatePolynomial::new(&self.ring, Some(self.nterms()), Arc::new(vars));
+        let mut exp = vec![E::zero(); self.nvars() - 1];
+        for t in self {
+            exp[..var].copy_from_slice(&t.exponents[..var]);
+            exp[var..].copy_from_slice(&t.exponents[var + 1..]);
+            res.append_monomial(t.coefficient.clone(), &exp);
+        }
+        res
+    }
+}
+
 #[cfg(test)]
 mod test {
     use std::sync::Arc;
@@ -257,6 +321,34 @@ mod test {
         assert_eq!(prim, res);
     }
 
+    #[test]
+    fn eliminate_variable() {
+        let vars = Arc::new(vec![symbol!("v1").into(), symbol!("v2").into()]);
+        let a = parse!("v1-v2")
+            .unwrap()
+            .to_polynomial::<_, u8>(&Z, Some(vars.clone()));
+        let b = parse!("v1^2-v2")
+            .unwrap()
+            .to_polynomial::<_, u8>(&Z, Some(vars.clone()));
+
+        let r = a.resultant(&b, 1);
+        assert_eq!(r.nvars(), 1);
+        assert_eq!(r.to_expression(), parse!("v1-v1^2").unwrap());
+
+        // the resultant with a polynomial that is constant in the variable
+        let c = parse!("v1+1")
+            .unwrap()
+            .to_polynomial::<_, u8>(&Z, Some(vars.clone()));
+        let r = b.resultant(&c, 1);
+        assert_eq!(r.to_expression(), parse!("v1+1").unwrap());
+
+        let d = parse!("v1^2+v2*v1+1")
+            .unwrap()
+            .to_polynomial::<_, u8>(&Z, Some(vars))
+            .discriminant(0);
+        assert_eq!(d.to_expression(), parse!("v2^2-4").unwrap());
+    }
+
     #[test]
     fn resultant_prs_large() {
         let system = [
@@ -5,9 +5,12 @@ use std::fmt::{self, Error, Write};
 use colored::Colorize;
 
 use crate::{
-    atom::{representation::FunView, AddView, AtomView, MulView, NumView, PowView, VarView},
+    atom::{
+        representation::FunView, AddView, Atom, AtomView, MulView, NumView, PowView, Symbol,
+        VarView,
+    },
     coefficient::CoefficientView,
-    domains::{finite_field::FiniteFieldCore, SelfRing},
+    domains::{finite_field::FiniteFieldCore, rational::Rational, SelfRing},
     state::State,
 };
 
@@ -36,6 +39,11 @@ pub struct PrintOptions {
     pub hide_all_namespaces: bool,
     pub color_namespace: bool,
     pub max_terms: Option<usize>,
+    /// Print derivatives `der(1,0,f(x,y))` as `f^(1,0)(x,y)`. The output cannot be parsed back.
+    pub pretty_derivatives: bool,
+    /// Separate all factors by `\cdot` in LaTeX output, instead of only
+    /// when juxtaposition is ambiguous.
+    pub latex_cdot: bool,
 }
 
 impl PrintOptions {
@@ -59,6 +67,8 @@ impl PrintOptions {
             hide_all_namespaces: true,
             color_namespace: true,
             max_terms: None,
+            pretty_derivatives: false,
+            latex_cdot: false,
         }
     }
 
@@ -83,6 +93,8 @@ impl PrintOptions {
             hide_all_namespaces: true,
             color_namespace: false,
             max_terms: None,
+            pretty_derivatives: false,
+            latex_cdot: false,
         }
     }
 
@@ -107,6 +119,8 @@ impl PrintOptions {
             hide_all_namespaces: true,
             color_namespace: false,
             max_terms: None,
+            pretty_derivatives: false,
+            latex_cdot: false,
         }
     }
 
@@ -131,6 +145,8 @@ impl PrintOptions {
             hide_all_namespaces: false,
             color_namespace: false,
             max_terms: None,
+            pretty_derivatives: false,
+            latex_cdot: false,
         }
     }
 
@@ -360,6 +376,105 @@ impl<'a> AtomView<'a> {
         s
     }
 
+    /// Print the atom in Mathematica's `FullForm`, e.g. `Times[Rational[1, 2], Power[x, 2]]`.
+    /// Namespaces are dropped, and characters in symbol names that are not allowed by
+    /// Mathematica are replaced by `$`. Builtin functions and constants are mapped
+    /// to their Mathematica counterparts.
+    ///
+    /// Only output is supported: there is no parser for the `FullForm` syntax.
+    pub(crate) fn to_mathematica(&self) -> String {
+        let mut s = String::new();
+        self.to_mathematica_impl(&mut s);
+        s
+    }
+
+    fn to_mathematica_impl(&self, out: &mut String) {
+        fn write_symbol(s: Symbol, out: &mut String) {
+            let name = match s {
+                Atom::E => "E",
+                Atom::I => "I",
+                Atom::PI => "Pi",
+                Atom::EXP => "Exp",
+                Atom::LOG => "Log",
+                Atom::SIN => "Sin",
+                Atom::COS => "Cos",
+                Atom::TAN => "Tan",
+                Atom::SINH => "Sinh",
+                Atom::COSH => "Cosh",
+                Atom::TANH => "Tanh",
+                Atom::ASIN => "ArcSin",
+                Atom::ACOS => "ArcCos",
+                Atom::ATAN => "ArcTan",
+                Atom::SQRT => "Sqrt",
+                Atom::MAX => "Max",
+                Atom::MIN => "Min",
+                _ => {
+                    let name = s.get_stripped_name();
+                    if name.starts_with(|c: char| c.is_ascii_digit()) {
+                        out.push('$');
+                    }
+                    out.extend(
+                        name.chars()
+                            .map(|c| if c.is_ascii_alphanumeric() { c } else { '$' }),
+                    );
+                    return;
+                }
+            };
+            out.push_str(name);
+        }
+
+        fn write_head<'a>(head: &str, args: impl Iterator<Item = AtomView<'a>>, out: &mut String) {
+            out.push_str(head);
+            out.push('[');
+            for (i, a) in args.enumerate() {
+                if i > 0 {
+                    out.push_str(", ");
+                }
+                a.to_mathematica_impl(out);
+            }
+            out.push(']');
+        }
+
+        match self {
+            AtomView::Num(n) => match n.get_coeff_view() {
+                CoefficientView::Natural(n, 1) => write!(out, "{}", n).unwrap(),
+                CoefficientView::Natural(n, d) => write!(out, "Rational[{}, {}]", n, d).unwrap(),
+                CoefficientView::Large(r) => {
+                    let r = r.to_rat();
+                    if r.is_integer() {
+                        write!(out, "{}", r.numerator_ref()).unwrap()
+                    } else {
+                        write!(
+                            out,
+                            "Rational[{}, {}]",
+                            r.numerator_ref(),
+                            r.denominator_ref()
+                        )
+                        .unwrap()
+                    }
+                }
+                _ => write!(out, "{}", self.printer(PrintOptions::mathematica())).unwrap(),
+            },
+            AtomView::Var(v) => write_symbol(v.get_symbol(), out),
+            AtomView::Fun(f) if f.get_symbol() == Atom::ATAN2 && f.get_nargs() == 2 => {
+                // Mathematica's ArcTan[x, y] takes the arguments of atan2(y, x) in reverse
+                let mut args = f.iter();
+                let (y, x) = (args.next().unwrap(), args.next().unwrap());
+                write_head("ArcTan", [x, y].into_iter(), out);
+            }
+            AtomView::Fun(f) => {
+                write_symbol(f.get_symbol(), out);
+                write_head("", f.iter(), out);
+            }
+            AtomView::Pow(p) => {
+                let (b, e) = p.get_base_exp();
+                write_head("Power", [b, e].into_iter(), out);
+            }
+            AtomView::Mul(m) => write_head("Times", m.iter(), out),
+            AtomView::Add(a) => write_head("Plus", a.iter(), out),
+        }
+    }
+
     fn to_canonical_view_impl(&self, out: &mut String) {
         fn add_paren(cur: AtomView, s: AtomView) -> bool {
             if let AtomView::Pow(_) = cur {
@@ -676,6 +791,20 @@ impl<'a> FormattedPrintNum for NumView<'a> {
     }
 }
 
+/// Check if `x` is a power with a negative numerical exponent.
+fn has_negative_exponent(x: AtomView) -> bool {
+    if let AtomView::Pow(p) = x {
+        if let AtomView::Num(n) = p.get_exp() {
+            return match n.get_coeff_view() {
+                CoefficientView::Natural(n, _) => n < 0,
+                CoefficientView::Large(r) => r.is_negative(),
+                _ => false,
+            };
+        }
+    }
+    false
+}
+
 impl<'a> FormattedPrintMul for MulView<'a> {
     fn fmt_debug(&self, f: &mut fmt::Formatter) -> fmt::Result {
         <Self as std::fmt::Debug>::fmt(self, f)
@@ -698,6 +827,63 @@ impl<'a> FormattedPrintMul for MulView<'a> {
             print_state.in_exp = false;
         }
 
+        // write x*y^-1 as \frac{x}{y}
+        if opts.latex && self.iter().any(has_negative_exponent) {
+            let mut num = Atom::new_num(1);
+            let mut den = Atom::new_num(1);
+            let mut negative = false;
+            for x in self.iter() {
+                match x {
+                    AtomView::Num(n) => {
+                        let r = match n.get_coeff_view() {
+                            CoefficientView::Natural(a, b) => Rational::from((a, b)),
+                            CoefficientView::Large(r) => r.to_rat(),
+                            _ => {
+                                num = num * x;
+                                continue;
+                            }
+                        };
+
+                        negative = r.is_negative();
+                        num = num * Atom::new_num(r.numerator().abs());
+                        den = den * Atom::new_num(r.denominator());
+                    }
+                    _ if has_negative_exponent(x) => den = den / x,
+                    _ => num = num * x,
+                }
+            }
+
+            if negative {
+                if print_state.top_level_add_child && opts.color_top_level_sum {
+                    f.write_fmt(format_args!("{}", "-".yellow()))?;
+                } else {
+                    f.write_char('-')?;
+                }
+            } else if print_state.in_sum {
+                if print_state.top_level_add_child && opts.color_top_level_sum {
+                    f.write_fmt(format_args!("{}", "+".yellow()))?;
+                } else {
+                    f.write_char('+')?;
+                }
+            }
+
+            print_state.top_level_add_child = false;
+            print_state.level += 1;
+            print_state.in_sum = false;
+            print_state.suppress_one = false;
+
+            f.write_str("\\frac{")?;
+            num.as_view().format(f, opts, print_state)?;
+            f.write_str("}{")?;
+            den.as_view().format(f, opts, print_state)?;
+            f.write_char('}')?;
+
+            if add_paren {
+                f.write_char(')')?;
+            }
+            return Ok(false);
+        }
+
         // write the coefficient first
         let mut first = true;
         let mut skip_num = false;
@@ -735,7 +921,18 @@ impl<'a> FormattedPrintMul for MulView<'a> {
         }) {
             if !first {
                 if opts.latex {
-                    f.write_char(' ')?;
+                    // juxtaposition of a number is ambiguous, e.g. 2 3^{x}
+                    let starts_with_num = match x {
+                        AtomView::Num(_) => true,
+                        AtomView::Pow(p) => matches!(p.get_base(), AtomView::Num(_)),
+                        _ => false,
+                    };
+
+                    if opts.latex_cdot || starts_with_num {
+                        f.write_str(" \\cdot ")?;
+                    } else {
+                        f.write_char(' ')?;
+                    }
                 } else {
                     f.write_char(opts.multiplication_operator)?;
                 }
@@ -789,7 +986,96 @@ impl<'a> FormattedPrintFn for FunView<'a> {
             }
         }
 
-        id.format(opts, f)?;
+        print_state.top_level_add_child = false;
+        print_state.level += 1;
+        print_state.in_sum = false;
+        print_state.suppress_one = false;
+
+        if opts.latex {
+            if id == Atom::EXP && self.get_nargs() == 1 {
+                f.write_str("e^{")?;
+                self.iter().next().unwrap().format(f, opts, print_state)?;
+                f.write_char('}')?;
+                return Ok(false);
+            }
+
+            // print der(1,0,f(x,y)) as \frac{\partial}{\partial x} f(x,y)
+            if id == Atom::DERIVATIVE {
+                if let Some(AtomView::Fun(g)) = self.iter().last() {
+                    let orders: Option<Vec<i64>> = self
+                        .iter()
+                        .take(g.get_nargs())
+                        .map(|x| match x {
+                            AtomView::Num(n) => match n.get_coeff_view() {
+                                CoefficientView::Natural(o, 1) if o >= 0 => Some(o),
+                                _ => None,
+                            },
+                            _ => None,
+                        })
+                        .collect();
+
+                    if let Some(orders) = orders.filter(|o| {
+                        self.get_nargs() == g.get_nargs() + 1
+                            && g.iter()
+                                .zip(o)
+                                .all(|(a, o)| *o == 0 || matches!(a, AtomView::Var(_)))
+                    }) {
+                        let total: i64 = orders.iter().sum();
+                        if total == 1 {
+                            f.write_str("\\frac{\\partial}{")?;
+                        } else {
+                            f.write_fmt(format_args!("\\frac{{\\partial^{{{}}}}}{{", total))?;
+                        }
+
+                        let mut first = true;
+                        for (a, o) in g.iter().zip(&orders) {
+                            if *o == 0 {
+                                continue;
+                            }
+
+                            if !first {
+                                f.write_char(' ')?;
+                            }
+                            first = false;
+
+                            f.write_str("\\partial ")?;
+                            a.format(f, opts, print_state)?;
+                            if *o > 1 {
+                                f.write_fmt(format_args!("^{{{}}}", o))?;
+                            }
+                        }
+
+                        f.write_str("} ")?;
+                        return g.fmt_output(f, opts, print_state);
+                    }
+                }
+            }
+        }
+
+        // print der(1,0,f(x,y)) as f^(1,0)(x,y)
+        let mut fun = *self;
+        let mut pretty_der = false;
+        if opts.pretty_derivatives && !opts.latex && id == Atom::DERIVATIVE {
+            if let Some(AtomView::Fun(g)) = self.iter().last() {
+                if self.get_nargs() == g.get_nargs() + 1 {
+                    g.get_symbol().format(opts, f)?;
+                    f.write_str("^(")?;
+                    for (i, x) in self.iter().take(g.get_nargs()).enumerate() {
+                        if i > 0 {
+                            f.write_char(',')?;
+                        }
+                        x.format(f, opts, print_state)?;
+                    }
+                    f.write_char(')')?;
+                    fun = g;
+                    pretty_der = true;
+                }
+            }
+        }
+
+        if !pretty_der {
+            id.format(opts, f)?;
+        }
 
         if opts.latex {
             f.write_str("\\!\\left(")?;
@@ -801,12 +1087,8 @@ impl<'a> FormattedPrintFn for FunView<'a> {
             }
         }
 
-        print_state.top_level_add_child = false;
-        print_state.level += 1;
-        print_state.in_sum = false;
-        print_state.suppress_one = false;
         let mut first = true;
-        for x in self.iter() {
+        for x in fun.iter() {
             if !first {
                 f.write_char(',')?;
             }
@@ -856,14 +1138,13 @@ impl<'a> FormattedPrintPow for PowView<'a> {
 
         let mut superscript_exponent = false;
         if opts.latex {
-            if let AtomView::Num(n) = e {
-                if n.get_coeff_view() == CoefficientView::Natural(-1, 1) {
-                    // TODO: construct the numerator
-                    f.write_str("\\frac{1}{")?;
-                    b.format(f, opts, print_state)?;
-                    f.write_char('}')?;
-                    return Ok(false);
-                }
+            if has_negative_exponent(self.as_view()) {
+                f.write_str("\\frac{1}{")?;
+                (Atom::new_num(1) / self.as_view())
+                    .as_view()
+                    .format(f, opts, print_state)?;
+                f.write_char('}')?;
+                return Ok(false);
             }
         } else if opts.num_exp_as_superscript {
             if let AtomView::Num(n) = e {
@@ -871,22 +1152,25 @@ impl<'a> FormattedPrintPow for PowView<'a> {
             }
         }
 
-        let base_needs_parentheses =
-            matches!(b, AtomView::Add(_) | AtomView::Mul(_) | AtomView::Pow(_))
-                || if let AtomView::Num(n) = b {
-                    match n.get_coeff_view() {
-                        CoefficientView::Natural(n, d) => n < 0 || d != 1,
-                        CoefficientView::Float(_) => true, // TODO
-                        CoefficientView::Large(r) => r.is_negative() || !r.to_rat().is_integer(),
-                        CoefficientView::FiniteField(n, i) => {
-                            opts.symmetric_representation_for_finite_field
-                                && n.0 * 2 > State::get_finite_field(i).get_prime()
-                        }
-                        CoefficientView::RationalPolynomial(_) => true,
-                    }
-                } else {
-                    false
-                };
+        let base_needs_parentheses = matches!(
+            b,
+            AtomView::Add(_) | AtomView::Mul(_) | AtomView::Pow(_)
+        ) || if let AtomView::Num(n) = b {
+            match n.get_coeff_view() {
+                CoefficientView::Natural(n, d) => n < 0 || d != 1,
+                CoefficientView::Float(_) => true, // TODO
+                CoefficientView::Large(r) => r.is_negative() || !r.to_rat().is_integer(),
+                CoefficientView::FiniteField(n, i) => {
+                    opts.symmetric_representation_for_finite_field
+                        && n.0 * 2 > State::get_finite_field(i).get_prime()
+                }
+                CoefficientView::RationalPolynomial(_) => true,
+            }
+        } else {
+            // e^{x} cannot be raised to a power directly
+            opts.latex
+                && matches!(b, AtomView::Fun(f) if f.get_symbol() == Atom::EXP && f.get_nargs() == 1)
+        };
 
         if base_needs_parentheses {
             if opts.latex {
@@ -1012,16 +1296,37 @@ impl<'a> FormattedPrintAdd for AddView<'a> {
 
 #[cfg(test)]
 mod test {
+    use std::sync::Arc;
+
     use colored::control::ShouldColorize;
 
     use crate::{
         atom::{AtomCore, AtomView},
         domains::{finite_field::Zp, integer::Z, SelfRing},
         parse,
+        poly::GrevLexOrder,
         printer::{AtomPrinter, PrintOptions, PrintState},
         symbol,
     };
 
+    #[test]
+    fn derivatives() {
+        let a = parse!("f(x,y)").unwrap().derivative(symbol!("x"));
+        let opts = PrintOptions {
+            pretty_derivatives: true,
+            ..PrintOptions::file_no_namespace()
+        };
+
+        assert_eq!(format!("{}", a.printer(opts)), "f^(1,0)(x,y)");
+        assert_eq!(
+            format!("{}", a.printer(PrintOptions::file_no_namespace())),
+            "der(1,0,f(x,y))"
+        );
+
+        let a = a.derivative(symbol!("y"));
+        assert_eq!(format!("{}", a.printer(opts)), "f^(1,1)(x,y)");
+    }
+
     #[test]
     fn atoms() {
         let a = parse!("f(x,y^2)^(x+z)/5+3").unwrap();
@@ -1093,6 +1398,95 @@ mod test {
         assert_eq!(s, "-2*x^2 % 17");
     }
 
+    #[test]
+    fn polynomial_display_order() {
+        let vars = Arc::new(vec![symbol!("x").into(), symbol!("y").into()]);
+        let a = parse!("x+y+x*y").unwrap().to_polynomial::<_, u8>(&Z, vars);
+
+        let mut s = String::new();
+        a.format(
+            &PrintOptions::file_no_namespace(),
+            PrintState::new(),
+            &mut s,
+        )
+        .unwrap();
+        assert_eq!(s, "y+x+x*y");
+
+        let mut s = String::new();
+        a.format_with_order(
+            &[1, 0],
+            &PrintOptions::file_no_namespace(),
+            PrintState::new(),
+            &mut s,
+        )
+        .unwrap();
+        assert_eq!(s, "x+y+y*x");
+        assert_eq!(a.variable_names(), ["x", "y"]);
+    }
+
+    #[test]
+    fn polynomial_display_options() {
+        let vars = Arc::new(vec![symbol!("x").into(), symbol!("y").into()]);
+        let zero = parse!("0")
+            .unwrap()
+            .to_polynomial::<_, u8>(&Z, vars.clone());
+        assert_eq!(format!("{}", zero), "0");
+        let c = parse!("5")
+            .unwrap()
+            .to_polynomial::<_, u8>(&Z, vars.clone());
+        assert_eq!(format!("{}", c), "5");
+
+        let a = parse!("x^2+y^3").unwrap().to_polynomial::<_, u8>(&Z, vars);
+
+        let mut s = String::new();
+        a.format(
+            &PrintOptions {
+                double_star_for_exponentiation: true,
+                ..PrintOptions::file_no_namespace()
+            },
+            PrintState::new(),
+            &mut s,
+        )
+        .unwrap();
+        assert_eq!(s, "y**3+x**2");
+
+        let mut s = String::new();
+        a.reorder::<GrevLexOrder>()
+            .format(
+                &PrintOptions::file_no_namespace(),
+                PrintState::new(),
+                &mut s,
+            )
+            .unwrap();
+        assert_eq!(s, "x^2+y^3");
+    }
+
+    #[test]
+    fn mathematica() {
+        assert_eq!(parse!("-3/4").unwrap().to_mathematica(), "Rational[-3, 4]");
+        assert_eq!(
+            parse!("v1^(v2^-2)").unwrap().to_mathematica(),
+            "Power[v1, Power[v2, -2]]"
+        );
+        assert_eq!(
+            parse!("f1(v1, 𝑖, exp(1/2))").unwrap().to_mathematica(),
+            "f1[v1, I, Exp[Rational[1, 2]]]"
+        );
+        assert_eq!(
+            parse!("100000000000000000000000/3")
+                .unwrap()
+                .to_mathematica(),
+            "Rational[100000000000000000000000, 3]"
+        );
+
+        assert_eq!(parse!("2*v1").unwrap().to_mathematica(), "Times[v1, 2]");
+        assert_eq!(parse!("v1 + v2").unwrap().to_mathematica(), "Plus[v1, v2]");
+        assert_eq!(
+            parse!("atan2(v1, v2)").unwrap().to_mathematica(),
+            "ArcTan[v2, v1]"
+        );
+    }
+
     #[test]
     fn rational_polynomials() {
         let a = parse!("15 x^2 / (1+x)")
@@ -1191,4 +1585,49 @@ mod test {
         let s = format!("{}", e.printer(PrintOptions::latex()));
         assert_eq!(s, "\\mu^{2}+\\mu_{1}+\\mu_{1,2}");
     }
+
+    #[test]
+    fn latex() {
+        let opts = PrintOptions::latex();
+
+        let a = parse!("v1/2").unwrap();
+        assert_eq!(format!("{}", a.printer(opts)), "\\frac{1}{2} v1");
+
+        let a = parse!("v1/v2").unwrap();
+        assert_eq!(format!("{}", a.printer(opts)), "\\frac{v1}{v2}");
+
+        let a = parse!("-2/v1^2").unwrap();
+        assert_eq!(format!("{}", a.printer(opts)), "-\\frac{2}{v1^{2}}");
+
+        let a = parse!("v1^-1").unwrap();
+        assert_eq!(format!("{}", a.printer(opts)), "\\frac{1}{v1}");
+
+        let a = parse!("exp(v1)^2").unwrap();
+        assert_eq!(format!("{}", a.printer(opts)), "\\left(e^{v1}\\right)^{2}");
+
+        let a = parse!("sin(v1)").unwrap();
+        assert_eq!(format!("{}", a.printer(opts)), "\\sin\\!\\left(v1\\right)");
+
+        let a = parse!("f1(v1,v2)").unwrap().derivative(symbol!("v1"));
+        assert_eq!(
+            format!("{}", a.printer(opts)),
+            "\\frac{\\partial}{\\partial v1} f1\\!\\left(v1,v2\\right)"
+        );
+
+        let a = a.derivative(symbol!("v1")).derivative(symbol!("v2"));
+        assert_eq!(
+            format!("{}", a.printer(opts)),
+            "\\frac{\\partial^{3}}{\\partial v1^{2} \\partial v2} f1\\!\\left(v1,v2\\right)"
+        );
+
+        let a = parse!("v1*v2").unwrap();
+        let s = format!(
+            "{}",
+            a.printer(PrintOptions {
+                latex_cdot: true,
+                ..opts
+            })
+        );
+        assert_eq!(s, "v1 \\cdot v2");
+    }
 }
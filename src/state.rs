@@ -4,7 +4,7 @@ use byteorder::{ReadBytesExt, WriteBytesExt};
 use std::borrow::Cow;
 use std::hash::Hash;
 use std::io::{Read, Write};
-use std::sync::atomic::{AtomicUsize, Ordering};
+use std::sync::atomic::{AtomicBool, AtomicUsize, Ordering};
 use std::sync::{Arc, RwLock, RwLockWriteGuard};
 use std::thread::LocalKey;
 use std::{
@@ -19,8 +19,9 @@ use byteorder::LittleEndian;
 use once_cell::sync::Lazy;
 use smartstring::alias::String;
 
-use crate::atom::{FunctionAttribute, NamespacedSymbol, NormalizationFunction};
+use crate::atom::{AtomCore, FunctionAttribute, NamespacedSymbol, NormalizationFunction};
 use crate::domains::finite_field::Zp64;
+use crate::id::Pattern;
 use crate::poly::Variable;
 use crate::printer::PrintFunction;
 use crate::wrap_symbol;
@@ -55,20 +56,35 @@ impl StateMap {
     }
 }
 
+#[derive(Clone)]
 pub(crate) struct SymbolData {
     pub(crate) name: String,
     pub(crate) namespace: Cow<'static, str>,
     pub(crate) file: Cow<'static, str>,
     pub(crate) line: usize,
-    pub(crate) custom_normalization: Option<NormalizationFunction>,
-    pub(crate) custom_print: Option<PrintFunction>,
+    pub(crate) custom_normalization: Option<Arc<NormalizationFunction>>,
+    pub(crate) custom_print: Option<Arc<PrintFunction>>,
+}
+
+/// A snapshot of the symbols defined in the global state,
+/// created with [State::checkpoint] and restored with [State::restore].
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct StateCheckpoint {
+    symbol_count: usize,
 }
 
 static STATE: Lazy<RwLock<State>> = Lazy::new(|| RwLock::new(State::new()));
-static ID_TO_STR: AppendOnlyVec<(Symbol, SymbolData)> = AppendOnlyVec::new();
+/// The entries of all symbols that were ever defined. The table is append-only, so that
+/// references to the data of a symbol remain valid after it is removed by [State::restore].
+static SYMBOL_DATA: AppendOnlyVec<(Symbol, SymbolData)> = AppendOnlyVec::new();
+/// The position in [SYMBOL_DATA] of the entry of every symbol, shifted by [SYMBOL_OFFSET].
+/// Identifiers that are reused after [State::restore] point to a new entry.
+static ID_TO_STR: AppendOnlyVec<AtomicUsize> = AppendOnlyVec::new();
 static FINITE_FIELDS: AppendOnlyVec<Zp64> = AppendOnlyVec::new();
 static VARIABLE_LISTS: AppendOnlyVec<Arc<Vec<Variable>>> = AppendOnlyVec::new();
 static SYMBOL_OFFSET: AtomicUsize = AtomicUsize::new(0);
+static SYMBOL_COUNT: AtomicUsize = AtomicUsize::new(0);
+static HAS_DERIVATIVE_RULES: AtomicBool = AtomicBool::new(false);
 
 thread_local!(
     /// A thread-local workspace, that stores recyclable atoms.
@@ -78,6 +94,7 @@ thread_local!(
 /// A global state, that stores mappings from variable and function names to ids.
 pub struct State {
     str_to_id: HashMap<String, Symbol>,
+    derivative_rules: HashMap<(Symbol, usize), Arc<(Pattern, Pattern)>>,
 }
 
 impl Default for State {
@@ -98,10 +115,42 @@ impl State {
     pub(crate) const E: Symbol = Symbol::raw_var(8, 0);
     pub(crate) const I: Symbol = Symbol::raw_var(9, 0);
     pub(crate) const PI: Symbol = Symbol::raw_var(10, 0);
+    pub(crate) const ATAN2: Symbol = Symbol::raw_fn(11, 0, false, false, false, false);
+    pub(crate) const MAX: Symbol = Symbol::raw_fn(12, 0, false, false, false, false);
+    pub(crate) const MIN: Symbol = Symbol::raw_fn(13, 0, false, false, false, false);
+    pub(crate) const INTEGRATE: Symbol = Symbol::raw_fn(14, 0, false, false, false, false);
+    pub(crate) const TAN: Symbol = Symbol::raw_fn(15, 0, false, false, false, false);
+    pub(crate) const SINH: Symbol = Symbol::raw_fn(16, 0, false, false, false, false);
+    pub(crate) const COSH: Symbol = Symbol::raw_fn(17, 0, false, false, false, false);
+    pub(crate) const TANH: Symbol = Symbol::raw_fn(18, 0, false, false, false, false);
+    pub(crate) const ASIN: Symbol = Symbol::raw_fn(19, 0, false, false, false, false);
+    pub(crate) const ACOS: Symbol = Symbol::raw_fn(20, 0, false, false, false, false);
+    pub(crate) const ATAN: Symbol = Symbol::raw_fn(21, 0, false, false, false, false);
 
     /// The list of built-in symbols.
-    pub const BUILTIN_SYMBOL_NAMES: [&'static str; 11] = [
-        "arg", "coeff", "exp", "log", "sin", "cos", "sqrt", "der", "𝑒", "𝑖", "𝜋",
+    pub const BUILTIN_SYMBOL_NAMES: [&'static str; 22] = [
+        "arg",
+        "coeff",
+        "exp",
+        "log",
+        "sin",
+        "cos",
+        "sqrt",
+        "der",
+        "𝑒",
+        "𝑖",
+        "𝜋",
+        "atan2",
+        "max",
+        "min",
+        "integrate",
+        "tan",
+        "sinh",
+        "cosh",
+        "tanh",
+        "asin",
+        "acos",
+        "atan",
     ];
 
     pub fn is_builtin_name<S: AsRef<str>>(str: S) -> bool {
@@ -113,6 +162,7 @@ impl State {
 
         let mut state = State {
             str_to_id: HashMap::new(),
+            derivative_rules: HashMap::new(),
         };
 
         for x in Self::BUILTIN_SYMBOL_NAMES {
@@ -127,12 +177,6 @@ impl State {
         state
     }
 
-    /// Get the global state.
-    #[inline]
-    pub(crate) fn get_global_state() -> &'static RwLock<State> {
-        &STATE
-    }
-
     /// Initialize the global state for testing purposes by allocating
     /// variables and functions with the names v0, ..., v29, f0, ..., f29,
     /// that can be used in concurrently run unit tests without interference.
@@ -203,7 +247,10 @@ impl State {
         let mut state = STATE.write().unwrap();
 
         state.str_to_id.clear();
+        state.derivative_rules.clear();
+        HAS_DERIVATIVE_RULES.store(false, Ordering::Relaxed);
         SYMBOL_OFFSET.store(ID_TO_STR.len(), Ordering::Relaxed);
+        SYMBOL_COUNT.store(0, Ordering::Release);
 
         for x in Self::BUILTIN_SYMBOL_NAMES {
             state.get_symbol(wrap_symbol!(x));
@@ -215,6 +262,185 @@ impl State {
         }
     }
 
+    /// Create a checkpoint of the symbols that are currently defined.
+    /// Use [State::restore] to remove all symbols that were defined after the checkpoint.
+    pub fn checkpoint() -> StateCheckpoint {
+        let _state = STATE.read().unwrap();
+
+        StateCheckpoint {
+            symbol_count: SYMBOL_COUNT.load(Ordering::Acquire),
+        }
+    }
+
+    /// Remove all symbols that were defined after the `checkpoint` was created,
+    /// so that their names and identifiers can be reused. The entries of the removed
+    /// symbols are kept in the symbol table, so that references to their names remain valid.
+    /// This will invalidate all currently existing atoms that contain these symbols.
+    ///
+    /// The atoms that are still in use after the restore should be passed as `atoms`.
+    /// In debug mode, it is checked that they do not contain any of the removed symbols.
+    ///
+    /// Example:
+    /// ```
+    /// # use symbolica::{parse, symbol};
+    /// # use symbolica::state::State;
+    /// let a = parse!("x + 1").unwrap();
+    /// let checkpoint = State::checkpoint();
+    /// symbol!("f"; Symmetric).unwrap();
+    /// unsafe { State::restore(checkpoint, &[a]); }
+    /// symbol!("f"; Antisymmetric).unwrap();
+    /// ```
+    ///
+    /// # Safety
+    ///
+    /// No atom, polynomial or other structure that is used after the restore may contain
+    /// symbols that were defined after the checkpoint.
+    pub unsafe fn restore(checkpoint: StateCheckpoint, atoms: &[Atom]) {
+        if cfg!(debug_assertions) {
+            for a in atoms {
+                if let Some(s) = a
+                    .get_all_symbols(true)
+                    .into_iter()
+                    .find(|s| s.get_id() as usize >= checkpoint.symbol_count)
+                {
+                    panic!(
+                        "Cannot restore the state: symbol {} is still in use in {}",
+                        s, a
+                    );
+                }
+            }
+        }
+
+        let mut state = STATE.write().unwrap();
+
+        let symbol_count = SYMBOL_COUNT.load(Ordering::Relaxed);
+        assert!(
+            checkpoint.symbol_count <= symbol_count,
+            "The checkpoint contains symbols that are no longer defined"
+        );
+
+        state
+            .str_to_id
+            .retain(|_, s| (s.get_id() as usize) < checkpoint.symbol_count);
+        state
+            .derivative_rules
+            .retain(|(s, _), _| (s.get_id() as usize) < checkpoint.symbol_count);
+        HAS_DERIVATIVE_RULES.store(!state.derivative_rules.is_empty(), Ordering::Relaxed);
+
+        // the identifiers of the removed symbols are assigned to new entries when reused
+        SYMBOL_COUNT.store(checkpoint.symbol_count, Ordering::Release);
+    }
+
+    /// Assign the identifiers of all user-defined symbols in the order of their names,
+    /// such that the identifiers are independent of the order in which the symbols were defined.
+    /// The expressions in `atoms` are rewritten to use the new identifiers, so that their
+    /// serialization is identical between sessions that define the same symbols.
+    ///
+    /// The returned map can be used to rename other expressions using [Atom::import_with_map].
+    ///
+    /// Example:
+    /// ```
+    /// # use symbolica::{parse, symbol};
+    /// # use symbolica::state::State;
+    /// let mut atoms = [parse!("canon_b + canon_a").unwrap()];
+    /// unsafe { State::canonicalize_ids(&mut atoms) };
+    /// assert!(symbol!("canon_a").get_id() < symbol!("canon_b").get_id());
+    /// ```
+    ///
+    /// # Safety
+    ///
+    /// All atoms, polynomials and other structures that contain user-defined symbols
+    /// and that are not in `atoms` are invalidated.
+    pub unsafe fn canonicalize_ids(atoms: &mut [Atom]) -> StateMap {
+        let mut state_map = StateMap {
+            symbols: HashMap::default(),
+            finite_fields: HashMap::default(),
+            variables_lists: HashMap::default(),
+        };
+
+        let derivative_rules;
+        {
+            let mut state = STATE.write().unwrap();
+            derivative_rules = std::mem::take(&mut state.derivative_rules);
+
+            let offset = SYMBOL_OFFSET.load(Ordering::Relaxed);
+            let n_builtin = Self::BUILTIN_SYMBOL_NAMES.len();
+
+            let count = SYMBOL_COUNT.load(Ordering::Relaxed);
+
+            let mut user_symbols: Vec<_> = (offset + n_builtin..offset + count)
+                .map(|i| Self::symbol_entry(i).clone())
+                .collect();
+            user_symbols.sort_by(|a, b| a.1.name.cmp(&b.1.name));
+
+            // builtin symbols keep their identifiers, but are added to the map
+            // so that the arguments of all functions are renamed
+            for i in offset..offset + n_builtin {
+                let s = Self::symbol_entry(i).0;
+                state_map.symbols.insert(s.get_id(), s);
+            }
+
+            for (new_id, (s, d)) in (n_builtin..).zip(user_symbols) {
+                let new_symbol = Symbol::raw_fn(
+                    new_id as u32,
+                    s.get_wildcard_level(),
+                    s.is_symmetric(),
+                    s.is_antisymmetric(),
+                    s.is_cyclesymmetric(),
+                    s.is_linear(),
+                );
+
+                state.str_to_id.insert(d.name.clone(), new_symbol);
+                state_map.symbols.insert(s.get_id(), new_symbol);
+
+                let index = SYMBOL_DATA.push((new_symbol, d));
+                ID_TO_STR[offset + new_id].store(index, Ordering::Release);
+            }
+        }
+
+        let derivative_rules = derivative_rules
+            .into_iter()
+            .map(|((s, index), rule)| {
+                let s = state_map.symbols.get(&s.get_id()).copied().unwrap_or(s);
+                let (lhs, rhs) = rule.as_ref();
+                let rule = (lhs.rename(&state_map), rhs.rename(&state_map));
+                ((s, index), Arc::new(rule))
+            })
+            .collect();
+        STATE.write().unwrap().derivative_rules = derivative_rules;
+
+        // rename the variables of rational polynomial coefficients
+        for i in 0..VARIABLE_LISTS.len() {
+            let vars = VARIABLE_LISTS[i].clone();
+            let new_vars: Vec<_> = vars
+                .iter()
+                .map(|v| match v {
+                    Variable::Symbol(s) => {
+                        Variable::Symbol(state_map.symbols.get(&s.get_id()).copied().unwrap_or(*s))
+                    }
+                    Variable::Temporary(t) => Variable::Temporary(*t),
+                    Variable::Function(s, f) => Variable::Function(
+                        state_map.symbols.get(&s.get_id()).copied().unwrap_or(*s),
+                        Arc::new(f.as_view().rename(&state_map)),
+                    ),
+                    Variable::Other(f) => Variable::Other(Arc::new(f.as_view().rename(&state_map))),
+                })
+                .collect();
+
+            if new_vars != *vars {
+                let new_vars = Arc::new(new_vars);
+                State::get_or_insert_variable_list(new_vars.clone());
+                state_map.variables_lists.insert(i as u64, new_vars);
+            }
+        }
+
+        for a in atoms {
+            *a = a.as_view().rename(&state_map);
+        }
+
+        state_map
+    }
+
     #[inline(always)]
     #[allow(dead_code)]
     pub(crate) unsafe fn symbol_from_id(id: u32) -> Symbol {
@@ -222,7 +448,13 @@ impl State {
             let _ = *STATE; // initialize the state
         }
 
-        ID_TO_STR[id as usize].0
+        Self::symbol_entry(id as usize).0
+    }
+
+    /// Get the entry in the symbol table at position `index`, which includes the offset.
+    #[inline(always)]
+    fn symbol_entry(index: usize) -> &'static (Symbol, SymbolData) {
+        &SYMBOL_DATA[ID_TO_STR[index].load(Ordering::Acquire)]
     }
 
     /// Iterate over all defined symbols.
@@ -234,7 +466,67 @@ impl State {
         ID_TO_STR
             .iter()
             .skip(SYMBOL_OFFSET.load(Ordering::Relaxed))
-            .map(|s| (s.0, s.1.name.as_str()))
+            .take(SYMBOL_COUNT.load(Ordering::Acquire))
+            .map(|i| {
+                let (s, d) = &SYMBOL_DATA[i.load(Ordering::Acquire)];
+                (*s, d.name.as_str())
+            })
+    }
+
+    /// Register the derivative of the function in `lhs` with respect to its argument
+    /// at position `arg_index`. When a derivative is taken of a function that matches `lhs`,
+    /// the wildcards in `rule` are substituted and the chain rule is applied.
+    ///
+    /// If the pattern `lhs` is not a function, an error is returned.
+    ///
+    /// Example:
+    /// ```
+    /// # use symbolica::{atom::AtomCore, parse, state::State, symbol};
+    /// State::register_derivative(
+    ///     parse!("W(x_)").unwrap().to_pattern(),
+    ///     0,
+    ///     parse!("W(x_)/(x_*(1+W(x_)))").unwrap().to_pattern(),
+    /// )
+    /// .unwrap();
+    ///
+    /// let r = parse!("W(x^2)").unwrap().derivative(symbol!("x"));
+    /// assert_eq!(r, parse!("2*x*W(x^2)/(x^2*(1+W(x^2)))").unwrap());
+    /// ```
+    pub fn register_derivative(
+        lhs: Pattern,
+        arg_index: usize,
+        rule: Pattern,
+    ) -> Result<(), String> {
+        let Pattern::Fn(fun, _) = &lhs else {
+            return Err("The left-hand side of a derivative rule must be a function".into());
+        };
+
+        STATE
+            .write()
+            .unwrap()
+            .derivative_rules
+            .insert((*fun, arg_index), Arc::new((lhs, rule)));
+        HAS_DERIVATIVE_RULES.store(true, Ordering::Relaxed);
+        Ok(())
+    }
+
+    /// Get the registered derivative rule of function `fun` with respect to
+    /// its argument at position `arg_index`.
+    pub(crate) fn get_derivative_rule(
+        fun: Symbol,
+        arg_index: usize,
+    ) -> Option<Arc<(Pattern, Pattern)>> {
+        // avoid taking the lock when no rules are registered
+        if !HAS_DERIVATIVE_RULES.load(Ordering::Relaxed) {
+            return None;
+        }
+
+        STATE
+            .read()
+            .unwrap()
+            .derivative_rules
+            .get(&(fun, arg_index))
+            .cloned()
     }
 
     /// Returns `true` iff this identifier is defined by Symbolica.
@@ -242,14 +534,29 @@ impl State {
         id.get_id() < Self::BUILTIN_SYMBOL_NAMES.len() as u32
     }
 
+    /// Add a new symbol to the symbol table, reusing the identifier of a removed symbol if possible.
+    /// Must only be called while holding the write lock on the state.
+    fn push_symbol(symbol: Symbol, data: SymbolData) {
+        let data_index = SYMBOL_DATA.push((symbol, data));
+
+        let index = SYMBOL_OFFSET.load(Ordering::Relaxed) + SYMBOL_COUNT.load(Ordering::Relaxed);
+        if index < ID_TO_STR.len() {
+            // the identifier belonged to a symbol that was removed by `restore`
+            ID_TO_STR[index].store(data_index, Ordering::Release);
+        } else {
+            ID_TO_STR.push(AtomicUsize::new(data_index));
+        }
+
+        SYMBOL_COUNT.fetch_add(1, Ordering::Release);
+    }
+
     /// Get the symbol for a certain name if the name is already registered,
     /// else register it and return a new symbol without attributes.
     pub(crate) fn get_symbol(&mut self, name: NamespacedSymbol) -> Symbol {
         match self.str_to_id.entry(name.symbol.into()) {
             Entry::Occupied(o) => *o.get(),
             Entry::Vacant(v) => {
-                let offset = SYMBOL_OFFSET.load(Ordering::Relaxed);
-                if ID_TO_STR.len() - offset == u32::MAX as usize - 1 {
+                if SYMBOL_COUNT.load(Ordering::Relaxed) == u32::MAX as usize - 1 {
                     panic!("Too many variables defined");
                 }
 
@@ -263,9 +570,9 @@ impl State {
 
                 // there is no synchronization issue since only one thread can insert at a time
                 // as the state itself is behind a mutex
-                let id = ID_TO_STR.len() - offset;
+                let id = SYMBOL_COUNT.load(Ordering::Relaxed);
                 let new_symbol = Symbol::raw_var(id as u32, wildcard_level);
-                let id_ret = ID_TO_STR.push((
+                Self::push_symbol(
                     new_symbol,
                     SymbolData {
                         name: v.key().clone(),
@@ -275,8 +582,7 @@ impl State {
                         custom_normalization: None,
                         custom_print: None,
                     },
-                )) - offset;
-                assert_eq!(id, id_ret);
+                );
 
                 v.insert(new_symbol);
                 new_symbol
@@ -284,6 +590,19 @@ impl State {
         }
     }
 
+    /// Get the symbol for a certain name if the name is already registered,
+    /// else register it and return a new symbol without attributes.
+    ///
+    /// Registered symbols are looked up using a shared lock on the global state,
+    /// so that multiple threads can obtain symbols simultaneously.
+    pub(crate) fn get_or_insert_symbol(name: NamespacedSymbol) -> Symbol {
+        if let Some(s) = STATE.read().unwrap().str_to_id.get(name.symbol.as_ref()) {
+            return *s;
+        }
+
+        STATE.write().unwrap().get_symbol(name)
+    }
+
     pub(crate) fn get_state_mut() -> RwLockWriteGuard<'static, State> {
         STATE.write().unwrap()
     }
@@ -317,7 +636,7 @@ impl State {
                 if r == new_id && normalization_function.is_none() {
                     Ok(r)
                 } else {
-                    let data = &ID_TO_STR[r.get_id() as usize].1;
+                    let data = &Self::symbol_entry(r.get_id() as usize).1;
                     if data.file.is_empty() {
                         return Err(
                             format!("Symbol {} redefined with new attributes.", data.name).into(),
@@ -328,14 +647,13 @@ impl State {
                 }
             }
             Entry::Vacant(v) => {
-                let offset = SYMBOL_OFFSET.load(Ordering::Relaxed);
-                if ID_TO_STR.len() - offset == u32::MAX as usize - 1 {
+                if SYMBOL_COUNT.load(Ordering::Relaxed) == u32::MAX as usize - 1 {
                     panic!("Too many variables defined");
                 }
 
                 // there is no synchronization issue since only one thread can insert at a time
                 // as the state itself is behind a mutex
-                let id = ID_TO_STR.len() - offset;
+                let id = SYMBOL_COUNT.load(Ordering::Relaxed);
 
                 let mut wildcard_level = 0;
                 for x in v.key().chars().rev() {
@@ -354,18 +672,17 @@ impl State {
                     attributes.contains(&FunctionAttribute::Linear),
                 );
 
-                let id_ret = ID_TO_STR.push((
+                Self::push_symbol(
                     new_symbol,
                     SymbolData {
                         name: v.key().clone(),
                         file: name.file,
                         namespace: name.namespace,
                         line: name.line,
-                        custom_normalization: normalization_function,
-                        custom_print: print_function,
+                        custom_normalization: normalization_function.map(Arc::new),
+                        custom_print: print_function.map(Arc::new),
                     },
-                )) - offset;
-                assert_eq!(id, id_ret);
+                );
 
                 v.insert(new_symbol);
 
@@ -381,7 +698,7 @@ impl State {
             let _ = *STATE; // initialize the state
         }
 
-        &ID_TO_STR[id.get_id() as usize + SYMBOL_OFFSET.load(Ordering::Relaxed)]
+        &Self::symbol_entry(id.get_id() as usize + SYMBOL_OFFSET.load(Ordering::Relaxed))
             .1
             .name
     }
@@ -393,7 +710,7 @@ impl State {
             let _ = *STATE; // initialize the state
         }
 
-        ID_TO_STR[id.get_id() as usize + SYMBOL_OFFSET.load(Ordering::Relaxed)]
+        Self::symbol_entry(id.get_id() as usize + SYMBOL_OFFSET.load(Ordering::Relaxed))
             .1
             .namespace
             .as_ref()
@@ -406,7 +723,7 @@ impl State {
             let _ = *STATE; // initialize the state
         }
 
-        &ID_TO_STR[id.get_id() as usize + SYMBOL_OFFSET.load(Ordering::Relaxed)].1
+        &Self::symbol_entry(id.get_id() as usize + SYMBOL_OFFSET.load(Ordering::Relaxed)).1
     }
 
     /// Get the user-specified normalization function for the symbol.
@@ -416,10 +733,10 @@ impl State {
             let _ = *STATE; // initialize the state
         }
 
-        ID_TO_STR[id.get_id() as usize + SYMBOL_OFFSET.load(Ordering::Relaxed)]
+        Self::symbol_entry(id.get_id() as usize + SYMBOL_OFFSET.load(Ordering::Relaxed))
             .1
             .custom_normalization
-            .as_ref()
+            .as_deref()
     }
 
     pub(crate) fn get_finite_field(fi: FiniteFieldIndex) -> &'static Zp64 {
@@ -473,9 +790,7 @@ impl State {
         dest.write_u32::<LittleEndian>(SYMBOLICA_MAGIC)?;
         dest.write_u16::<LittleEndian>(EXPORT_FORMAT_VERSION)?;
 
-        dest.write_u64::<LittleEndian>(
-            ID_TO_STR.len() as u64 - SYMBOL_OFFSET.load(Ordering::Relaxed) as u64,
-        )?;
+        dest.write_u64::<LittleEndian>(SYMBOL_COUNT.load(Ordering::Acquire) as u64)?;
 
         for (s, n) in State::symbol_iter() {
             dest.write_u32::<LittleEndian>(n.as_bytes().len() as u32)?;
@@ -657,7 +972,7 @@ impl State {
                         if let Some(new_id) = state_map.symbols.get(&id) {
                             variables.push(Variable::Symbol(*new_id));
                         } else {
-                            variables.push(Variable::Symbol(ID_TO_STR[id as usize].0))
+                            variables.push(Variable::Symbol(Self::symbol_entry(id as usize).0))
                         }
                     }
                     1 => {
@@ -669,7 +984,7 @@ impl State {
                         let symb = if let Some(new_id) = state_map.symbols.get(&id) {
                             *new_id
                         } else {
-                            ID_TO_STR[id as usize].0
+                            Self::symbol_entry(id as usize).0
                         };
 
                         let mut f = Atom::new();
@@ -888,6 +1203,45 @@ mod tests {
         assert!(i.is_empty());
     }
 
+    #[test]
+    fn concurrent_symbols() {
+        let inputs: Vec<_> = (0..50)
+            .map(|i| {
+                format!(
+                    "concurrent_x_{} * concurrent_f_{}(concurrent_x_{}, concurrent_y) + 1",
+                    i,
+                    i % 5,
+                    (i + 1) % 50
+                )
+            })
+            .collect();
+
+        let results: Vec<Vec<_>> = std::thread::scope(|s| {
+            let handles: Vec<_> = (0..4)
+                .map(|t| {
+                    let inputs = &inputs;
+                    s.spawn(move || {
+                        let mut r: Vec<_> = (0..inputs.len())
+                            .cycle()
+                            .skip(t * 7)
+                            .take(inputs.len())
+                            .map(|i| (i, parse!(&inputs[i]).unwrap()))
+                            .collect();
+                        r.sort_by_key(|(i, _)| *i);
+                        r
+                    })
+                })
+                .collect();
+
+            handles.into_iter().map(|h| h.join().unwrap()).collect()
+        });
+
+        assert!(results.iter().all(|r| r == &results[0]));
+        for (i, a) in &results[0] {
+            assert_eq!(a, &parse!(&inputs[*i]).unwrap());
+        }
+    }
+
     #[test]
     fn custom_normalization() {
         let _real_log = symbol!(
@@ -32,6 +32,11 @@ impl<T: Clone + Send + Sync + Fn(AtomView<'_>, &mut Atom) -> Result<(), Transfor
 {
 }
 
+/// A function that maps a number to another number.
+pub trait MapNumber: Fn(&Coefficient) -> Coefficient + DynClone + Send + Sync {}
+dyn_clone::clone_trait_object!(MapNumber);
+impl<T: Clone + Send + Sync + Fn(&Coefficient) -> Coefficient> MapNumber for T {}
+
 /// Options for printing statistics of operations used in
 /// [Transformer::Stats].
 #[derive(Clone, Debug)]
@@ -158,6 +163,9 @@ pub enum Transformer {
     Linearize(Option<Vec<Symbol>>),
     /// Map the rhs with a user-specified function.
     Map(Box<dyn Map>),
+    /// Map the rhs with a user-specified function if it is a number.
+    /// Other inputs are left unchanged.
+    MapNumber(Box<dyn MapNumber>),
     /// Apply a transformation to each argument of the `arg()` function.
     /// If the input is not `arg()`, map the current input.
     ForEach(Vec<Transformer>),
@@ -165,7 +173,17 @@ pub enum Transformer {
     MapTerms(Vec<Transformer>, Option<Arc<ThreadPool>>),
     /// Split a `Mul` or `Add` into a list of arguments.
     Split,
+    /// Partition the arguments of `arg()` into bins with the given names and sizes.
+    /// The result is the sum of all distinct partitions, where each term is a product
+    /// of the bins weighted by its multiplicity. If the first flag is set, the last bin
+    /// collects all left-over arguments and its size is ignored. If the second flag is set,
+    /// the bins are repeated until all arguments are placed.
     Partition(Vec<(Symbol, usize)>, bool, bool),
+    /// Partition the arguments of `arg()` into bins with the given names, where
+    /// the size of each bin is given by the leading arguments, i.e.,
+    /// `arg(2, 1, x1, x2, x3)` is partitioned into bins of size 2 and 1.
+    /// This allows the sizes to come from wildcards matched at runtime.
+    PartitionWithSizes(Vec<Symbol>),
     Sort,
     CycleSymmetrize,
     Deduplicate,
@@ -207,6 +225,7 @@ impl std::fmt::Debug for Transformer {
             Transformer::ArgCount(p) => f.debug_tuple("ArgCount").field(p).finish(),
             Transformer::Linearize(s) => f.debug_tuple("Linearize").field(s).finish(),
             Transformer::Map(_) => f.debug_tuple("Map").finish(),
+            Transformer::MapNumber(_) => f.debug_tuple("MapNumber").finish(),
             Transformer::MapTerms(v, c) => f.debug_tuple("Map").field(v).field(c).finish(),
             Transformer::ForEach(t) => f.debug_tuple("ForEach").field(t).finish(),
             Transformer::Split => f.debug_tuple("Split").finish(),
@@ -216,6 +235,9 @@ impl std::fmt::Debug for Transformer {
                 .field(b1)
                 .field(b2)
                 .finish(),
+            Transformer::PartitionWithSizes(g) => {
+                f.debug_tuple("PartitionWithSizes").field(g).finish()
+            }
             Transformer::Sort => f.debug_tuple("Sort").finish(),
             Transformer::CycleSymmetrize => f.debug_tuple("CycleSymmetrize").finish(),
             Transformer::Deduplicate => f.debug_tuple("Deduplicate").finish(),
@@ -421,6 +443,49 @@ impl Transformer {
         Transformer::Partition(vec![partition], false, true)
     }
 
+    /// Write the sum of all partitions of `args` into `bins` to `out`.
+    fn partition_into(
+        args: &[AtomView],
+        bins: &[(Symbol, usize)],
+        fill_last: bool,
+        repeat: bool,
+        workspace: &Workspace,
+        out: &mut Atom,
+    ) {
+        let partitions = partitions(args, bins, fill_last, repeat);
+
+        if partitions.is_empty() {
+            out.set_from_view(&workspace.new_num(0).as_view());
+            return;
+        }
+
+        let mut sum_h = workspace.new_atom();
+        let sum = sum_h.to_add();
+
+        for (p, args) in partitions {
+            let mut mul_h = workspace.new_atom();
+            let mul = mul_h.to_mul();
+
+            if !p.is_one() {
+                mul.extend(workspace.new_num(p).as_view());
+            }
+
+            for (name, f_args) in args {
+                let mut fun_h = workspace.new_atom();
+                let fun = fun_h.to_fun(name);
+                for x in f_args {
+                    fun.add_arg(x);
+                }
+
+                mul.extend(fun_h.as_view());
+            }
+
+            sum.extend(mul_h.as_view());
+        }
+
+        sum_h.as_view().normalize(workspace, out);
+    }
+
     /// Apply the transformer to `input`.
     pub fn execute(&self, input: AtomView<'_>) -> Result<Atom, TransformerError> {
         let mut a = Atom::new();
@@ -490,6 +555,13 @@ impl Transformer {
                 Transformer::Map(f) => {
                     f(cur_input, out)?;
                 }
+                Transformer::MapNumber(f) => {
+                    if let AtomView::Num(n) = cur_input {
+                        out.to_num(f(&n.get_coeff_view().to_owned()));
+                    } else {
+                        std::mem::swap(out, &mut tmp);
+                    }
+                }
                 Transformer::MapTerms(t, p) => {
                     if let Some(p) = p {
                         *out = cur_input.map_terms_with_pool(
@@ -710,39 +782,50 @@ impl Transformer {
                     if let AtomView::Fun(f) = cur_input {
                         if f.get_symbol() == Atom::ARG {
                             let args: Vec<_> = f.iter().collect();
+                            Self::partition_into(&args, bins, *fill_last, *repeat, workspace, out);
+                            continue;
+                        }
+                    }
 
-                            let mut sum_h = workspace.new_atom();
-                            let sum = sum_h.to_add();
-
-                            let partitions = partitions(&args, bins, *fill_last, *repeat);
-
-                            if partitions.is_empty() {
-                                out.set_from_view(&workspace.new_num(0).as_view());
-                                continue;
+                    std::mem::swap(out, &mut tmp);
+                }
+                Transformer::PartitionWithSizes(names) => {
+                    if let AtomView::Fun(f) = cur_input {
+                        if f.get_symbol() == Atom::ARG {
+                            let args: Vec<_> = f.iter().collect();
+                            if args.len() < names.len() {
+                                return Err(TransformerError::ValueError(format!(
+                                    "Expected {} partition sizes in {}",
+                                    names.len(),
+                                    cur_input
+                                )));
                             }
 
-                            for (p, args) in partitions {
-                                let mut mul_h = workspace.new_atom();
-                                let mul = mul_h.to_mul();
-
-                                if !p.is_one() {
-                                    mul.extend(workspace.new_num(p).as_view());
-                                }
-
-                                for (name, f_args) in args {
-                                    let mut fun_h = workspace.new_atom();
-                                    let fun = fun_h.to_fun(name);
-                                    for x in f_args {
-                                        fun.add_arg(x);
-                                    }
-
-                                    mul.extend(fun_h.as_view());
-                                }
-
-                                sum.extend(mul_h.as_view());
+                            let mut bins = Vec::with_capacity(names.len());
+                            for (name, size) in names.iter().zip(&args) {
+                                let Ok(size) = i64::try_from(*size) else {
+                                    return Err(TransformerError::ValueError(format!(
+                                        "Partition size {} is not an integer",
+                                        size
+                                    )));
+                                };
+                                let Ok(size) = usize::try_from(size) else {
+                                    return Err(TransformerError::ValueError(format!(
+                                        "Partition size {} is negative",
+                                        size
+                                    )));
+                                };
+                                bins.push((*name, size));
                             }
 
-                            sum_h.as_view().normalize(workspace, out);
+                            Self::partition_into(
+                                &args[names.len()..],
+                                &bins,
+                                false,
+                                false,
+                                workspace,
+                                out,
+                            );
                             continue;
                         }
                     }
@@ -912,7 +995,8 @@ impl Transformer {
 mod test {
     use crate::{
         atom::{Atom, AtomCore, FunctionBuilder},
-        id::{Condition, Match, MatchSettings, WildcardRestriction},
+        coefficient::Coefficient,
+        id::{Condition, Match, MatchSettings, Pattern, WildcardRestriction},
         parse,
         printer::PrintOptions,
         state::Workspace,
@@ -963,6 +1047,88 @@ mod test {
         assert_eq!(out, r);
     }
 
+    #[test]
+    fn sum_captured_args() {
+        for (input, res) in [
+            ("f(1,2,3)", "6"),
+            ("f(1/2,1/3)", "5/6"),
+            ("f(v1,2,v1)", "2*v1+2"),
+        ] {
+            let rhs = Pattern::Transformer(Box::new((
+                Some(parse!("x__").unwrap().to_pattern()),
+                vec![Transformer::Sum],
+            )));
+
+            let out = parse!(input)
+                .unwrap()
+                .replace(parse!("f(x__)").unwrap())
+                .with(rhs);
+            assert_eq!(out, parse!(res).unwrap());
+        }
+    }
+
+    #[test]
+    fn product_captured_args() {
+        let p = parse!("f(2,3,4)").unwrap();
+        let rhs = Pattern::Transformer(Box::new((
+            Some(parse!("x__").unwrap().to_pattern()),
+            vec![Transformer::Product],
+        )));
+
+        let out = p.replace(parse!("f(x__)").unwrap()).with(rhs);
+        assert_eq!(out, Atom::new_num(24));
+    }
+
+    #[test]
+    fn count_captured_args() {
+        let p = parse!("f(1,2,3,4)").unwrap();
+        let rhs = Pattern::Transformer(Box::new((
+            Some(parse!("x__").unwrap().to_pattern()),
+            vec![Transformer::ArgCount(true)],
+        )));
+
+        let out = p.replace(parse!("f(x__)").unwrap()).with(rhs);
+        assert_eq!(out, Atom::new_num(4));
+    }
+
+    #[test]
+    fn partition_with_sizes() {
+        let (g1, g2) = symbol!("g1", "g2");
+        let p = parse!("f(2,1,v1,v2,v3)").unwrap();
+        let rhs = Pattern::Transformer(Box::new((
+            Some(parse!("arg(n1_,n2_,x__)").unwrap().to_pattern()),
+            vec![Transformer::PartitionWithSizes(vec![g1, g2])],
+        )));
+
+        let out = p.replace(parse!("f(n1_,n2_,x__)").unwrap()).with(rhs);
+
+        let r = parse!("f(v1,v2,v3)")
+            .unwrap()
+            .replace(parse!("f(x__)").unwrap())
+            .with(Pattern::Transformer(Box::new((
+                Some(parse!("x__").unwrap().to_pattern()),
+                vec![Transformer::Partition(vec![(g1, 2), (g2, 1)], false, false)],
+            ))));
+        assert_eq!(out, r);
+        assert_eq!(out.nterms(), 3);
+    }
+
+    #[test]
+    fn partition_count() {
+        let f = symbol!("f");
+        let p = parse!("arg(v1,v2,v3,v4)").unwrap();
+
+        let out = Transformer::Partition(vec![(f, 2), (f, 2)], false, false)
+            .execute(p.as_view())
+            .unwrap();
+        assert_eq!(out.nterms(), 3);
+
+        let out = Transformer::Partition(vec![(f, 1)], false, true)
+            .execute(p.as_view())
+            .unwrap();
+        assert_eq!(out, parse!("f(v1)*f(v2)*f(v3)*f(v4)").unwrap());
+    }
+
     #[test]
     fn product_series() {
         let p = parse!("arg(v1,v1+1,3)").unwrap();
@@ -1019,6 +1185,41 @@ mod test {
         assert_eq!(out, r);
     }
 
+    #[test]
+    fn map_number() {
+        let p = parse!("f1(3,v1,1/2)").unwrap();
+
+        let mut out = Atom::new();
+        Workspace::get_local().with(|ws| {
+            Transformer::execute_chain(
+                p.as_view(),
+                &[
+                    Transformer::ReplaceAll(
+                        parse!("f1(x__)").unwrap().to_pattern(),
+                        parse!("x__").unwrap().to_pattern().into(),
+                        Condition::default(),
+                        MatchSettings::default(),
+                    ),
+                    Transformer::ForEach(vec![Transformer::MapNumber(Box::new(
+                        |n: &Coefficient| -n.clone(),
+                    ))]),
+                    Transformer::Map(Box::new(|x, out| {
+                        let mut f = FunctionBuilder::new(symbol!("f1"));
+                        f = f.add_arg(x);
+                        *out = f.finish();
+                        Ok(())
+                    })),
+                ],
+                ws,
+                &mut out,
+            )
+            .unwrap()
+        });
+
+        let r = parse!("f1(-3,v1,-1/2)").unwrap();
+        assert_eq!(out, r);
+    }
+
     #[test]
     fn deep_nesting() {
         let p = parse!("arg(3,2,1,3)").unwrap();
@@ -294,6 +294,39 @@ class Expression:
     LOG: Expression
     """The built-in logarithm function."""
 
+    ATAN2: Expression
+    """The built-in two-argument arctangent function `atan2(y, x)`."""
+
+    MAX: Expression
+    """The built-in function `max` that yields the largest of its arguments."""
+
+    MIN: Expression
+    """The built-in function `min` that yields the smallest of its arguments."""
+
+    INTEGRATE: Expression
+    """The built-in function `integrate(f, x)` that represents an unevaluated integral."""
+
+    TAN: Expression
+    """The built-in tangent function."""
+
+    SINH: Expression
+    """The built-in hyperbolic sine function."""
+
+    COSH: Expression
+    """The built-in hyperbolic cosine function."""
+
+    TANH: Expression
+    """The built-in hyperbolic tangent function."""
+
+    ASIN: Expression
+    """The built-in inverse sine function."""
+
+    ACOS: Expression
+    """The built-in inverse cosine function."""
+
+    ATAN: Expression
+    """The built-in inverse tangent function."""
+
     @overload
     @classmethod
     def symbol(_cls,
@@ -572,6 +605,7 @@ class Expression:
         latex: bool = False,
         show_namespaces: bool = False,
         max_terms: Optional[int] = 100,
+        pretty_derivatives: bool = False,
     ) -> str:
         """
         Convert the expression into a human-readable string, with tunable settings.
@@ -2460,6 +2494,7 @@ class Transformer:
         latex: bool = False,
         show_namespaces: bool = False,
         max_terms: Optional[int] = None,
+        pretty_derivatives: bool = False,
     ) -> Transformer:
         """
         Create a transformer that prints the expression.
@@ -2598,6 +2633,7 @@ class Series:
         precision: Optional[int] = None,
         show_namespaces: bool = False,
         max_terms: Optional[int] = None,
+        pretty_derivatives: bool = False,
     ) -> str:
         """
         Convert the series into a human-readable string.
@@ -2802,6 +2838,7 @@ class Polynomial:
         precision: Optional[int] = None,
         show_namespaces: bool = False,
         max_terms: Optional[int] = None,
+        pretty_derivatives: bool = False,
     ) -> str:
         """
         Convert the polynomial into a human-readable string, with tunable settings.
@@ -3092,6 +3129,7 @@ class NumberFieldPolynomial:
         precision: Optional[int] = None,
         show_namespaces: bool = False,
         max_terms: Optional[int] = None,
+        pretty_derivatives: bool = False,
     ) -> str:
         """
         Convert the polynomial into a human-readable string, with tunable settings.
@@ -3338,6 +3376,7 @@ class FiniteFieldPolynomial:
         precision: Optional[int] = None,
         show_namespaces: bool = False,
         max_terms: Optional[int] = None,
+        pretty_derivatives: bool = False,
     ) -> str:
         """
         Convert the polynomial into a human-readable string, with tunable settings.
@@ -3819,6 +3858,7 @@ class Matrix:
         precision: Optional[int] = None,
         show_namespaces: bool = False,
         max_terms: Optional[int] = None,
+        pretty_derivatives: bool = False,
     ) -> str:
         """
         Convert the matrix into a human-readable string, with tunable settings.
@@ -3887,6 +3927,7 @@ class Evaluator:
         - `('pow', ('out', 0), ('param', 0), -1)` which means `out[0] = param[0]^-1`.
         - `('powf', ('out', 0), ('param', 0), ('param', 1))` which means `out[0] = param[0]^param[1]`.
         - `('fun', ('temp', 1), cos, ('param', 0))` which means `temp[1] = cos(param[0])`.
+        - `('fun', ('temp', 1), atan2, ('param', 0), ('param', 1))` which means `temp[1] = atan2(param[0], param[1])`.
 
         Examples
         --------
@@ -0,0 +1,69 @@
+use std::sync::Arc;
+
+use symbolica::{
+    atom::{Atom, AtomCore},
+    parse,
+    state::State,
+    symbol,
+};
+
+fn canonical_export(input: &str) -> (Vec<u8>, Atom) {
+    let mut atoms = [parse!(input).unwrap()];
+    unsafe { State::canonicalize_ids(&mut atoms) };
+
+    let mut export = vec![];
+    atoms[0].as_view().write(&mut export).unwrap();
+    let [a] = atoms;
+    (export, a)
+}
+
+fn ids() -> Vec<u32> {
+    let (x, y, z) = symbol!("x", "y", "z");
+    let f = symbol!("f"; Symmetric).unwrap();
+    vec![x.get_id(), y.get_id(), z.get_id(), f.get_id()]
+}
+
+#[test]
+fn insertion_order_independence() {
+    symbol!("z", "y", "x");
+    symbol!("f"; Symmetric).unwrap();
+    let (e1, a1) = canonical_export("f(x, y)*z^2 + y");
+    let ids1 = ids();
+
+    unsafe { State::reset() };
+
+    symbol!("f"; Symmetric).unwrap();
+    symbol!("x", "y", "z");
+    let (e2, a2) = canonical_export("f(x, y)*z^2 + y");
+    let ids2 = ids();
+
+    assert_eq!(ids1, ids2);
+    assert_eq!(e1, e2);
+    assert_eq!(a1, a2);
+    assert_eq!(a2, parse!("f(x, y)*z^2 + y").unwrap());
+
+    // symbols in rational polynomial coefficients are renamed as well
+    let mut atoms = [parse!("y/(1+x)")
+        .unwrap()
+        .set_coefficient_ring(&Arc::new(vec![symbol!("x").into()]))];
+    symbol!("a");
+    unsafe { State::canonicalize_ids(&mut atoms) };
+    assert_eq!(
+        atoms[0].set_coefficient_ring(&Arc::new(vec![])),
+        parse!("y/(1+x)").unwrap()
+    );
+
+    // registered derivative rules are renamed as well
+    State::register_derivative(
+        parse!("w(x_)").unwrap().to_pattern(),
+        0,
+        parse!("w(x_)^2").unwrap().to_pattern(),
+    )
+    .unwrap();
+    symbol!("b");
+    unsafe { State::canonicalize_ids(&mut []) };
+    assert_eq!(
+        parse!("w(x^2)").unwrap().derivative(symbol!("x")),
+        parse!("2*x*w(x^2)^2").unwrap()
+    );
+}
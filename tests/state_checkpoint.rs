@@ -0,0 +1,29 @@
+use symbolica::{parse, state::State, symbol};
+
+#[test]
+fn restore_drops_symbols() {
+    let a = parse!("checkpoint_x + 1").unwrap();
+
+    let checkpoint = State::checkpoint();
+    let f = symbol!("checkpoint_f"; Symmetric).unwrap();
+    let f_name = f.get_name().to_string();
+    let f_name_ref = f.get_name();
+    let _ = parse!("checkpoint_f(checkpoint_y)").unwrap();
+
+    unsafe { State::restore(checkpoint, &[a.clone()]) };
+
+    assert!(State::symbol_iter().all(|(_, n)| n != f_name));
+    assert_eq!(State::checkpoint(), checkpoint);
+
+    // the identifiers of the removed symbols are reused
+    let f2 = symbol!("checkpoint_f"; Antisymmetric).unwrap();
+    assert_eq!(f.get_id(), f2.get_id());
+    assert!(f2.is_antisymmetric());
+
+    // references to the data of removed symbols remain valid
+    let g = symbol!("checkpoint_g");
+    assert_eq!(f_name_ref, f_name);
+    assert_ne!(g.get_name(), f_name);
+
+    assert_eq!(a, parse!("checkpoint_x + 1").unwrap());
+}